@@ -1,5 +1,7 @@
 #!/bin/bash
 
+# the positions are also available as test suite, see suites/analyze.epd
+
 #
 # BLACK TO MOVE
 #
@@ -24,3 +24,5 @@ hyperfine \
     "$BENCH_COMMAND" \
     --export-json "$EXPORT_PATH_NAME" \
     --warmup 1
+
+cargo run --release -- bench > "${EXPORT_PATH_NAME%.json}_bench.txt"
@@ -1,11 +1,175 @@
 use std::fmt::Debug;
+use std::fmt::Display;
 use std::ops::BitAnd;
+use std::ops::BitAndAssign;
 use std::ops::BitOr;
+use std::ops::BitOrAssign;
+use std::ops::BitXor;
+use std::ops::BitXorAssign;
+use std::ops::Not;
 use std::ops::Shl;
+use std::ops::ShlAssign;
+use std::ops::Shr;
+use std::ops::ShrAssign;
 
-#[derive(Clone, Copy, PartialEq)]
+use crate::model::file::File;
+use crate::model::rank::Rank;
+use crate::model::square::Square;
+use crate::model::types::SquareIndex;
+
+/// A set of squares, bit `i` stands for the square with index `i` (a1 = 0,
+/// h8 = 63).
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 pub struct Bitboard(pub u64);
 
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(0);
+    pub const FULL: Bitboard = Bitboard(u64::MAX);
+
+    pub const FILE_A: Bitboard = Bitboard(0x0101010101010101);
+    pub const FILE_B: Bitboard = Bitboard(Self::FILE_A.0 << 1);
+    pub const FILE_C: Bitboard = Bitboard(Self::FILE_A.0 << 2);
+    pub const FILE_D: Bitboard = Bitboard(Self::FILE_A.0 << 3);
+    pub const FILE_E: Bitboard = Bitboard(Self::FILE_A.0 << 4);
+    pub const FILE_F: Bitboard = Bitboard(Self::FILE_A.0 << 5);
+    pub const FILE_G: Bitboard = Bitboard(Self::FILE_A.0 << 6);
+    pub const FILE_H: Bitboard = Bitboard(Self::FILE_A.0 << 7);
+
+    pub const RANK_1: Bitboard = Bitboard(0xFF);
+    pub const RANK_2: Bitboard = Bitboard(Self::RANK_1.0 << 8);
+    pub const RANK_3: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 2));
+    pub const RANK_4: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 3));
+    pub const RANK_5: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 4));
+    pub const RANK_6: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 5));
+    pub const RANK_7: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 6));
+    pub const RANK_8: Bitboard = Bitboard(Self::RANK_1.0 << (8 * 7));
+
+    /// The diagonal from a1 to h8.
+    pub const DIAGONAL_A1_H8: Bitboard = Bitboard(0x8040201008040201);
+    /// The anti-diagonal from a8 to h1.
+    pub const ANTI_DIAGONAL_A8_H1: Bitboard = Bitboard(0x0102040810204080);
+
+    pub const fn from_square(square: SquareIndex) -> Bitboard {
+        Bitboard(1 << square)
+    }
+
+    pub const fn file(file: File) -> Bitboard {
+        Bitboard(Self::FILE_A.0 << file.index())
+    }
+
+    pub const fn rank(rank: Rank) -> Bitboard {
+        Bitboard(Self::RANK_1.0 << (8 * rank.index()))
+    }
+
+    /// The diagonal in the direction of a1-h8 through the square.
+    pub const fn diagonal(square: Square) -> Bitboard {
+        let ranks = square.rank().index() as i32 - square.file().index() as i32;
+        Self::shift_ranks(Self::DIAGONAL_A1_H8, ranks)
+    }
+
+    /// The anti-diagonal in the direction of a8-h1 through the square.
+    pub const fn anti_diagonal(square: Square) -> Bitboard {
+        let ranks =
+            square.rank().index() as i32 + square.file().index() as i32 - 7;
+        Self::shift_ranks(Self::ANTI_DIAGONAL_A8_H1, ranks)
+    }
+
+    /// Moves all squares `ranks` to the north (south if negative), squares
+    /// leaving the board are dropped.
+    const fn shift_ranks(bb: Bitboard, ranks: i32) -> Bitboard {
+        if ranks >= 0 {
+            Bitboard(bb.0 << (8 * ranks))
+        } else {
+            Bitboard(bb.0 >> (-8 * ranks))
+        }
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub const fn has_more_than_one(self) -> bool {
+        self.0 & self.0.wrapping_sub(1) != 0
+    }
+
+    pub const fn contains(self, square: SquareIndex) -> bool {
+        self.0 & 1 << square != 0
+    }
+
+    pub const fn set(&mut self, square: SquareIndex) {
+        self.0 |= 1 << square;
+    }
+
+    pub const fn clear(&mut self, square: SquareIndex) {
+        self.0 &= !(1 << square);
+    }
+
+    /// The lowest set square, if any.
+    pub const fn first(self) -> Option<SquareIndex> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.0.trailing_zeros() as SquareIndex)
+        }
+    }
+
+    /// The highest set square, if any.
+    pub const fn last(self) -> Option<SquareIndex> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(63 - self.0.leading_zeros() as SquareIndex)
+        }
+    }
+}
+
+/// Yields the set squares from a1 to h8.
+pub struct BitboardIter(u64);
+
+impl Iterator for BitboardIter {
+    type Item = SquareIndex;
+
+    fn next(&mut self) -> Option<SquareIndex> {
+        if self.0 == 0 {
+            return None;
+        }
+        let square = self.0.trailing_zeros() as SquareIndex;
+        self.0 &= self.0 - 1;
+        Some(square)
+    }
+}
+
+impl IntoIterator for Bitboard {
+    type Item = SquareIndex;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> BitboardIter {
+        BitboardIter(self.0)
+    }
+}
+
+impl FromIterator<SquareIndex> for Bitboard {
+    fn from_iter<T: IntoIterator<Item = SquareIndex>>(iter: T) -> Self {
+        let mut bb = Bitboard::EMPTY;
+        for square in iter {
+            bb.set(square);
+        }
+        bb
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0)
+    }
+}
+
 impl BitAnd for Bitboard {
     type Output = Bitboard;
 
@@ -14,6 +178,12 @@ impl BitAnd for Bitboard {
     }
 }
 
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
 impl BitOr for Bitboard {
     type Output = Bitboard;
 
@@ -22,6 +192,26 @@ impl BitOr for Bitboard {
     }
 }
 
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        self.0 ^= rhs.0;
+    }
+}
+
 impl Shl<u8> for Bitboard {
     type Output = Bitboard;
 
@@ -30,6 +220,26 @@ impl Shl<u8> for Bitboard {
     }
 }
 
+impl ShlAssign<u8> for Bitboard {
+    fn shl_assign(&mut self, rhs: u8) {
+        self.0 <<= rhs;
+    }
+}
+
+impl Shr<u8> for Bitboard {
+    type Output = Bitboard;
+
+    fn shr(self, rhs: u8) -> Self::Output {
+        Bitboard(self.0 >> rhs)
+    }
+}
+
+impl ShrAssign<u8> for Bitboard {
+    fn shr_assign(&mut self, rhs: u8) {
+        self.0 >>= rhs;
+    }
+}
+
 impl From<u64> for Bitboard {
     fn from(value: u64) -> Self {
         Bitboard(value)
@@ -41,11 +251,11 @@ impl From<[[u8; 8]; 8]> for Bitboard {
     fn from(board: [[u8; 8]; 8]) -> Self {
         let mut bb = 0u64;
 
-        for y in 0..8 {
+        for (y, row) in board.iter().enumerate() {
             let rank = 7 - y;
-            for file in 0..8 {
+            for (file, square) in row.iter().enumerate() {
                 let i = 8 * rank + file;
-                if board[y][file] != 0 {
+                if *square != 0 {
                     bb |= 1u64 << i;
                 }
             }
@@ -95,22 +305,147 @@ impl Debug for Bitboard {
             for file in 0..8 {
                 let i = 8 * rank + file;
                 if self.0 & 1 << i == 0 {
-                    result.push_str("0");
+                    result.push('0');
                 } else {
-                    result.push_str("1");
+                    result.push('1');
                 }
             }
-            result.push_str("\n");
+            result.push('\n');
         }
 
         write!(f, "{}", result)
     }
 }
 
+impl Display for Bitboard {
+    /// Draws the board with rank 8 on top, one `1` for every set square, in
+    /// the format `From<&str>` reads.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for rank in Rank::iter().rev() {
+            let row: Vec<&str> = File::iter()
+                .map(|file| Square::from_file_rank(file, rank).index())
+                .map(|square| if self.contains(square) { "1" } else { "0" })
+                .collect();
+            writeln!(f, "{}", row.join(" "))?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod test {
+    use crate::model::types::square_names::*;
+
     use super::*;
 
+    #[test]
+    fn it_combines_bitboards_with_operators() {
+        let a = Bitboard(0b1100);
+        let b = Bitboard(0b1010);
+
+        assert_eq!(a & b, Bitboard(0b1000));
+        assert_eq!(a | b, Bitboard(0b1110));
+        assert_eq!(a ^ b, Bitboard(0b0110));
+        assert_eq!(!a & Bitboard(0b1111), Bitboard(0b0011));
+        assert_eq!(a << 1, Bitboard(0b11000));
+        assert_eq!(a >> 2, Bitboard(0b11));
+
+        let mut c = a;
+        c &= b;
+        c |= Bitboard(1);
+        c ^= Bitboard(0b11);
+        c <<= 2;
+        c >>= 1;
+        assert_eq!(c, Bitboard(0b10100));
+    }
+
+    #[test]
+    fn it_sets_clears_and_counts_squares() {
+        let mut bb = Bitboard::EMPTY;
+        assert!(bb.is_empty());
+
+        bb.set(E4);
+        assert!(bb.contains(E4));
+        assert!(!bb.has_more_than_one());
+
+        bb.set(H8);
+        bb.set(H8);
+        assert_eq!(bb.count(), 2);
+        assert!(bb.has_more_than_one());
+
+        bb.clear(E4);
+        bb.clear(A1);
+        assert_eq!(bb, Bitboard::from_square(H8));
+        assert_eq!(Bitboard::FULL.count(), 64);
+    }
+
+    #[test]
+    fn it_finds_the_lowest_and_highest_square() {
+        assert_eq!(Bitboard(52345).first(), Some(0));
+        assert_eq!(Bitboard(52345).last(), Some(15));
+        assert_eq!(Bitboard(8342384342).first(), Some(1));
+        assert_eq!(Bitboard(8342384342).last(), Some(32));
+        assert_eq!(Bitboard(1 << 10 | 1 << 23).first(), Some(10));
+        assert_eq!(Bitboard(1 << 10 | 1 << 23).last(), Some(23));
+        assert_eq!(Bitboard::EMPTY.first(), None);
+        assert_eq!(Bitboard::EMPTY.last(), None);
+    }
+
+    #[test]
+    fn it_iterates_the_set_squares_in_ascending_order() {
+        let bb = Bitboard::from_square(H8)
+            | Bitboard::from_square(A1)
+            | Bitboard::from_square(E4);
+
+        assert_eq!(bb.into_iter().collect::<Vec<_>>(), vec![A1, E4, H8]);
+        assert_eq!(bb.into_iter().collect::<Bitboard>(), bb);
+        assert_eq!(Bitboard::EMPTY.into_iter().next(), None);
+    }
+
+    #[test]
+    fn it_builds_files_ranks_and_diagonals() {
+        let e4 = Square::from_index(E4);
+
+        assert_eq!(Bitboard::file(File::E), Bitboard::FILE_E);
+        assert_eq!(Bitboard::rank(Rank::FOURTH), Bitboard::RANK_4);
+        assert_eq!(
+            Bitboard::diagonal(e4),
+            [B1, C2, D3, E4, F5, G6, H7].into_iter().collect()
+        );
+        assert_eq!(
+            Bitboard::anti_diagonal(e4),
+            [H1, G2, F3, E4, D5, C6, B7, A8].into_iter().collect()
+        );
+        assert_eq!(
+            Bitboard::diagonal(Square::from_index(A8)),
+            Bitboard::from_square(A8)
+        );
+        assert_eq!(
+            Bitboard::anti_diagonal(Square::from_index(H8)),
+            Bitboard::from_square(H8)
+        );
+    }
+
+    #[test]
+    fn it_displays_a_grid_which_reads_back_to_the_same_bitboard() {
+        let bb =
+            Bitboard::FILE_A | Bitboard::RANK_8 | Bitboard::from_square(E4);
+
+        let mut grid = String::new();
+        grid.push_str("1 1 1 1 1 1 1 1\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+        grid.push_str("1 0 0 0 1 0 0 0\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+        grid.push_str("1 0 0 0 0 0 0 0\n");
+
+        assert_eq!(bb.to_string(), grid);
+        assert_eq!(Bitboard::from(bb.to_string()), bb);
+    }
+
     #[test]
     fn it_reads_a_bitboard_from_a_byte_array() {
         let bb = Bitboard::from([
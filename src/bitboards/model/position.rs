@@ -3,6 +3,7 @@ use crate::model::board_castle::BoardCastle;
 use crate::model::color::Color;
 use crate::model::piece::Piece;
 use crate::model::piece_type::PieceType;
+use crate::model::square::Square;
 use crate::model::types::SquareIndex;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -28,7 +29,7 @@ pub struct Position {
 impl Position {
     pub fn from_fen(fen: &str) -> Position {
         let board = Self::get_board_from_fen(fen);
-        let castling = Self::get_casting_from_fen(fen);
+        let castling = Self::get_casting_from_fen(fen, &board);
         let draw_by_repetition = false;
         let en_passant = Self::get_en_passant_from_fen(fen);
         let fullmove = Self::get_fullmove_from_fen(fen);
@@ -124,16 +125,9 @@ impl Position {
         board
     }
 
-    fn get_casting_from_fen(fen: &str) -> BoardCastle {
+    fn get_casting_from_fen(fen: &str, board: &Board) -> BoardCastle {
         let fen_split: Vec<_> = fen.split(' ').collect();
-        let fen_castle = fen_split[2];
-
-        BoardCastle {
-            black_long: fen_castle.contains("q"),
-            black_short: fen_castle.contains("k"),
-            white_long: fen_castle.contains("Q"),
-            white_short: fen_castle.contains("K"),
-        }
+        BoardCastle::from_fen(fen_split[2], board)
     }
 
     fn get_en_passant_from_fen(fen: &str) -> Option<SquareIndex> {
@@ -144,11 +138,11 @@ impl Position {
             return None;
         }
 
-        let fen_en_passant_bytes: Vec<u8> = fen_en_passant.bytes().collect();
-        let file = fen_en_passant_bytes[0] - b'a';
-        let rank = fen_en_passant_bytes[1] - b'1';
+        let square: Square = fen_en_passant
+            .parse()
+            .unwrap_or_else(|e| panic!("Invalid FEN: en passant {e}"));
 
-        Some(8 * rank + file)
+        Some(square.index())
     }
 
     fn get_fullmove_from_fen(fen: &str) -> usize {
@@ -178,9 +172,9 @@ impl Position {
         for i in 0..64 {
             if let Some(piece) = board[i] {
                 if piece.is_black() {
-                    black.0 |= 1 << i;
+                    black.set(i as SquareIndex);
                 } else {
-                    white.0 |= 1 << i;
+                    white.set(i as SquareIndex);
                 }
             }
         }
@@ -199,22 +193,22 @@ impl Position {
         for i in 0..64 {
             match board[i] {
                 Some(Piece::BlackBishop) | Some(Piece::WhiteBishop) => {
-                    bishops.0 |= 1 << i
+                    bishops.set(i as SquareIndex)
                 }
                 Some(Piece::BlackKing) | Some(Piece::WhiteKing) => {
-                    kings.0 |= 1 << i
+                    kings.set(i as SquareIndex)
                 }
                 Some(Piece::BlackKnight) | Some(Piece::WhiteKnight) => {
-                    knights.0 |= 1 << i
+                    knights.set(i as SquareIndex)
                 }
                 Some(Piece::BlackPawn) | Some(Piece::WhitePawn) => {
-                    pawns.0 |= 1 << i
+                    pawns.set(i as SquareIndex)
                 }
                 Some(Piece::BlackQueen) | Some(Piece::WhiteQueen) => {
-                    queens.0 |= 1 << i
+                    queens.set(i as SquareIndex)
                 }
                 Some(Piece::BlackRook) | Some(Piece::WhiteRook) => {
-                    rooks.0 |= 1 << i
+                    rooks.set(i as SquareIndex)
                 }
                 _ => {}
             }
@@ -226,6 +220,8 @@ impl Position {
 
 #[cfg(test)]
 mod test {
+    use crate::model::file::File;
+
     use super::*;
 
     const NP: Option<Piece> = None;
@@ -285,10 +281,10 @@ mod test {
         #[test]
         fn it_sets_up_the_castling() {
             let position = Position::from_fen(FEN);
-            assert!(position.castling.black_long);
-            assert!(position.castling.black_short);
-            assert!(position.castling.white_long);
-            assert!(position.castling.white_short);
+            assert_eq!(position.castling.black_long, Some(File::A));
+            assert_eq!(position.castling.black_short, Some(File::H));
+            assert_eq!(position.castling.white_long, Some(File::A));
+            assert_eq!(position.castling.white_short, Some(File::H));
         }
 
         #[test]
@@ -494,10 +490,10 @@ mod test {
         #[test]
         fn it_sets_up_the_castling() {
             let position = Position::from_fen(FEN);
-            assert!(position.castling.black_long);
-            assert!(position.castling.black_short);
-            assert!(position.castling.white_long);
-            assert!(position.castling.white_short);
+            assert_eq!(position.castling.black_long, Some(File::A));
+            assert_eq!(position.castling.black_short, Some(File::H));
+            assert_eq!(position.castling.white_long, Some(File::A));
+            assert_eq!(position.castling.white_short, Some(File::H));
         }
 
         #[test]
@@ -706,10 +702,10 @@ mod test {
         #[test]
         fn it_sets_up_the_castling() {
             let position = Position::from_fen(FEN);
-            assert!(!position.castling.black_long);
-            assert!(position.castling.black_short);
-            assert!(!position.castling.white_long);
-            assert!(!position.castling.white_short);
+            assert_eq!(position.castling.black_long, None);
+            assert_eq!(position.castling.black_short, Some(File::H));
+            assert_eq!(position.castling.white_long, None);
+            assert_eq!(position.castling.white_short, None);
         }
 
         #[test]
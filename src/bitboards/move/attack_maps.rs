@@ -0,0 +1,212 @@
+use crate::bitboards::model::bitboard::Bitboard;
+use crate::bitboards::r#move::attacks::get_piece_pseudo_attacks;
+use crate::bitboards::r#move::attacks::KING_PSEUDO_ATTACKS;
+use crate::bitboards::r#move::attacks::KNIGHT_PSEUDO_ATTACKS;
+use crate::bitboards::r#move::attacks::PAWN_PSEUDO_ATTACKS;
+use crate::model::board::Board;
+use crate::model::color::Color;
+use crate::model::piece_type::PieceType;
+use crate::model::types::SquareIndex;
+
+/// Bitboards of the pieces of a (mailbox) board, used to answer attack
+/// queries without generating moves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AttackMaps {
+    pub bishops: Bitboard,
+    pub colors: [Bitboard; 2],
+    pub kings: Bitboard,
+    pub knights: Bitboard,
+    pub occupancy: Bitboard,
+    pub pawns: Bitboard,
+    pub queens: Bitboard,
+    pub rooks: Bitboard,
+}
+
+impl AttackMaps {
+    pub fn from_board(board: &Board) -> Self {
+        let pieces = &board.pieces;
+        let both = |piece_type| {
+            pieces.get(Color::Black, piece_type)
+                | pieces.get(Color::White, piece_type)
+        };
+        // the pieces of each color are consecutive, see `Piece`
+        let colors = [
+            pieces.sets[..6]
+                .iter()
+                .fold(Bitboard::EMPTY, |bb, s| bb | *s),
+            pieces.sets[6..]
+                .iter()
+                .fold(Bitboard::EMPTY, |bb, s| bb | *s),
+        ];
+
+        AttackMaps {
+            bishops: both(PieceType::Bishop),
+            colors,
+            kings: both(PieceType::King),
+            knights: both(PieceType::Knight),
+            occupancy: colors[0] | colors[1],
+            pawns: both(PieceType::Pawn),
+            queens: both(PieceType::Queen),
+            rooks: both(PieceType::Rook),
+        }
+    }
+
+    /// All pieces of both colors which attack `square`, with sliders blocked
+    /// by `occupancy`. Pass a different occupancy to look through pieces.
+    pub fn attackers_to(
+        &self,
+        square: SquareIndex,
+        occupancy: Bitboard,
+    ) -> Bitboard {
+        let s = square as usize;
+        let black = self.colors[Color::Black as usize];
+        let white = self.colors[Color::White as usize];
+
+        let bishop_attacks =
+            get_piece_pseudo_attacks(PieceType::Bishop, square, occupancy);
+        let rook_attacks =
+            get_piece_pseudo_attacks(PieceType::Rook, square, occupancy);
+
+        // a black pawn attacks the square if a white pawn on the square
+        // would attack it, and vice versa
+        (PAWN_PSEUDO_ATTACKS[Color::White as usize][s] & self.pawns & black)
+            | (PAWN_PSEUDO_ATTACKS[Color::Black as usize][s]
+                & self.pawns
+                & white)
+            | (KNIGHT_PSEUDO_ATTACKS[s] & self.knights)
+            | (KING_PSEUDO_ATTACKS[s] & self.kings)
+            | (bishop_attacks & (self.bishops | self.queens))
+            | (rook_attacks & (self.rooks | self.queens))
+    }
+
+    /// Whether a piece of color `by` can capture on `square`. Kings are
+    /// ignored because they cannot capture in atomic chess.
+    pub fn is_square_attacked(&self, square: SquareIndex, by: Color) -> bool {
+        !(self.attackers_to(square, self.occupancy)
+            & self.colors[by as usize]
+            & !self.kings)
+            .is_empty()
+    }
+
+    /// The squares next to the king at `king` holding pieces of the king's
+    /// color which the opponent can capture, so that the explosion destroys
+    /// the king. This is no check in the sense of the rules, but loses the
+    /// game just the same. Nothing is threatened while the kings touch,
+    /// because the explosion would destroy both kings.
+    pub fn explosion_threats(&self, king: SquareIndex) -> Bitboard {
+        let ours = if self.colors[Color::Black as usize].contains(king) {
+            Color::Black
+        } else {
+            Color::White
+        };
+        let theirs = match ours {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        };
+
+        let neighborhood = KING_PSEUDO_ATTACKS[king as usize];
+        if !(neighborhood & self.kings & self.colors[theirs as usize])
+            .is_empty()
+        {
+            return Bitboard::EMPTY;
+        }
+
+        (neighborhood & self.colors[ours as usize])
+            .into_iter()
+            .filter(|square| self.is_square_attacked(*square, theirs))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_the_attackers_of_both_colors() {
+        let board = Board::from_fen("4k3/8/2n5/1b6/3P4/8/1Q3N2/R3K3 w - - 0 1");
+        let maps = AttackMaps::from_board(&board);
+
+        assert_eq!(
+            maps.attackers_to(D4, maps.occupancy),
+            Bitboard::from([
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 1, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 1, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+            ])
+        );
+        assert_eq!(
+            maps.attackers_to(C4, maps.occupancy),
+            Bitboard::from([
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 1, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+            ])
+        );
+        assert_eq!(
+            maps.attackers_to(A5, maps.occupancy),
+            Bitboard::from([
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 1, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [0, 0, 0, 0, 0, 0, 0, 0],
+                [1, 0, 0, 0, 0, 0, 0, 0],
+            ])
+        );
+    }
+
+    #[test]
+    fn it_looks_through_pieces_removed_from_the_occupancy() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/P7/R3K3 w - - 0 1");
+        let maps = AttackMaps::from_board(&board);
+        let occupancy = maps.occupancy & !Bitboard::from_square(A2);
+
+        assert_eq!(maps.attackers_to(A5, maps.occupancy), Bitboard(0));
+        assert_eq!(maps.attackers_to(A5, occupancy), Bitboard::from_square(A1));
+    }
+
+    #[test]
+    fn it_ignores_kings_when_checking_if_a_square_is_attacked() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/3NK3 b - - 0 1");
+        let maps = AttackMaps::from_board(&board);
+
+        assert!(maps.is_square_attacked(C3, Color::White));
+        assert!(!maps.is_square_attacked(F1, Color::White));
+        assert!(!maps.is_square_attacked(C3, Color::Black));
+    }
+
+    #[test]
+    fn it_finds_pieces_next_to_the_king_whose_capture_explodes_it() {
+        let board = Board::from_fen("4k3/8/8/8/1b6/8/3P4/4KN1r w - - 0 1");
+        let maps = AttackMaps::from_board(&board);
+
+        assert_eq!(
+            maps.explosion_threats(E1),
+            Bitboard::from_square(D2) | Bitboard::from_square(F1)
+        );
+        assert_eq!(maps.explosion_threats(E8), Bitboard(0));
+    }
+
+    #[test]
+    fn it_finds_no_explosion_threats_when_the_kings_touch() {
+        let board = Board::from_fen("8/8/8/8/1b6/8/3Pk3/4K3 w - - 0 1");
+        let maps = AttackMaps::from_board(&board);
+
+        assert_eq!(maps.explosion_threats(E1), Bitboard(0));
+    }
+}
@@ -8,6 +8,7 @@ use crate::bitboards::model::position::PositionColor;
 use crate::bitboards::r#move::bishop_table::BISHOP_TABLE;
 use crate::bitboards::r#move::rook_table::ROOK_TABLE;
 use crate::model::piece_type::PieceType;
+use crate::model::square::Square;
 use crate::model::types::square_names::*;
 use crate::model::types::SquareIndex;
 
@@ -110,24 +111,6 @@ impl Magic {
     }
 }
 
-const FILE_A: Bitboard = Bitboard(0x0101010101010101);
-const FILE_B: Bitboard = Bitboard(FILE_A.0 << 1);
-const FILE_C: Bitboard = Bitboard(FILE_A.0 << 2);
-const FILE_D: Bitboard = Bitboard(FILE_A.0 << 3);
-const FILE_E: Bitboard = Bitboard(FILE_A.0 << 4);
-const FILE_F: Bitboard = Bitboard(FILE_A.0 << 5);
-const FILE_G: Bitboard = Bitboard(FILE_A.0 << 6);
-const FILE_H: Bitboard = Bitboard(FILE_A.0 << 7);
-
-const RANK_1: Bitboard = Bitboard(0xFF);
-const RANK_2: Bitboard = Bitboard(RANK_1.0 << (8 * 1));
-const RANK_3: Bitboard = Bitboard(RANK_1.0 << (8 * 2));
-const RANK_4: Bitboard = Bitboard(RANK_1.0 << (8 * 3));
-const RANK_5: Bitboard = Bitboard(RANK_1.0 << (8 * 4));
-const RANK_6: Bitboard = Bitboard(RANK_1.0 << (8 * 5));
-const RANK_7: Bitboard = Bitboard(RANK_1.0 << (8 * 6));
-const RANK_8: Bitboard = Bitboard(RANK_1.0 << (8 * 7));
-
 #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
 const HAS_PEXT: bool = true;
 
@@ -172,48 +155,24 @@ const fn max_u8(a: u8, b: u8) -> u8 {
     }
 }
 
-const fn rank_bb_from_rank(r: u8) -> Bitboard {
-    Bitboard(RANK_1.0 << (8 * r))
-}
-
 const fn rank_bb_from_square(s: SquareIndex) -> Bitboard {
-    rank_bb_from_rank(RANK_OF[s as usize])
+    Bitboard::rank(Square::from_index(s).rank())
 }
 
-const fn file_bb_from_file(f: u8) -> Bitboard {
-    Bitboard(FILE_A.0 << f)
+const fn file_bb_from_square(s: SquareIndex) -> Bitboard {
+    Bitboard::file(Square::from_index(s).file())
 }
 
-const fn file_bb_from_square(s: SquareIndex) -> Bitboard {
-    file_bb_from_file(FILE_OF[s as usize])
+const fn file_of(s: SquareIndex) -> u8 {
+    Square::from_index(s).file().index()
 }
 
-#[rustfmt::skip]
-const FILE_OF: [SquareIndex; 64] = [
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-    0, 1, 2, 3, 4, 5, 6, 7,
-];
-
-#[rustfmt::skip]
-const RANK_OF: [SquareIndex; 64] = [
-    0, 0, 0, 0, 0, 0, 0, 0,
-    1, 1, 1, 1, 1, 1, 1, 1,
-    2, 2, 2, 2, 2, 2, 2, 2,
-    3, 3, 3, 3, 3, 3, 3, 3,
-    4, 4, 4, 4, 4, 4, 4, 4,
-    5, 5, 5, 5, 5, 5, 5, 5,
-    6, 6, 6, 6, 6, 6, 6, 6,
-    7, 7, 7, 7, 7, 7, 7, 7,
-];
+const fn rank_of(s: SquareIndex) -> u8 {
+    Square::from_index(s).rank().index()
+}
 
 const fn is_aligned(s1: SquareIndex, s2: SquareIndex, s3: SquareIndex) -> bool {
-    LINE_BB[s1 as usize][s2 as usize].0 & SQUARE[s3 as usize].0 != 0
+    LINE_BB[s1 as usize][s2 as usize].0 & Bitboard::from_square(s3).0 != 0
 }
 
 const FILE_DISTANCE: [[u8; 8]; 8] = {
@@ -251,8 +210,10 @@ const SQUARE_DISTANCE: [[u8; 64]; 64] = {
         let mut s2 = 0;
         while s2 < 64 {
             square_distance[s1 as usize][s2 as usize] = max_u8(
-                RANK_DISTANCE[RANK_OF[s1] as usize][RANK_OF[s2] as usize],
-                FILE_DISTANCE[FILE_OF[s1] as usize][FILE_OF[s2] as usize],
+                RANK_DISTANCE[rank_of(s1 as SquareIndex) as usize]
+                    [rank_of(s2 as SquareIndex) as usize],
+                FILE_DISTANCE[file_of(s1 as SquareIndex) as usize]
+                    [file_of(s2 as SquareIndex) as usize],
             );
             s2 += 1;
         }
@@ -263,47 +224,11 @@ const SQUARE_DISTANCE: [[u8; 64]; 64] = {
 
 const EDGE_DISTANCE: [u8; 8] = [0, 1, 2, 3, 3, 2, 1, 0];
 
-const fn popcount(b: Bitboard) -> u8 {
-    b.0.count_ones() as u8
-}
-
-const fn lsb(b: Bitboard) -> SquareIndex {
-    debug_assert!(b.0 != 0);
-    b.0.trailing_zeros() as SquareIndex
-}
-
-const fn msb(b: Bitboard) -> SquareIndex {
-    debug_assert!(b.0 != 0);
-    63 - b.0.leading_zeros() as SquareIndex
-}
-
 const fn least_significant_square_bb(b: Bitboard) -> Bitboard {
     debug_assert!(b.0 != 0);
     Bitboard(b.0 & 0u64.wrapping_sub(b.0))
 }
 
-pub fn pop_lsb(b: &mut Bitboard) -> SquareIndex {
-    debug_assert!(b.0 != 0);
-    let s = lsb(*b);
-    *b = Bitboard(b.0 & (b.0 - 1));
-    s
-}
-
-const SQUARE: [Bitboard; 64] = {
-    let mut square = [Bitboard(0); 64];
-    let mut s = 0;
-    while s < 64 {
-        square[s].0 = 1 << s;
-        s += 1;
-    }
-    square
-};
-
-const fn is_more_than_one(b: Bitboard) -> bool {
-    // equal to "b.0.count_ones() > 0"
-    b.0 & b.0.wrapping_sub(1) != 0
-}
-
 const fn is_ok(s: SquareIndex) -> bool {
     s >= A1 && s <= H8
 }
@@ -313,7 +238,7 @@ const fn safe_destination(s: SquareIndex, step: i32) -> Bitboard {
     let to = s as i32 + step;
     if is_ok(to as SquareIndex) && SQUARE_DISTANCE[s as usize][to as usize] <= 2
     {
-        SQUARE[to as usize]
+        Bitboard::from_square(to as SquareIndex)
     } else {
         Bitboard(0)
     }
@@ -351,10 +276,10 @@ const fn sliding_attack(
         let mut square = s as i32;
         let direction = directions[d];
         while safe_destination(square as SquareIndex, direction as i32).0 != 0
-            && occupied.0 & SQUARE[square as usize].0 == 0
+            && occupied.0 & Bitboard::from_square(square as SquareIndex).0 == 0
         {
             square += direction as i32;
-            attacks.0 |= SQUARE[square as usize].0;
+            attacks.0 |= Bitboard::from_square(square as SquareIndex).0;
         }
 
         d += 1;
@@ -369,12 +294,12 @@ const fn shift(d: Direction, b: Bitboard) -> Bitboard {
         Direction::South => Bitboard(b.0 >> 8),
         Direction::NorthNorth => Bitboard(b.0 << 16),
         Direction::SouthSouth => Bitboard(b.0 >> 16),
-        Direction::East => Bitboard((b.0 & !FILE_H.0) << 1),
-        Direction::West => Bitboard((b.0 & !FILE_A.0) >> 1),
-        Direction::NorthEast => Bitboard((b.0 & !FILE_H.0) << 9),
-        Direction::NorthWest => Bitboard((b.0 & !FILE_A.0) << 7),
-        Direction::SouthEast => Bitboard((b.0 & !FILE_H.0) >> 7),
-        Direction::SouthWest => Bitboard((b.0 & !FILE_A.0) >> 9),
+        Direction::East => Bitboard((b.0 & !Bitboard::FILE_H.0) << 1),
+        Direction::West => Bitboard((b.0 & !Bitboard::FILE_A.0) >> 1),
+        Direction::NorthEast => Bitboard((b.0 & !Bitboard::FILE_H.0) << 9),
+        Direction::NorthWest => Bitboard((b.0 & !Bitboard::FILE_A.0) << 7),
+        Direction::SouthEast => Bitboard((b.0 & !Bitboard::FILE_H.0) >> 7),
+        Direction::SouthWest => Bitboard((b.0 & !Bitboard::FILE_A.0) >> 9),
     }
 }
 
@@ -397,15 +322,21 @@ const fn pawn_attacks_by_square(c: PositionColor, s: SquareIndex) -> Bitboard {
 }
 
 /// corresponds to stockfish's PawnAttacks[color][square]
-const PAWN_PSEUDO_ATTACKS: [[Bitboard; 64]; 2] = {
+pub const PAWN_PSEUDO_ATTACKS: [[Bitboard; 64]; 2] = {
     let mut attacks = [[Bitboard(0); 64]; 2];
 
     let mut s = 0;
     while s < 64 {
         attacks[PositionColor::Black as usize][s as usize] =
-            pawn_attacks_by_bitboard(PositionColor::Black, SQUARE[s as usize]);
+            pawn_attacks_by_bitboard(
+                PositionColor::Black,
+                Bitboard::from_square(s),
+            );
         attacks[PositionColor::White as usize][s as usize] =
-            pawn_attacks_by_bitboard(PositionColor::White, SQUARE[s as usize]);
+            pawn_attacks_by_bitboard(
+                PositionColor::White,
+                Bitboard::from_square(s),
+            );
         s += 1;
     }
 
@@ -413,7 +344,7 @@ const PAWN_PSEUDO_ATTACKS: [[Bitboard; 64]; 2] = {
 };
 
 /// corresponds to stockfish's PseudoAttacks[KING][square]
-const KING_PSEUDO_ATTACKS: [Bitboard; 64] = {
+pub const KING_PSEUDO_ATTACKS: [Bitboard; 64] = {
     let mut attacks = [Bitboard(0); 64];
 
     let mut s = 0;
@@ -431,7 +362,7 @@ const KING_PSEUDO_ATTACKS: [Bitboard; 64] = {
 };
 
 /// corresponds to stockfish's PseudoAttacks[KNIGHT][square]
-const KNIGHT_PSEUDO_ATTACKS: [Bitboard; 64] = {
+pub const KNIGHT_PSEUDO_ATTACKS: [Bitboard; 64] = {
     let mut attacks = [Bitboard(0); 64];
 
     let mut s = 0;
@@ -499,7 +430,9 @@ const LINE_BB: [[Bitboard; 64]; 64] = {
     while s1 < 64 {
         let mut s2 = 0;
         while s2 < 64 {
-            if BISHOP_PSEUDO_ATTACKS[s1 as usize].0 & SQUARE[s2 as usize].0 != 0
+            if BISHOP_PSEUDO_ATTACKS[s1 as usize].0
+                & Bitboard::from_square(s2).0
+                != 0
             {
                 bb[s1 as usize][s2 as usize] = Bitboard(
                     (get_piece_pseudo_attacks(
@@ -512,12 +445,14 @@ const LINE_BB: [[Bitboard; 64]; 64] = {
                         s2,
                         Bitboard(0),
                     )
-                    .0) | SQUARE[s1 as usize].0
-                        | SQUARE[s2 as usize].0,
+                    .0) | Bitboard::from_square(s1).0
+                        | Bitboard::from_square(s2).0,
                 );
             }
 
-            if ROOK_PSEUDO_ATTACKS[s1 as usize].0 & SQUARE[s2 as usize].0 != 0 {
+            if ROOK_PSEUDO_ATTACKS[s1 as usize].0 & Bitboard::from_square(s2).0
+                != 0
+            {
                 bb[s1 as usize][s2 as usize] = Bitboard(
                     (get_piece_pseudo_attacks(
                         PieceType::Rook,
@@ -529,8 +464,8 @@ const LINE_BB: [[Bitboard; 64]; 64] = {
                         s2,
                         Bitboard(0),
                     )
-                    .0) | SQUARE[s1 as usize].0
-                        | SQUARE[s2 as usize].0,
+                    .0) | Bitboard::from_square(s1).0
+                        | Bitboard::from_square(s2).0,
                 );
             }
 
@@ -549,40 +484,44 @@ const BETWEEN_BB: [[Bitboard; 64]; 64] = {
     while s1 < 64 {
         let mut s2 = 0;
         while s2 < 64 {
-            if BISHOP_PSEUDO_ATTACKS[s1 as usize].0 & SQUARE[s2 as usize].0 != 0
+            if BISHOP_PSEUDO_ATTACKS[s1 as usize].0
+                & Bitboard::from_square(s2).0
+                != 0
             {
                 bb[s1 as usize][s2 as usize] = Bitboard(
                     get_piece_pseudo_attacks(
                         PieceType::Bishop,
                         s1,
-                        SQUARE[s2 as usize],
+                        Bitboard::from_square(s2),
                     )
                     .0 & get_piece_pseudo_attacks(
                         PieceType::Bishop,
                         s2,
-                        SQUARE[s1 as usize],
+                        Bitboard::from_square(s1),
                     )
                     .0,
                 );
             }
-            bb[s1 as usize][s2 as usize].0 |= SQUARE[s2 as usize].0;
+            bb[s1 as usize][s2 as usize].0 |= Bitboard::from_square(s2).0;
 
-            if ROOK_PSEUDO_ATTACKS[s1 as usize].0 & SQUARE[s2 as usize].0 != 0 {
+            if ROOK_PSEUDO_ATTACKS[s1 as usize].0 & Bitboard::from_square(s2).0
+                != 0
+            {
                 bb[s1 as usize][s2 as usize] = Bitboard(
                     get_piece_pseudo_attacks(
                         PieceType::Rook,
                         s1,
-                        SQUARE[s2 as usize],
+                        Bitboard::from_square(s2),
                     )
                     .0 & get_piece_pseudo_attacks(
                         PieceType::Rook,
                         s2,
-                        SQUARE[s1 as usize],
+                        Bitboard::from_square(s1),
                     )
                     .0,
                 );
             }
-            bb[s1 as usize][s2 as usize].0 |= SQUARE[s2 as usize].0;
+            bb[s1 as usize][s2 as usize].0 |= Bitboard::from_square(s2).0;
 
             s2 += 1;
         }
@@ -609,7 +548,7 @@ const fn get_piece_pseudo_attacks_by_square(
 }
 
 /// corresponds to stockfish's attack_bb functions by square and occupation
-const fn get_piece_pseudo_attacks(
+pub const fn get_piece_pseudo_attacks(
     pt: PieceType,
     s: SquareIndex,
     occupied: Bitboard,
@@ -660,15 +599,17 @@ const fn init_magic_table<const TABLE_SIZE: usize>() -> MagicTable<TABLE_SIZE> {
     while s < 64 {
         let si = s as usize;
         edges = Bitboard(
-            ((RANK_1.0 | RANK_8.0) & !rank_bb_from_square(s).0)
-                | ((FILE_A.0 | FILE_H.0) & !file_bb_from_square(s).0),
+            ((Bitboard::RANK_1.0 | Bitboard::RANK_8.0)
+                & !rank_bb_from_square(s).0)
+                | ((Bitboard::FILE_A.0 | Bitboard::FILE_H.0)
+                    & !file_bb_from_square(s).0),
         );
 
         mt.magics[si].mask =
             Bitboard(sliding_attack(pt, s, Bitboard(0)).0 & !edges.0);
 
         mt.magics[si].shift =
-            if IS_64_BIT { 64 } else { 32 } - popcount(mt.magics[si].mask);
+            if IS_64_BIT { 64 } else { 32 } - mt.magics[si].mask.count() as u8;
 
         mt.magics[si].attacks = if s == A1 {
             0
@@ -705,9 +646,9 @@ const fn init_magic_table<const TABLE_SIZE: usize>() -> MagicTable<TABLE_SIZE> {
         }
 
         let mut seed = if IS_64_BIT {
-            seeds_64[RANK_OF[s as usize] as usize]
+            seeds_64[rank_of(s) as usize]
         } else {
-            seeds_32[RANK_OF[s as usize] as usize]
+            seeds_32[rank_of(s) as usize]
         };
 
         let mut i = 0;
@@ -720,7 +661,7 @@ const fn init_magic_table<const TABLE_SIZE: usize>() -> MagicTable<TABLE_SIZE> {
 
                 let multi =
                     mt.magics[si].magic.0.wrapping_mul(mt.magics[si].mask.0);
-                if popcount(Bitboard(multi >> 56)) >= 6 {
+                if Bitboard(multi >> 56).count() as u8 >= 6 {
                     break;
                 }
             }
@@ -779,7 +720,7 @@ mod test {
     #[test]
     fn it_gives_the_bitboard_representing_one_square() {
         assert_eq!(
-            SQUARE[A1 as usize],
+            Bitboard::from_square(A1),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 0],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -793,7 +734,7 @@ mod test {
         );
 
         assert_eq!(
-            SQUARE[E4 as usize],
+            Bitboard::from_square(E4),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 0],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -807,7 +748,7 @@ mod test {
         );
 
         assert_eq!(
-            SQUARE[D5 as usize],
+            Bitboard::from_square(D5),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 0],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -821,7 +762,7 @@ mod test {
         );
 
         assert_eq!(
-            SQUARE[H8 as usize],
+            Bitboard::from_square(H8),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 1],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -850,7 +791,7 @@ mod test {
     #[test]
     fn it_calculates_the_sliding_attack_bitboards() {
         assert_eq!(
-            sliding_attack(PieceType::Bishop, E4, FILE_G),
+            sliding_attack(PieceType::Bishop, E4, Bitboard::FILE_G),
             Bitboard::from([
                 [1, 0, 0, 0, 0, 0, 0, 0],
                 [0, 1, 0, 0, 0, 0, 0, 0],
@@ -864,7 +805,11 @@ mod test {
         );
 
         assert_eq!(
-            sliding_attack(PieceType::Bishop, E4, FILE_C | FILE_G),
+            sliding_attack(
+                PieceType::Bishop,
+                E4,
+                Bitboard::FILE_C | Bitboard::FILE_G
+            ),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 0],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -892,7 +837,11 @@ mod test {
         );
 
         assert_eq!(
-            sliding_attack(PieceType::Rook, D4, RANK_2 | RANK_5 | FILE_F),
+            sliding_attack(
+                PieceType::Rook,
+                D4,
+                Bitboard::RANK_2 | Bitboard::RANK_5 | Bitboard::FILE_F
+            ),
             Bitboard::from([
                 [0, 0, 0, 0, 0, 0, 0, 0],
                 [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1356,7 +1305,7 @@ mod test {
                 get_piece_pseudo_attacks(
                     PieceType::Knight,
                     E4,
-                    RANK_1 | RANK_2 | RANK_3
+                    Bitboard::RANK_1 | Bitboard::RANK_2 | Bitboard::RANK_3
                 ),
                 Bitboard::from([
                     [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1377,7 +1326,7 @@ mod test {
                 get_piece_pseudo_attacks(
                     PieceType::Bishop,
                     E4,
-                    FILE_C | RANK_1,
+                    Bitboard::FILE_C | Bitboard::RANK_1,
                 ),
                 Bitboard::from([
                     [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1394,14 +1343,14 @@ mod test {
                 get_piece_pseudo_attacks(
                     PieceType::Bishop,
                     E4,
-                    RANK_1
-                        | RANK_2
-                        | RANK_3
-                        | RANK_4
-                        | RANK_5
-                        | RANK_6
-                        | RANK_7
-                        | RANK_8
+                    Bitboard::RANK_1
+                        | Bitboard::RANK_2
+                        | Bitboard::RANK_3
+                        | Bitboard::RANK_4
+                        | Bitboard::RANK_5
+                        | Bitboard::RANK_6
+                        | Bitboard::RANK_7
+                        | Bitboard::RANK_8
                 ),
                 Bitboard::from([
                     [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1419,7 +1368,11 @@ mod test {
         #[test]
         fn it_generates_rook_pseudo_attacks_on_an_occupied_board() {
             assert_eq!(
-                get_piece_pseudo_attacks(PieceType::Rook, E4, FILE_C | RANK_1,),
+                get_piece_pseudo_attacks(
+                    PieceType::Rook,
+                    E4,
+                    Bitboard::FILE_C | Bitboard::RANK_1,
+                ),
                 Bitboard::from([
                     [0, 0, 0, 0, 1, 0, 0, 0],
                     [0, 0, 0, 0, 1, 0, 0, 0],
@@ -1435,14 +1388,14 @@ mod test {
                 get_piece_pseudo_attacks(
                     PieceType::Rook,
                     E4,
-                    RANK_1
-                        | RANK_2
-                        | RANK_3
-                        | RANK_4
-                        | RANK_5
-                        | RANK_6
-                        | RANK_7
-                        | RANK_8
+                    Bitboard::RANK_1
+                        | Bitboard::RANK_2
+                        | Bitboard::RANK_3
+                        | Bitboard::RANK_4
+                        | Bitboard::RANK_5
+                        | Bitboard::RANK_6
+                        | Bitboard::RANK_7
+                        | Bitboard::RANK_8
                 ),
                 Bitboard::from([
                     [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1460,7 +1413,11 @@ mod test {
         #[test]
         fn it_generates_queen_pseudo_attacks_on_an_occupied_board() {
             assert_eq!(
-                get_piece_pseudo_attacks(PieceType::Queen, E4, FILE_C | RANK_1,),
+                get_piece_pseudo_attacks(
+                    PieceType::Queen,
+                    E4,
+                    Bitboard::FILE_C | Bitboard::RANK_1,
+                ),
                 Bitboard::from([
                     [0, 0, 0, 0, 1, 0, 0, 0],
                     [0, 0, 0, 0, 1, 0, 0, 1],
@@ -1476,14 +1433,14 @@ mod test {
                 get_piece_pseudo_attacks(
                     PieceType::Queen,
                     E4,
-                    RANK_1
-                        | RANK_2
-                        | RANK_3
-                        | RANK_4
-                        | RANK_5
-                        | RANK_6
-                        | RANK_7
-                        | RANK_8
+                    Bitboard::RANK_1
+                        | Bitboard::RANK_2
+                        | Bitboard::RANK_3
+                        | Bitboard::RANK_4
+                        | Bitboard::RANK_5
+                        | Bitboard::RANK_6
+                        | Bitboard::RANK_7
+                        | Bitboard::RANK_8
                 ),
                 Bitboard::from([
                     [0, 0, 0, 0, 0, 0, 0, 0],
@@ -1634,60 +1591,6 @@ mod test {
         assert_eq!(BETWEEN_BB[14][44], Bitboard(17592186044416));
     }
 
-    #[test]
-    fn if_checks_for_more_than_one_bit_set() {
-        assert!(!is_more_than_one(Bitboard(1 << 10)));
-        assert!(!is_more_than_one(Bitboard(1 << 23)));
-        assert!(!is_more_than_one(Bitboard(1)));
-        assert!(!is_more_than_one(Bitboard(0)));
-        assert!(is_more_than_one(Bitboard(52345)));
-        assert!(is_more_than_one(Bitboard(8342384342)));
-        assert!(is_more_than_one(Bitboard(7777777777)));
-        assert!(is_more_than_one(Bitboard(1 << 10 | 1 << 23)));
-
-        for i in 0..1000 {
-            assert_eq!(is_more_than_one(Bitboard(i)), i.count_ones() > 1);
-        }
-    }
-
-    #[test]
-    fn it_calculates_the_index_of_the_least_significant_bit() {
-        assert_eq!(lsb(Bitboard(1 << 51)), 51);
-        assert_eq!(lsb(Bitboard(1 << 10)), 10);
-        assert_eq!(lsb(Bitboard(1 << 23)), 23);
-        assert_eq!(lsb(Bitboard(1)), 0);
-        assert_eq!(lsb(Bitboard(52345)), 0);
-        assert_eq!(lsb(Bitboard(8342384342)), 1);
-        assert_eq!(lsb(Bitboard(7777777777)), 0);
-        assert_eq!(lsb(Bitboard(1 << 10 | 1 << 23)), 10);
-    }
-
-    #[test]
-    #[should_panic]
-    #[cfg(debug_assertions)]
-    fn it_panics_when_checking_for_lsb_on_0_bitboard() {
-        lsb(Bitboard(0));
-    }
-
-    #[test]
-    fn it_calculates_the_index_of_the_most_significant_bit() {
-        assert_eq!(msb(Bitboard(1 << 51)), 51);
-        assert_eq!(msb(Bitboard(1 << 10)), 10);
-        assert_eq!(msb(Bitboard(1 << 23)), 23);
-        assert_eq!(msb(Bitboard(1)), 0);
-        assert_eq!(msb(Bitboard(52345)), 15);
-        assert_eq!(msb(Bitboard(8342384342)), 32);
-        assert_eq!(msb(Bitboard(7777777777)), 32);
-        assert_eq!(msb(Bitboard(1 << 10 | 1 << 23)), 23);
-    }
-
-    #[test]
-    #[should_panic]
-    #[cfg(debug_assertions)]
-    fn it_panics_when_checking_for_msb_on_0_bitboard() {
-        msb(Bitboard(0));
-    }
-
     #[test]
     #[rustfmt::skip]
     fn it_calculates_the_bitboard_of_the_least_significant_square() {
@@ -1707,11 +1610,4 @@ mod test {
     fn it_panics_when_checking_for_least_significant_square_on_0_bitboard() {
         least_significant_square_bb(Bitboard(0));
     }
-
-    #[test]
-    fn it_pops_and_returns_the_least_significant_bit() {
-        let mut b = Bitboard(534253458324823408);
-        assert_eq!(pop_lsb(&mut b), 4);
-        assert_eq!(b, Bitboard(534253458324823392));
-    }
 }
@@ -0,0 +1,161 @@
+use std::time::Instant;
+
+use crate::common::random::Random;
+use crate::model::bench_args::BenchArgs;
+use crate::model::bench_result::BenchResult;
+use crate::model::board::Board;
+use crate::model::playout_policy::PlayoutPolicy;
+use crate::model::tree::Tree;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
+use crate::move_generator::legal_moves::generate_move;
+use crate::move_generator::perft::perft;
+use crate::search::search::search_iteration;
+
+const BENCH_SEED: u64 = 19870826;
+
+/// Random moves generated per position, as done on every ply of a playout.
+const BENCH_RANDOM_MOVES: u64 = 20000;
+
+const BENCH_POSITIONS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "rnbqk2r/pppp1ppp/4pn2/8/1bPP4/2N5/PP2PPPP/R1BQKBNR w KQkq - 2 4",
+    "rnbqkb1r/pppppppp/5n2/8/P7/8/1PPPPPPP/RNBQKBNR b KQkq - 0 2",
+    "rnbqkbnr/pppp3p/4p1p1/5p1Q/3N4/4P3/PPPP1PPP/RNB1KB1R w KQkq - 0 5",
+    "3R1r1r/p1k2P2/7p/1pp1B1p1/4P3/3p2P1/P2P3P/R5K1 b - - 1 25",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r1bqkbnr/pPpp1ppp/2n5/4p3/8/8/P1PPPPPP/RNBQKBNR w KQkq - 0 4",
+    "7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1",
+];
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Searches a fixed list of positions with a fixed seed and prints the
+/// totals. Matching signatures of two builds mean that the search behaved
+/// exactly the same.
+pub fn bench(bench_args: &BenchArgs) -> BenchResult {
+    let mut result = BenchResult {
+        signature: FNV_OFFSET_BASIS,
+        ..BenchResult::default()
+    };
+
+    for (i, fen) in BENCH_POSITIONS.iter().enumerate() {
+        let board = Board::from_fen(fen);
+
+        let start_time = Instant::now();
+        let tree = search_bench_position(&board, bench_args.iterations);
+        result.search_time += start_time.elapsed();
+        result.nodes += tree.get_size() as u64;
+        result.tree_bytes += tree.get_allocated_bytes() as u64;
+        result.playouts += bench_args.iterations;
+        result.signature = hash_tree(result.signature, &tree);
+
+        let start_time = Instant::now();
+        let moves_generated = perft(&board, bench_args.perft_depth);
+        result.move_generation_time += start_time.elapsed();
+        result.moves_generated += moves_generated;
+
+        let start_time = Instant::now();
+        generate_random_moves(&board, BENCH_RANDOM_MOVES);
+        result.random_move_time += start_time.elapsed();
+        result.random_moves += BENCH_RANDOM_MOVES;
+
+        println!(
+            "position {}/{}: {} nodes, {moves_generated} perft leaves, {fen}",
+            i + 1,
+            BENCH_POSITIONS.len(),
+            tree.get_size(),
+        );
+    }
+
+    println!("total nodes: {}", result.nodes);
+    println!("total playouts: {}", result.playouts);
+    println!("search time: {} ms", result.search_time.as_millis());
+    println!("playouts/sec: {:.0}", result.get_playouts_per_second());
+    println!("tree bytes/node: {:.1}", result.get_tree_bytes_per_node());
+    println!("moves generated: {}", result.moves_generated);
+    println!(
+        "move generation time: {} ms",
+        result.move_generation_time.as_millis()
+    );
+    println!(
+        "moves generated/sec: {:.0}",
+        result.get_moves_generated_per_second()
+    );
+    println!(
+        "random moves/sec: {:.0}",
+        result.get_random_moves_per_second()
+    );
+    println!("signature: {:016x}", result.signature);
+
+    result
+}
+
+fn search_bench_position(board: &Board, iterations: u64) -> Tree {
+    let mut tree = Tree::new(board.clone());
+    let mut random = Random::from_seed(BENCH_SEED);
+
+    for _ in 0..iterations {
+        search_iteration(&mut tree, &mut random, PlayoutPolicy::Random);
+    }
+
+    tree
+}
+
+fn generate_random_moves(board: &Board, count: u64) {
+    let mut board = board.clone();
+    let mut random = Random::from_seed(BENCH_SEED);
+
+    for _ in 0..count {
+        std::hint::black_box(generate_move(&mut board, &mut random));
+    }
+}
+
+/// Continues the FNV-1a hash with the tree size and the visits of the root
+/// children, which is enough to detect any change in the search.
+fn hash_tree(hash: u64, tree: &Tree) -> u64 {
+    let root_visits = tree
+        .get_node(TREE_NODE_ROOT_INDEX)
+        .get_child_indices()
+        .map(|c| tree.get_score(c).get_visits());
+
+    std::iter::once(tree.get_size() as u64)
+        .chain(root_visits)
+        .flat_map(u64::to_le_bytes)
+        .fold(hash, |h, b| (h ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_produces_the_same_signature_on_every_run() {
+        let bench_args = BenchArgs {
+            iterations: 20,
+            perft_depth: 1,
+        };
+
+        let first = bench(&bench_args);
+        let second = bench(&bench_args);
+
+        assert_eq!(first.signature, second.signature);
+        assert_eq!(first.nodes, second.nodes);
+        assert_eq!(first.playouts, 20 * BENCH_POSITIONS.len() as u64);
+        assert_eq!(first.moves_generated, second.moves_generated);
+    }
+
+    #[test]
+    fn it_changes_the_signature_with_the_search() {
+        let few = bench(&BenchArgs {
+            iterations: 10,
+            perft_depth: 1,
+        });
+        let more = bench(&BenchArgs {
+            iterations: 11,
+            perft_depth: 1,
+        });
+
+        assert_ne!(few.signature, more.signature);
+    }
+}
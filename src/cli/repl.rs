@@ -0,0 +1,386 @@
+use std::io::stdin;
+use std::io::stdout;
+use std::io::BufRead;
+use std::io::Write;
+
+use crate::common::random::Random;
+use crate::model::args::Args;
+use crate::model::board::Board;
+use crate::model::color::Color;
+use crate::model::game::Game;
+use crate::model::tree::Tree;
+use crate::model::types::TreeNodeIndex;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
+use crate::move_generator::legal_moves::generate_moves;
+use crate::move_generator::make_move::make_move;
+use crate::move_generator::perft::perft;
+use crate::search::interpret::get_principal_variation_moves;
+use crate::search::search::search_iteration;
+use crate::view::format_uci::format_uci;
+use crate::view::format_uci::format_uci_line;
+use crate::view::print_metrics_json::format_evaluation_json;
+
+const HELP: &str = "\
+fen <fen>        set up a position
+moves            list the legal moves
+play <move>      play a move in UCI notation, e.g. e2e4
+undo             take back the last move
+show             show the board
+go <iterations>  continue the search of the current position
+pv               show the principal variation
+tree [<move>..]  show the children stats of the node reached by the moves
+perft <depth>    count the leaf nodes of the move tree
+help             show this help
+quit             leave the shell";
+
+/// Interactive shell which keeps a game and (after `go`) the search tree of its
+/// current position between commands.
+pub struct Repl {
+    args: Args,
+    game: Game,
+    random: Random,
+    tree: Option<Tree>,
+}
+
+impl Repl {
+    /// Starts with the position, moves, seed and search settings of the args.
+    pub fn new(args: Args) -> Self {
+        Self {
+            game: Game::from_args(&args),
+            random: Random::from_seed(args.seed),
+            tree: None,
+            args,
+        }
+    }
+
+    /// Executes a single command line, returns false if the shell should be
+    /// left.
+    pub fn execute(&mut self, line: &str, out: &mut impl Write) -> bool {
+        let mut words = line.split_whitespace();
+        let command = words.next().unwrap_or("");
+        let params: Vec<&str> = words.collect();
+
+        let result = match command {
+            "" => Ok(()),
+            "fen" => self.fen(&params.join(" "), out),
+            "moves" => self.moves(out),
+            "play" => self.play(&params, out),
+            "undo" => self.undo(out),
+            "show" => self.show(out),
+            "go" => self.go(&params, out),
+            "pv" => self.pv(out),
+            "tree" => self.tree(&params, out),
+            "perft" => self.perft(&params, out),
+            "help" => writeln!(out, "{HELP}").map_err(|e| e.to_string()),
+            "quit" | "exit" => return false,
+            _ => Err(format!("unknown command '{command}', try 'help'")),
+        };
+
+        if let Err(message) = result {
+            writeln!(out, "error: {message}").unwrap();
+        }
+
+        true
+    }
+
+    fn fen(&mut self, fen: &str, out: &mut impl Write) -> Result<(), String> {
+        let mut board =
+            Board::try_from_fen_with_variant(fen, self.args.variant)?;
+        board.castle.chess960 |= self.args.chess960;
+
+        self.game = Game::new(board);
+        self.tree = None;
+        self.show(out)
+    }
+
+    fn moves(&mut self, out: &mut impl Write) -> Result<(), String> {
+        let board = self.game.get_board();
+        let moves: Vec<String> = generate_moves(&mut board.clone())
+            .iter()
+            .map(|m| format_uci(board, m))
+            .collect();
+
+        writeln!(out, "{}", moves.join(" ")).map_err(|e| e.to_string())
+    }
+
+    fn play(
+        &mut self,
+        params: &[&str],
+        out: &mut impl Write,
+    ) -> Result<(), String> {
+        if params.is_empty() {
+            return Err(String::from("play needs at least one move"));
+        }
+
+        for uci in params {
+            self.game.play_uci(uci)?;
+            self.tree = None;
+        }
+
+        self.show(out)
+    }
+
+    fn undo(&mut self, out: &mut impl Write) -> Result<(), String> {
+        self.game
+            .undo()
+            .ok_or(String::from("there is no move to take back"))?;
+
+        self.tree = None;
+        self.show(out)
+    }
+
+    fn show(&self, out: &mut impl Write) -> Result<(), String> {
+        let board = self.game.get_board();
+        let to_move = match board.our_color {
+            Color::Black => "black",
+            Color::White => "white",
+        };
+
+        write!(out, "{:?}", board.pieces.squares)
+            .and_then(|_| writeln!(out, "fen: {}", board.to_fen()))
+            .and_then(|_| writeln!(out, "to move: {to_move}"))
+            .map_err(|e| e.to_string())
+    }
+
+    fn go(
+        &mut self,
+        params: &[&str],
+        out: &mut impl Write,
+    ) -> Result<(), String> {
+        let iterations: u64 = parse_param(params, "go <iterations>")?;
+        let tree = self.tree.get_or_insert_with(|| {
+            let mut tree = Tree::new(self.game.get_board().clone());
+            tree.set_history(self.game.get_previous_hashes());
+            tree.configure(&self.args);
+            tree
+        });
+
+        for _ in 0..iterations {
+            search_iteration(tree, &mut self.random, self.args.playout_policy);
+        }
+
+        writeln!(out, "tree size: {}", tree.get_size())
+            .map_err(|e| e.to_string())?;
+        self.pv(out)
+    }
+
+    fn pv(&self, out: &mut impl Write) -> Result<(), String> {
+        let tree = self.get_tree()?;
+        let pv = format_uci_line(
+            self.game.get_board(),
+            &get_principal_variation_moves(tree, TREE_NODE_ROOT_INDEX),
+        );
+
+        writeln!(out, "pv {}", pv.join(" ")).map_err(|e| e.to_string())
+    }
+
+    fn tree(
+        &self,
+        params: &[&str],
+        out: &mut impl Write,
+    ) -> Result<(), String> {
+        let tree = self.get_tree()?;
+        let mut node_index = TREE_NODE_ROOT_INDEX;
+        let mut board = self.game.get_board().clone();
+
+        for uci in params {
+            node_index = tree
+                .get_node(node_index)
+                .get_child_indices()
+                .find(|c| {
+                    format_uci(&board, &tree.get_node(*c).get_last_move())
+                        == *uci
+                })
+                .ok_or(format!("'{uci}' is not in the tree"))?;
+            make_move(&mut board, &tree.get_node(node_index).get_last_move());
+        }
+
+        let mut child_indices: Vec<TreeNodeIndex> =
+            tree.get_node(node_index).get_child_indices().collect();
+        child_indices
+            .sort_by_key(|c| u64::MAX - tree.get_score(*c).get_visits());
+
+        for child_index in child_indices {
+            writeln!(out, "{}", format_child_stats(tree, child_index, &board))
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn perft(
+        &self,
+        params: &[&str],
+        out: &mut impl Write,
+    ) -> Result<(), String> {
+        let depth: u32 = parse_param(params, "perft <depth>")?;
+        writeln!(
+            out,
+            "perft {depth}: {}",
+            perft(self.game.get_board(), depth)
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    fn get_tree(&self) -> Result<&Tree, String> {
+        self.tree
+            .as_ref()
+            .ok_or(String::from("there is no search tree yet, use 'go'"))
+    }
+}
+
+pub fn repl(args: Args) {
+    let mut repl = Repl::new(args);
+    let mut out = stdout();
+
+    for line in stdin().lock().lines() {
+        let line = line.expect("Could not read from stdin");
+        if !repl.execute(&line, &mut out) {
+            break;
+        }
+        out.flush().unwrap();
+    }
+}
+
+fn parse_param<T: std::str::FromStr>(
+    params: &[&str],
+    usage: &str,
+) -> Result<T, String> {
+    params
+        .first()
+        .and_then(|p| p.parse().ok())
+        .ok_or(format!("usage: {usage}"))
+}
+
+/// Formats the stats of a child of the node whose position is `board`.
+fn format_child_stats(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+    board: &Board,
+) -> String {
+    let node = tree.get_node(node_index);
+    let node_score = tree.get_score(node_index);
+    let visits = node_score.get_visits();
+    let score = if visits == 0 {
+        0.0
+    } else {
+        let score = node_score.wins_white as f64 - node_score.wins_black as f64;
+        100.0 * score / visits as f64
+    };
+
+    format!(
+        "{:<6}|visits {:05}|W/D/B {}/{}/{}|score {:>6.1}|uct {:>10}|{}",
+        format_uci(board, &node.get_last_move()),
+        visits,
+        node_score.wins_white,
+        node_score.draws,
+        node_score.wins_black,
+        score,
+        tree.calculate_uct(node_index),
+        format_evaluation_json(node.evaluation),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run(repl: &mut Repl, line: &str) -> String {
+        let mut out = Vec::new();
+        repl.execute(line, &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    fn new_repl() -> Repl {
+        Repl::new(Args {
+            seed: 111,
+            ..Args::default()
+        })
+    }
+
+    #[test]
+    fn it_plays_and_takes_back_moves() {
+        let mut repl = new_repl();
+
+        let output = run(&mut repl, "play e2e4 e7e5");
+        assert!(output.contains(
+            "fen: rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2"
+        ));
+
+        let output = run(&mut repl, "undo");
+        assert!(output.contains(
+            "fen: rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1"
+        ));
+        assert!(output.contains("to move: black"));
+    }
+
+    #[test]
+    fn it_starts_after_the_moves_of_the_args() {
+        let mut repl = Repl::new(Args {
+            moves: vec![String::from("e2e4")],
+            ..Args::default()
+        });
+
+        let output = run(&mut repl, "undo");
+        assert!(output.contains(
+            "fen: rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        ));
+    }
+
+    #[test]
+    fn it_rejects_illegal_moves() {
+        let mut repl = new_repl();
+        assert_eq!(
+            run(&mut repl, "play e2e5"),
+            "error: 'e2e5' is not a legal move\n"
+        );
+        assert_eq!(
+            run(&mut repl, "undo"),
+            "error: there is no move to take back\n"
+        );
+    }
+
+    #[test]
+    fn it_sets_up_positions_from_fen() {
+        let mut repl = new_repl();
+        run(&mut repl, "fen 7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1");
+        assert_eq!(
+            run(&mut repl, "moves"),
+            "f6e4 f6g4 f6d5 f6h5 f6d7 f6h7 f6e8 f6g8\n"
+        );
+        assert_eq!(run(&mut repl, "perft 2"), "perft 2: 158\n");
+    }
+
+    #[test]
+    fn it_reports_invalid_fens() {
+        let mut repl = new_repl();
+        assert_eq!(
+            run(&mut repl, "fen 7k/8/8/8/8/8/8/K7 x - - 0 1"),
+            "error: Invalid FEN: active color must be 'b' or 'w'\n"
+        );
+    }
+
+    #[test]
+    fn it_searches_the_current_position() {
+        let mut repl = new_repl();
+        run(&mut repl, "fen 7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1");
+
+        assert_eq!(
+            run(&mut repl, "pv"),
+            "error: there is no search tree yet, use 'go'\n"
+        );
+        assert!(run(&mut repl, "go 20").contains("pv f6h7"));
+        assert!(run(&mut repl, "tree")
+            .lines()
+            .any(|l| l.starts_with("f6h7  |") && l.ends_with("|win_white")));
+        assert!(run(&mut repl, "tree e2e4").contains("not in the tree"));
+    }
+
+    #[test]
+    fn it_leaves_the_shell_on_quit() {
+        let mut repl = new_repl();
+        let mut out = Vec::new();
+        assert!(repl.execute("help", &mut out));
+        assert!(!repl.execute("quit", &mut out));
+    }
+}
@@ -0,0 +1,346 @@
+use std::fs;
+
+use crate::common::random::Random;
+use crate::model::args::Args;
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::engine_mode::EngineMode;
+use crate::model::game::Game;
+use crate::model::match_args::MatchArgs;
+use crate::model::match_score::get_sprt_bounds;
+use crate::model::match_score::MatchScore;
+use crate::model::r#move::Move;
+use crate::model::termination::Termination;
+use crate::move_generator::legal_moves::generate_move;
+use crate::search::search::search_best_move;
+use crate::view::format_pgn::format_pgn;
+use crate::view::format_pgn::format_result;
+use crate::view::format_san::format_san_line;
+
+struct Engine {
+    args: Args,
+    mode: EngineMode,
+    name: String,
+}
+
+/// Plays a match between engine A and engine B and returns the score of A.
+pub fn self_play(args: &Args, match_args: &MatchArgs) -> MatchScore {
+    let openings = read_openings(args, match_args);
+    let engines = create_engines(args, match_args);
+    let sprt = match (match_args.sprt_elo0, match_args.sprt_elo1) {
+        (Some(elo0), Some(elo1)) => Some((elo0, elo1)),
+        _ => None,
+    };
+    let (lower_bound, upper_bound) =
+        get_sprt_bounds(match_args.sprt_alpha, match_args.sprt_beta);
+
+    let mut random = Random::from_seed(args.seed);
+    let mut score = MatchScore::default();
+    let mut pgn = String::new();
+
+    println!("A: {}", engines[0].name);
+    println!("B: {}", engines[1].name);
+
+    for game_index in 0..match_args.games {
+        let opening = &openings[(game_index / 2) as usize % openings.len()];
+        let a_is_white = game_index % 2 == 0;
+        let (white, black) = if a_is_white {
+            (&engines[0], &engines[1])
+        } else {
+            (&engines[1], &engines[0])
+        };
+
+        let start_board = Board::from_fen_with_variant(opening, args.variant);
+        let game = play_game(
+            start_board,
+            white,
+            black,
+            match_args.max_plies,
+            &mut random,
+        );
+
+        let termination = game.termination.expect("the game has ended");
+        match (game.result.get_win_color(), a_is_white) {
+            (None, _) => score.draws += 1,
+            (Some(Color::White), true) | (Some(Color::Black), false) => {
+                score.wins += 1
+            }
+            _ => score.losses += 1,
+        }
+
+        let tags = [
+            ("Event", String::from("leucippus match")),
+            ("Site", String::from("?")),
+            ("Round", (game_index + 1).to_string()),
+            ("White", white.name.clone()),
+            ("Black", black.name.clone()),
+            ("Termination", termination.to_string()),
+        ];
+        pgn.push_str(&format_pgn(
+            &tags,
+            &game.start_board,
+            &format_san_line(&game.start_board, &game.moves),
+            game.result,
+        ));
+
+        println!(
+            "game {}/{}: {} - {} {} ({}, {} plies) | A: +{} ={} -{}",
+            game_index + 1,
+            match_args.games,
+            if a_is_white { "A" } else { "B" },
+            if a_is_white { "B" } else { "A" },
+            format_result(game.result),
+            termination,
+            game.moves.len(),
+            score.wins,
+            score.draws,
+            score.losses,
+        );
+
+        if let Some((elo0, elo1)) = sprt {
+            let llr = score.get_sprt_llr(elo0, elo1);
+            if llr >= upper_bound || llr <= lower_bound {
+                break;
+            }
+        }
+    }
+
+    if let Some(path) = &match_args.pgn {
+        fs::write(path, pgn)
+            .unwrap_or_else(|e| panic!("Could not write PGN to {path}: {e}"));
+    }
+
+    println!(
+        "A vs B: +{} ={} -{} | score {:.1}% | Elo {:+.1} +/- {:.1}",
+        score.wins,
+        score.draws,
+        score.losses,
+        100.0 * score.get_score(),
+        score.get_elo(),
+        score.get_elo_error(),
+    );
+
+    if let Some((elo0, elo1)) = sprt {
+        let llr = score.get_sprt_llr(elo0, elo1);
+        let verdict = if llr >= upper_bound {
+            "H1 accepted"
+        } else if llr <= lower_bound {
+            "H0 accepted"
+        } else {
+            "inconclusive"
+        };
+        println!(
+            "SPRT elo0 {elo0} elo1 {elo1}: llr {llr:.2} [{lower_bound:.2}, {upper_bound:.2}] {verdict}"
+        );
+    }
+
+    score
+}
+
+fn read_openings(args: &Args, match_args: &MatchArgs) -> Vec<String> {
+    let Some(path) = &match_args.openings else {
+        return vec![args.fen.clone()];
+    };
+
+    let openings: Vec<String> = fs::read_to_string(path)
+        .unwrap_or_else(|e| panic!("Could not read openings from {path}: {e}"))
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(String::from)
+        .collect();
+
+    if openings.is_empty() {
+        panic!("Invalid openings: {path} contains no positions");
+    }
+
+    openings
+}
+
+fn create_engines(args: &Args, match_args: &MatchArgs) -> [Engine; 2] {
+    [
+        create_engine(
+            args,
+            match_args.a_mode,
+            Args {
+                exploration_constant: match_args
+                    .a_exploration_constant
+                    .unwrap_or(args.exploration_constant),
+                playout_policy: match_args
+                    .a_playout_policy
+                    .unwrap_or(args.playout_policy),
+                max_iterations: match_args
+                    .a_max_iterations
+                    .unwrap_or(args.max_iterations),
+                max_time: match_args.a_max_time.or(args.max_time),
+                ..args.clone()
+            },
+        ),
+        create_engine(
+            args,
+            match_args.b_mode,
+            Args {
+                exploration_constant: match_args
+                    .b_exploration_constant
+                    .unwrap_or(args.exploration_constant),
+                playout_policy: match_args
+                    .b_playout_policy
+                    .unwrap_or(args.playout_policy),
+                max_iterations: match_args
+                    .b_max_iterations
+                    .unwrap_or(args.max_iterations),
+                max_time: match_args.b_max_time.or(args.max_time),
+                ..args.clone()
+            },
+        ),
+    ]
+}
+
+fn create_engine(args: &Args, mode: EngineMode, engine_args: Args) -> Engine {
+    if mode == EngineMode::Mcts
+        && engine_args.max_iterations == u64::MAX
+        && engine_args.max_time.is_none()
+    {
+        panic!("Invalid match: mcts engines need iteration or time limits");
+    }
+
+    let name = match mode {
+        EngineMode::Mcts => format!(
+            "mcts c={} playout={:?} iterations={} time={}",
+            engine_args.exploration_constant,
+            engine_args.playout_policy,
+            engine_args.max_iterations,
+            engine_args
+                .max_time
+                .map_or(String::from("-"), |t| format!("{t}ms")),
+        ),
+        EngineMode::Random => String::from("random"),
+    };
+
+    Engine {
+        args: Args {
+            seed: args.seed,
+            ..engine_args
+        },
+        mode,
+        name,
+    }
+}
+
+fn play_game(
+    start_board: Board,
+    white: &Engine,
+    black: &Engine,
+    max_plies: u64,
+    random: &mut Random,
+) -> Game {
+    let mut game = Game::new(start_board);
+
+    loop {
+        if game.evaluate(random).is_conclusive() {
+            return game;
+        }
+
+        if game.moves.len() as u64 >= max_plies {
+            game.finish(BoardEvaluation::Draw, Termination::MaxPlies);
+            return game;
+        }
+
+        let engine = match game.get_board().our_color {
+            Color::Black => black,
+            Color::White => white,
+        };
+        let m = choose_move(engine, &game, random);
+        game.play(&m);
+    }
+}
+
+fn choose_move(engine: &Engine, game: &Game, random: &mut Random) -> Move {
+    let m = match engine.mode {
+        EngineMode::Mcts => search_best_move(game, &engine.args, random),
+        EngineMode::Random => {
+            generate_move(&mut game.get_board().clone(), random)
+        }
+    };
+
+    m.expect("an inconclusive position must have a legal move")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn match_args(games: u64) -> MatchArgs {
+        MatchArgs {
+            games,
+            openings: None,
+            max_plies: 40,
+            pgn: None,
+            a_mode: EngineMode::Mcts,
+            a_exploration_constant: None,
+            a_playout_policy: None,
+            a_max_iterations: Some(30),
+            a_max_time: None,
+            b_mode: EngineMode::Random,
+            b_exploration_constant: None,
+            b_playout_policy: None,
+            b_max_iterations: None,
+            b_max_time: None,
+            sprt_elo0: None,
+            sprt_elo1: None,
+            sprt_alpha: 0.05,
+            sprt_beta: 0.05,
+        }
+    }
+
+    #[test]
+    fn it_plays_all_games_of_a_match() {
+        let score = self_play(&Args::default(), &match_args(2));
+        assert_eq!(score.get_games(), 2);
+    }
+
+    #[test]
+    fn it_stops_a_match_when_the_sprt_is_decided() {
+        let args = Args {
+            fen: String::from("7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1"),
+            ..Args::default()
+        };
+        // every opening is a mate in 1, so engine A wins with white and loses
+        // as black, until the SPRT gives up
+        let mut match_args = match_args(1000);
+        match_args.sprt_elo0 = Some(0.0);
+        match_args.sprt_elo1 = Some(400.0);
+
+        let score = self_play(&args, &match_args);
+        assert!(score.get_games() < 1000);
+    }
+
+    #[test]
+    fn it_plays_a_game_until_the_king_explodes() {
+        let engine = Engine {
+            args: Args {
+                max_iterations: 16,
+                ..Args::default()
+            },
+            mode: EngineMode::Mcts,
+            name: String::from("A"),
+        };
+        let mut random = Random::from_seed(0);
+
+        let game = play_game(
+            Board::from_fen("7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1"),
+            &engine,
+            &engine,
+            10,
+            &mut random,
+        );
+
+        assert_eq!(game.result, BoardEvaluation::WinWhite);
+        assert_eq!(
+            format_san_line(&game.start_board, &game.moves),
+            vec![String::from("Nxh7#")]
+        );
+        assert_eq!(game.termination, Some(Termination::Adjudicated));
+    }
+}
@@ -0,0 +1,236 @@
+use std::fs;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::common::random::Random;
+use crate::model::args::Args;
+use crate::model::board::Board;
+use crate::model::color::Color;
+use crate::model::epd_position::EpdPosition;
+use crate::model::r#move::Move;
+use crate::model::testsuite_args::TestsuiteArgs;
+use crate::model::tree::Tree;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
+use crate::move_generator::legal_moves::generate_moves;
+use crate::search::interpret::get_best_child_index;
+use crate::search::search::is_out_of_time;
+use crate::search::search::search_iteration;
+use crate::view::format_san::format_san;
+
+/// Search statistics at the moment a position was solved. A position counts
+/// as solved only if the solution was kept until the search ended.
+struct Solution {
+    elapsed: Duration,
+    iterations: u64,
+}
+
+/// Runs the search on every position of the EPD file and returns the number
+/// of solved positions.
+pub fn testsuite(args: &Args, testsuite_args: &TestsuiteArgs) -> u64 {
+    if args.max_iterations == u64::MAX && args.max_time.is_none() {
+        panic!("Invalid testsuite: set --max-iterations or --max-time");
+    }
+
+    let positions: Vec<EpdPosition> = fs::read_to_string(&testsuite_args.file)
+        .unwrap_or_else(|e| {
+            panic!("Could not read test suite {}: {e}", testsuite_args.file)
+        })
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(EpdPosition::from_epd)
+        .collect();
+
+    let mut solved = 0;
+    for position in &positions {
+        let (solution, best_move) = solve_position(position, args);
+
+        match solution {
+            Some(s) => {
+                solved += 1;
+                println!(
+                    "{}: solved after {} iterations ({} ms), best move {best_move}",
+                    position.get_name(),
+                    s.iterations,
+                    s.elapsed.as_millis(),
+                );
+            }
+            None => println!(
+                "{}: unsolved, best move {best_move}, expected {}",
+                position.get_name(),
+                format_expectation(position),
+            ),
+        }
+    }
+
+    let total = positions.len() as u64;
+    println!(
+        "solved {solved}/{total} ({:.1}%)",
+        100.0 * solved as f64 / total.max(1) as f64
+    );
+    if solved < testsuite_args.min_solved {
+        println!(
+            "regression: expected at least {} solved positions",
+            testsuite_args.min_solved
+        );
+    }
+
+    solved
+}
+
+/// Returns the solution (if any) and the best move in SAN.
+fn solve_position(
+    position: &EpdPosition,
+    args: &Args,
+) -> (Option<Solution>, String) {
+    let board = Board::from_fen_with_variant(&position.fen, args.variant);
+    let best_moves = resolve_moves(&board, &position.best_moves);
+    let avoid_moves = resolve_moves(&board, &position.avoid_moves);
+
+    let mut tree = Tree::new(board.clone());
+    tree.configure(args);
+    let mut random = Random::from_seed(args.seed);
+    let start_time = Instant::now();
+    let mut solution = None;
+
+    for i in 1..=args.max_iterations {
+        if is_out_of_time(args, start_time) {
+            break;
+        }
+        search_iteration(&mut tree, &mut random, args.playout_policy);
+
+        if !is_solved(&tree, position, &best_moves, &avoid_moves) {
+            solution = None;
+        } else if solution.is_none() {
+            solution = Some(Solution {
+                elapsed: start_time.elapsed(),
+                iterations: i,
+            });
+        }
+    }
+
+    let best_move = get_best_move(&tree)
+        .map_or(String::from("-"), |m| format_san(&board, &m));
+
+    (solution, best_move)
+}
+
+fn is_solved(
+    tree: &Tree,
+    position: &EpdPosition,
+    best_moves: &[Move],
+    avoid_moves: &[Move],
+) -> bool {
+    let Some(best_move) = get_best_move(tree) else {
+        return false;
+    };
+
+    if !best_moves.is_empty() && !best_moves.contains(&best_move) {
+        return false;
+    }
+    if avoid_moves.contains(&best_move) {
+        return false;
+    }
+
+    let root = tree.get_root();
+    let their_color = match root.our_color {
+        Color::Black => Color::White,
+        Color::White => Color::Black,
+    };
+    match position.direct_mate {
+        Some(n) if n > 0 => {
+            root.evaluation.get_win_color() == Some(root.our_color)
+        }
+        Some(_) => root.evaluation.get_win_color() == Some(their_color),
+        None => true,
+    }
+}
+
+fn get_best_move(tree: &Tree) -> Option<Move> {
+    get_best_child_index(tree, TREE_NODE_ROOT_INDEX)
+        .map(|i| tree.get_node(i).get_last_move())
+}
+
+/// Finds the legal moves given in SAN (or UCI), check and mate markers are
+/// optional.
+fn resolve_moves(board: &Board, sans: &[String]) -> Vec<Move> {
+    let legal_moves = generate_moves(&mut board.clone());
+
+    sans.iter()
+        .map(|san| {
+            let san = san.trim_end_matches(['+', '#']);
+            *legal_moves
+                .iter()
+                .find(|m| {
+                    format_san(board, m).trim_end_matches(['+', '#']) == san
+                        || m.to_string() == san
+                })
+                .unwrap_or_else(|| {
+                    panic!(
+                        "Invalid EPD: {san} is not a legal move in {}",
+                        board.to_fen()
+                    )
+                })
+        })
+        .collect()
+}
+
+fn format_expectation(position: &EpdPosition) -> String {
+    let mut expectations = Vec::new();
+    if !position.best_moves.is_empty() {
+        expectations.push(format!("bm {}", position.best_moves.join(" ")));
+    }
+    if !position.avoid_moves.is_empty() {
+        expectations.push(format!("am {}", position.avoid_moves.join(" ")));
+    }
+    if let Some(n) = position.direct_mate {
+        expectations.push(format!("dm {n}"));
+    }
+    expectations.join(", ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn run_suite(name: &str, epd: &str, max_iterations: u64) -> u64 {
+        let path = std::env::temp_dir()
+            .join(format!("leucippus-{name}-{}.epd", std::process::id()));
+        let path = path.to_str().unwrap();
+        fs::write(path, epd).unwrap();
+
+        let args = Args {
+            max_iterations,
+            ..Args::default()
+        };
+        let testsuite_args = TestsuiteArgs {
+            file: String::from(path),
+            min_solved: 0,
+        };
+        let solved = testsuite(&args, &testsuite_args);
+        fs::remove_file(path).unwrap();
+
+        solved
+    }
+
+    #[test]
+    fn it_solves_best_move_and_direct_mate_positions() {
+        let epd = "# mate in 1\n\
+            7k/7p/5N1P/8/8/8/2q5/K7 w - - bm Nxh7#; id \"bm\";\n\
+            7k/7p/5N1P/8/8/8/2q5/K7 w - - dm 1; id \"dm\";\n";
+        assert_eq!(run_suite("solved", epd, 16), 2);
+    }
+
+    #[test]
+    fn it_does_not_solve_positions_with_wrong_expectations() {
+        let epd = "7k/7p/5N1P/8/8/8/2q5/K7 w - - am Nxh7; id \"am\";\n\
+            7k/7p/5N1P/8/8/8/2q5/K7 w - - dm -1; id \"mated\";\n";
+        assert_eq!(run_suite("unsolved", epd, 16), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid EPD: Qh5 is not a legal move")]
+    fn it_rejects_illegal_expected_moves() {
+        resolve_moves(&Board::new(), &[String::from("Qh5")]);
+    }
+}
@@ -2,45 +2,109 @@ use std::ops::Range;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
-// https://en.wikipedia.org/wiki/Linear_congruential_generator#Parameters_in_common_use
-const A: u64 = 48271;
-const C: u64 = 0;
-const M: u64 = 2147483647;
+// https://prng.di.unimi.it/xoshiro256starstar.c
+const JUMP: [u64; 4] = [
+    0x180ec6d33cfd0aba,
+    0xd5a61266f0c9392c,
+    0xa9582618e03fc9aa,
+    0x39abdc4529b1661c,
+];
 
+/// xoshiro256** pseudo random number generator, seeded with splitmix64.
+/// See: https://prng.di.unimi.it
+#[derive(Clone, Debug, PartialEq)]
 pub struct Random {
-    seed: u64,
+    state: [u64; 4],
 }
 
 impl Random {
     pub fn new() -> Self {
-        Self {
-            seed: SystemTime::now()
+        Self::from_seed(
+            SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap()
-                .as_millis() as u64
-                % M,
-        }
+                .as_nanos() as u64,
+        )
     }
 
     pub fn from_seed(seed: u64) -> Self {
-        Self { seed }
+        let mut splitmix_state = seed;
+        let mut state = [0; 4];
+        for s in &mut state {
+            *s = splitmix64(&mut splitmix_state);
+        }
+        Self { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result =
+            self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
     }
 
     pub fn next(&mut self) -> u32 {
-        self.seed = (A * self.seed + C) % M;
-        self.seed as u32
+        (self.next_u64() >> 32) as u32
+    }
+
+    /// Uniformly distributed in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
     }
 
+    /// Unbiased, uses Lemire's multiply and reject method.
+    /// See: https://arxiv.org/abs/1805.10941
     pub fn next_range(&mut self, range: Range<u32>) -> u32 {
-        range.start + self.next() % (range.end - range.start)
+        let span = range.end - range.start;
+        let threshold = span.wrapping_neg() % span;
+
+        loop {
+            let product = self.next() as u64 * span as u64;
+            if (product as u32) >= threshold {
+                return range.start + (product >> 32) as u32;
+            }
+        }
     }
 
-    pub fn pick_element<'a, T>(&'a mut self, list: &'a Vec<T>) -> Option<&T> {
+    pub fn pick_element<'a, T>(&mut self, list: &'a Vec<T>) -> Option<&'a T> {
         if list.is_empty() {
             return None;
         }
 
-        Some(&list[self.next() as usize % list.len()])
+        Some(&list[self.next_range(0..list.len() as u32) as usize])
+    }
+
+    /// Picks an index with a probability proportional to its weight. Returns
+    /// `None` if there are no positive weights.
+    pub fn choose_weighted(&mut self, weights: &[f64]) -> Option<usize> {
+        let total: f64 = weights.iter().filter(|w| **w > 0.0).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut target = self.next_f64() * total;
+        let mut last_positive = None;
+        for (i, weight) in weights.iter().enumerate() {
+            if *weight <= 0.0 {
+                continue;
+            }
+            if target < *weight {
+                return Some(i);
+            }
+            target -= weight;
+            last_positive = Some(i);
+        }
+
+        // only reachable through rounding errors
+        last_positive
     }
 
     /// see https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle#The_modern_algorithm
@@ -53,6 +117,39 @@ impl Random {
             list.swap(i as usize, j as usize);
         }
     }
+
+    /// Advances the generator by 2^128 steps, which is equivalent to 2^128
+    /// calls of `next_u64`.
+    pub fn jump(&mut self) {
+        let mut state = [0; 4];
+        for jump in JUMP {
+            for b in 0..64 {
+                if jump & (1 << b) != 0 {
+                    for (s, t) in state.iter_mut().zip(self.state) {
+                        *s ^= t;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.state = state;
+    }
+
+    /// Returns a generator for an independent stream, e.g. for another
+    /// thread. The streams do not overlap for the first 2^128 numbers.
+    pub fn split(&mut self) -> Random {
+        let stream = self.clone();
+        self.jump();
+        stream
+    }
+}
+
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
 }
 
 #[cfg(test)]
@@ -96,53 +193,110 @@ mod test {
 
         random.shuffle(&mut list);
 
-        assert_eq!(list, vec![8, 1, 9, 5, 6, 10, 3, 2, 7, 4]);
+        assert_eq!(list, vec![8, 4, 9, 10, 2, 5, 7, 1, 3, 6]);
     }
 
     #[test]
     fn it_generates_the_same_random_numbers_with_one_seed() {
         let mut random = Random::from_seed(7);
-        assert_eq!(random.next(), 337897);
-        assert_eq!(random.next(), 1278240558);
-        assert_eq!(random.next(), 449829614);
-        assert_eq!(random.next(), 518142577);
+        assert_eq!(random.next(), 3008953079);
+        assert_eq!(random.next(), 1197227414);
+        assert_eq!(random.next(), 3606172489);
+        assert_eq!(random.next(), 4213782643);
     }
 
     #[test]
     fn it_generates_the_same_random_numbers_with_another_seed() {
         let mut random = Random::from_seed(123456);
-        assert_eq!(random.next(), 1664377282);
-        assert_eq!(random.next(), 1645061505);
-        assert_eq!(random.next(), 1261092736);
-        assert_eq!(random.next(), 1636001594);
+        assert_eq!(random.next(), 550889705);
+        assert_eq!(random.next(), 640065297);
+        assert_eq!(random.next(), 3084775289);
+        assert_eq!(random.next(), 1140657748);
     }
 
     #[test]
     fn it_generates_the_same_random_numbers_with_seed_in_a_range() {
         let mut random = Random::from_seed(10);
+        assert_eq!(random.next_range(2..4), 3);
+        assert_eq!(random.next_range(2..4), 2);
         assert_eq!(random.next_range(2..4), 2);
         assert_eq!(random.next_range(2..4), 2);
         assert_eq!(random.next_range(2..4), 2);
         assert_eq!(random.next_range(2..4), 2);
-        assert_eq!(random.next_range(2..4), 3);
-        assert_eq!(random.next_range(2..4), 3);
-        assert_eq!(random.next_range(2..4), 3);
         assert_eq!(random.next_range(2..4), 3);
         assert_eq!(random.next_range(2..4), 2);
+        assert_eq!(random.next_range(2..4), 3);
     }
 
     #[test]
     fn it_picks_random_elements_from_a_list() {
         let mut random = Random::from_seed(10);
         let list = vec![1, 2, 3, 4, 5, 6, 7, 8];
-        assert_eq!(*random.pick_element(&list).unwrap(), 7);
-        assert_eq!(*random.pick_element(&list).unwrap(), 5);
-        assert_eq!(*random.pick_element(&list).unwrap(), 3);
-        assert_eq!(*random.pick_element(&list).unwrap(), 3);
-        assert_eq!(*random.pick_element(&list).unwrap(), 4);
         assert_eq!(*random.pick_element(&list).unwrap(), 8);
-        assert_eq!(*random.pick_element(&list).unwrap(), 8);
-        assert_eq!(*random.pick_element(&list).unwrap(), 6);
+        assert_eq!(*random.pick_element(&list).unwrap(), 4);
         assert_eq!(*random.pick_element(&list).unwrap(), 1);
+        assert_eq!(*random.pick_element(&list).unwrap(), 1);
+        assert_eq!(*random.pick_element(&list).unwrap(), 4);
+        assert_eq!(*random.pick_element(&list).unwrap(), 2);
+        assert_eq!(*random.pick_element(&list).unwrap(), 7);
+        assert_eq!(*random.pick_element(&list).unwrap(), 2);
+        assert_eq!(*random.pick_element(&list).unwrap(), 7);
+    }
+
+    #[test]
+    fn it_matches_the_reference_implementations() {
+        let mut state = 0;
+        assert_eq!(splitmix64(&mut state), 0xe220a8397b1dcdaf);
+
+        let mut random = Random {
+            state: [1, 2, 3, 4],
+        };
+        assert_eq!(random.next_u64(), 11520);
+        assert_eq!(random.next_u64(), 0);
+        assert_eq!(random.next_u64(), 1509978240);
+        assert_eq!(random.next_u64(), 1215971899390074240);
+    }
+
+    #[test]
+    fn it_samples_ranges_without_bias() {
+        let mut random = Random::from_seed(7);
+        let mut counts = [0; 3];
+        for _ in 0..30000 {
+            counts[random.next_range(0..3) as usize] += 1;
+        }
+
+        assert!(counts.iter().all(|c| (9700..10300).contains(c)));
+        assert!((0..1000).all(|_| random.next_range(5..6) == 5));
+    }
+
+    #[test]
+    fn it_chooses_indices_proportional_to_their_weights() {
+        let mut random = Random::from_seed(7);
+        let mut counts = [0; 4];
+        for _ in 0..40000 {
+            let i = random.choose_weighted(&[1.0, 0.0, 3.0, -1.0]).unwrap();
+            counts[i] += 1;
+        }
+
+        assert!((9500..10500).contains(&counts[0]));
+        assert_eq!(counts[1], 0);
+        assert!((29500..30500).contains(&counts[2]));
+        assert_eq!(counts[3], 0);
+        assert_eq!(random.choose_weighted(&[]), None);
+        assert_eq!(random.choose_weighted(&[0.0, -2.0]), None);
+    }
+
+    #[test]
+    fn it_splits_into_independent_streams() {
+        let mut random = Random::from_seed(7);
+        let mut stream = random.split();
+
+        let mut jumped = Random::from_seed(7);
+        jumped.jump();
+        assert_eq!(random, jumped);
+
+        let mut unsplit = Random::from_seed(7);
+        assert_eq!(stream.next_u64(), unsplit.next_u64());
+        assert_ne!(random.next_u64(), stream.next_u64());
     }
 }
@@ -0,0 +1,575 @@
+use std::fs;
+
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::r#move::Move;
+use crate::model::r#move::MoveSpecial;
+use crate::model::tree::Tree;
+use crate::model::tree_node::TreeNode;
+use crate::model::tree_node::TreeNodeScore;
+use crate::model::tree_node::MOVES_NOT_GENERATED;
+use crate::model::types::TreeNodeIndex;
+use crate::model::variant::Variant;
+
+const TREE_FILE_MAGIC: &[u8; 4] = b"LTRE";
+pub const TREE_FILE_VERSION: u16 = 4;
+const NO_PARENT: u64 = u64::MAX;
+const NO_SPECIAL: u8 = u8::MAX;
+/// Bytes of a node, see `encode_tree`.
+const NODE_BYTES: usize = 49;
+
+/// Writes the tree to a temporary file first and renames it afterwards, so an
+/// interrupted save never destroys the previous tree file.
+pub fn save_tree(tree: &Tree, path: &str) {
+    let tmp_path = format!("{path}.tmp");
+    fs::write(&tmp_path, encode_tree(tree))
+        .unwrap_or_else(|e| panic!("Could not save tree to {tmp_path}: {e}"));
+    fs::rename(&tmp_path, path)
+        .unwrap_or_else(|e| panic!("Could not save tree to {path}: {e}"));
+}
+
+/// Loads a tree and checks that it was built for the given board.
+pub fn load_tree(path: &str, board: &Board) -> Result<Tree, String> {
+    let bytes = fs::read(path)
+        .map_err(|e| format!("Could not load tree from {path}: {e}"))?;
+    let tree = decode_tree(&bytes)?;
+
+    if tree.get_board().variant != board.variant {
+        return Err(format!(
+            "Invalid tree file: tree was built for {:?} but the variant is {:?}",
+            tree.get_board().variant,
+            board.variant
+        ));
+    }
+    if tree.get_board().get_hash() != board.get_hash() {
+        return Err(format!(
+            "Invalid tree file: tree was built for '{}' but the position is '{}'",
+            tree.get_board().to_fen(),
+            board.to_fen()
+        ));
+    }
+
+    Ok(tree)
+}
+
+/// Layout (little endian):
+/// magic "LTRE", version u16, FEN length u16, FEN bytes, variant u8, node
+/// count u64 and
+/// per node: board hash u64, parent index u64, move from u8, move to u8,
+/// move special u8, evaluation u8, our color u8, draws u64, black wins u64,
+/// white wins u64, child count u16 and move count u16 (65535 if the moves were
+/// not generated yet). The children of a node are the next nodes with it as
+/// parent, reserved children of untried moves included.
+pub fn encode_tree(tree: &Tree) -> Vec<u8> {
+    let fen = tree.get_board().to_fen();
+    let mut bytes =
+        Vec::with_capacity(16 + fen.len() + NODE_BYTES * tree.get_size());
+
+    bytes.extend_from_slice(TREE_FILE_MAGIC);
+    bytes.extend_from_slice(&TREE_FILE_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&(fen.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(fen.as_bytes());
+    bytes.push(encode_variant(tree.get_board().variant));
+    bytes.extend_from_slice(&(tree.get_size() as u64).to_le_bytes());
+
+    for i in 0..tree.get_size() as TreeNodeIndex {
+        let node = tree.get_node(i);
+        let score = tree.get_score(i);
+        let parent_index = node.parent_index.map_or(NO_PARENT, |p| p as u64);
+
+        bytes.extend_from_slice(&node.board_hash.to_le_bytes());
+        bytes.extend_from_slice(&parent_index.to_le_bytes());
+        encode_move(&mut bytes, node.get_last_move());
+        bytes.push(encode_evaluation(node.evaluation));
+        bytes.push(encode_color(node.our_color));
+        bytes.extend_from_slice(&score.draws.to_le_bytes());
+        bytes.extend_from_slice(&score.wins_black.to_le_bytes());
+        bytes.extend_from_slice(&score.wins_white.to_le_bytes());
+        bytes.extend_from_slice(&node.num_children.to_le_bytes());
+        bytes.extend_from_slice(&node.num_moves.to_le_bytes());
+    }
+
+    bytes
+}
+
+/// Decodes a tree written by `encode_tree`. Malformed files are rejected with
+/// an error instead of a panic and the counts in the file are only trusted as
+/// far as the remaining bytes can hold them.
+pub fn decode_tree(bytes: &[u8]) -> Result<Tree, String> {
+    let mut reader = ByteReader { bytes, position: 0 };
+
+    if reader.read(4)? != TREE_FILE_MAGIC {
+        return Err(String::from("Invalid tree file: not a tree file"));
+    }
+
+    let version = reader.read_u16()?;
+    if version != TREE_FILE_VERSION {
+        return Err(format!(
+            "Invalid tree file: version {version} is not supported, expected {TREE_FILE_VERSION}"
+        ));
+    }
+
+    let fen_len = reader.read_u16()? as usize;
+    let fen = String::from_utf8(reader.read(fen_len)?.to_vec())
+        .map_err(|_| "Invalid tree file: FEN is not valid UTF-8")?;
+    let variant = decode_variant(reader.read_u8()?)?;
+    let board = Board::try_from_fen_with_variant(&fen, variant)
+        .map_err(|_| format!("Invalid tree file: invalid FEN '{fen}'"))?;
+
+    let node_count = reader.read_u64()?;
+    if node_count == 0 {
+        return Err(String::from(
+            "Invalid tree file: the root node is missing",
+        ));
+    }
+    let node_count = TreeNodeIndex::try_from(node_count).map_err(|_| {
+        format!("Invalid tree file: {node_count} nodes do not fit in a tree")
+    })?;
+
+    let capacity = (node_count as usize).min(reader.remaining() / NODE_BYTES);
+    let mut nodes: Vec<TreeNode> = Vec::with_capacity(capacity);
+    let mut scores: Vec<TreeNodeScore> = Vec::with_capacity(capacity);
+    let mut children_found: Vec<u16> = Vec::with_capacity(capacity);
+
+    for self_index in 0..node_count {
+        let board_hash = reader.read_u64()?;
+        let parent_index = decode_parent_index(reader.read_u64()?, self_index)?;
+        let last_move = decode_move(&mut reader)?;
+        let evaluation = decode_evaluation(reader.read_u8()?)?;
+        let our_color = decode_color(reader.read_u8()?)?;
+        let score = TreeNodeScore {
+            draws: reader.read_u64()?,
+            wins_black: reader.read_u64()?,
+            wins_white: reader.read_u64()?,
+        };
+        let num_children = reader.read_u16()?;
+        let num_moves = reader.read_u16()?;
+        if num_children == MOVES_NOT_GENERATED
+            || (num_moves != MOVES_NOT_GENERATED && num_children > num_moves)
+        {
+            return Err(format!(
+                "Invalid tree file: node {self_index} has more children than moves"
+            ));
+        }
+
+        if let Some(p) = parent_index {
+            add_child(
+                &mut nodes[p as usize],
+                p,
+                self_index,
+                &mut children_found[p as usize],
+            )?;
+        }
+        nodes.push(TreeNode {
+            board_hash,
+            evaluation,
+            first_child: 0,
+            last_move: last_move.into(),
+            num_children,
+            num_moves,
+            our_color,
+            parent_index,
+        });
+        scores.push(score);
+        children_found.push(0);
+    }
+
+    let missing = nodes
+        .iter()
+        .zip(children_found)
+        .position(|(node, found)| found != node.get_num_slots());
+    if let Some(index) = missing {
+        return Err(format!(
+            "Invalid tree file: children of node {index} are missing"
+        ));
+    }
+
+    if reader.remaining() != 0 {
+        return Err(String::from(
+            "Invalid tree file: unexpected data after the last node",
+        ));
+    }
+
+    Ok(Tree::from_nodes(board, nodes, scores))
+}
+
+/// The children of a node have to be stored contiguously and as many as the
+/// node expects.
+fn add_child(
+    parent: &mut TreeNode,
+    parent_index: TreeNodeIndex,
+    child_index: TreeNodeIndex,
+    found: &mut u16,
+) -> Result<(), String> {
+    if *found == parent.get_num_slots() {
+        return Err(format!(
+            "Invalid tree file: node {parent_index} has too many children"
+        ));
+    }
+    if *found == 0 {
+        parent.first_child = child_index;
+    } else if parent.first_child + *found as TreeNodeIndex != child_index {
+        return Err(format!(
+            "Invalid tree file: the children of node {parent_index} are not contiguous"
+        ));
+    }
+    *found += 1;
+    Ok(())
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn read(&mut self, len: usize) -> Result<&'a [u8], String> {
+        if len > self.remaining() {
+            return Err(String::from(
+                "Invalid tree file: unexpected end of file",
+            ));
+        }
+        let slice = &self.bytes[self.position..self.position + len];
+        self.position += len;
+        Ok(slice)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read(N)?);
+        Ok(array)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        Ok(self.read(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, String> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64, String> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+}
+
+/// Parents are always stored before their children, which also guarantees
+/// that the nodes form a tree.
+fn decode_parent_index(
+    parent_index: u64,
+    self_index: TreeNodeIndex,
+) -> Result<Option<TreeNodeIndex>, String> {
+    match (parent_index, self_index) {
+        (NO_PARENT, 0) => Ok(None),
+        (p, i) if i > 0 && p < i as u64 => Ok(Some(p as TreeNodeIndex)),
+        _ => Err(format!(
+            "Invalid tree file: node {self_index} has a bad parent"
+        )),
+    }
+}
+
+fn encode_move(bytes: &mut Vec<u8>, m: Move) {
+    bytes.push(m.from);
+    bytes.push(m.to);
+    bytes.push(m.special.map_or(NO_SPECIAL, |s| s as u8));
+}
+
+fn decode_move(reader: &mut ByteReader) -> Result<Move, String> {
+    Ok(Move {
+        from: decode_square(reader.read_u8()?)?,
+        to: decode_square(reader.read_u8()?)?,
+        special: decode_special(reader.read_u8()?)?,
+    })
+}
+
+fn decode_square(square: u8) -> Result<u8, String> {
+    if square >= 64 {
+        return Err(format!(
+            "Invalid tree file: square {square} is off the board"
+        ));
+    }
+    Ok(square)
+}
+
+fn decode_special(special: u8) -> Result<Option<MoveSpecial>, String> {
+    if special == NO_SPECIAL {
+        return Ok(None);
+    }
+
+    match MoveSpecial::ALL.get(special as usize) {
+        Some(special) => Ok(Some(*special)),
+        None => {
+            Err(format!("Invalid tree file: unknown move special {special}"))
+        }
+    }
+}
+
+fn encode_evaluation(evaluation: BoardEvaluation) -> u8 {
+    match evaluation {
+        BoardEvaluation::Draw => 0,
+        BoardEvaluation::Inconclusive => 1,
+        BoardEvaluation::WinBlack => 2,
+        BoardEvaluation::WinWhite => 3,
+    }
+}
+
+fn decode_evaluation(evaluation: u8) -> Result<BoardEvaluation, String> {
+    match evaluation {
+        0 => Ok(BoardEvaluation::Draw),
+        1 => Ok(BoardEvaluation::Inconclusive),
+        2 => Ok(BoardEvaluation::WinBlack),
+        3 => Ok(BoardEvaluation::WinWhite),
+        _ => Err(format!(
+            "Invalid tree file: unknown evaluation {evaluation}"
+        )),
+    }
+}
+
+fn encode_variant(variant: Variant) -> u8 {
+    match variant {
+        Variant::Atomic => 0,
+        Variant::Standard => 1,
+        Variant::KingOfTheHill => 2,
+        Variant::ThreeCheck => 3,
+        Variant::Antichess => 4,
+    }
+}
+
+fn decode_variant(variant: u8) -> Result<Variant, String> {
+    match variant {
+        0 => Ok(Variant::Atomic),
+        1 => Ok(Variant::Standard),
+        2 => Ok(Variant::KingOfTheHill),
+        3 => Ok(Variant::ThreeCheck),
+        4 => Ok(Variant::Antichess),
+        _ => Err(format!("Invalid tree file: unknown variant {variant}")),
+    }
+}
+
+fn encode_color(color: Color) -> u8 {
+    match color {
+        Color::Black => 0,
+        Color::White => 1,
+    }
+}
+
+fn decode_color(color: u8) -> Result<Color, String> {
+    match color {
+        0 => Ok(Color::Black),
+        1 => Ok(Color::White),
+        _ => Err(format!("Invalid tree file: unknown color {color}")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    fn build_tree() -> Tree {
+        let board = Board::from_fen(
+            "r3k2r/pPpppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
+        );
+        let mut tree = Tree::new(board.clone());
+        tree.add_node(board.clone(), Move::castle_short_white(), 0);
+        tree.add_node(board.clone(), Move::promote_queen_white(B7, A8), 0);
+        tree.set_untried_moves(
+            0,
+            &[Move::from_to(H1, G1), Move::castle_long_white()],
+        );
+        tree.add_node(board.clone(), Move::from_to(H1, G1), 0);
+        tree.add_node(board.clone(), Move::from_to(E8, D8), 1);
+        tree.set_untried_moves(1, &[]);
+        tree.set_untried_moves(5, &[Move::from_to(A1, B1)]);
+        tree.get_score_mut(0).wins_white = 5;
+        tree.get_score_mut(0).draws = 2;
+        tree.get_node_mut(2).evaluation = BoardEvaluation::WinWhite;
+        tree.get_score_mut(5).wins_black = 7;
+        tree
+    }
+
+    fn assert_trees_eq(left: &Tree, right: &Tree) {
+        assert_eq!(left.get_board(), right.get_board());
+        assert_eq!(left.get_size(), right.get_size());
+        for i in 0..left.get_size() as TreeNodeIndex {
+            let l = left.get_node(i);
+            let r = right.get_node(i);
+            assert_eq!(l.board_hash, r.board_hash);
+            assert_eq!(l.get_child_indices(), r.get_child_indices());
+            assert_eq!(
+                l.has_untried_moves_generated(),
+                r.has_untried_moves_generated()
+            );
+            assert_eq!(left.get_untried_moves(i), right.get_untried_moves(i));
+            assert_eq!(l.evaluation, r.evaluation);
+            assert_eq!(l.last_move, r.last_move);
+            assert_eq!(l.our_color, r.our_color);
+            assert_eq!(l.parent_index, r.parent_index);
+            assert_eq!(left.get_score(i), right.get_score(i));
+        }
+    }
+
+    #[test]
+    fn it_restores_an_encoded_tree() {
+        let tree = build_tree();
+        assert_trees_eq(&decode_tree(&encode_tree(&tree)).unwrap(), &tree);
+    }
+
+    #[test]
+    fn it_saves_and_loads_a_tree() {
+        let tree = build_tree();
+        let path = std::env::temp_dir()
+            .join(format!("leucippus-tree-{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        save_tree(&tree, path);
+        let loaded = load_tree(path, tree.get_board());
+        fs::remove_file(path).unwrap();
+
+        assert_trees_eq(&loaded.unwrap(), &tree);
+    }
+
+    #[test]
+    fn it_rejects_unknown_versions() {
+        let mut bytes = encode_tree(&build_tree());
+        bytes[4] = 5;
+        assert!(decode_tree(&bytes)
+            .err()
+            .unwrap()
+            .starts_with("Invalid tree file: version 5 is not supported"));
+    }
+
+    #[test]
+    fn it_rejects_truncated_files() {
+        let bytes = encode_tree(&build_tree());
+        for len in 0..bytes.len() {
+            assert!(decode_tree(&bytes[..len]).is_err());
+        }
+        assert_eq!(
+            decode_tree(&bytes[..bytes.len() - 1]).err(),
+            Some(String::from("Invalid tree file: unexpected end of file"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_node_counts_the_file_cannot_hold() {
+        let tree = build_tree();
+        let mut bytes = encode_tree(&tree);
+        let count = 9 + tree.get_board().to_fen().len();
+        bytes[count..count + 8]
+            .copy_from_slice(&(TreeNodeIndex::MAX as u64).to_le_bytes());
+        assert_eq!(
+            decode_tree(&bytes).err(),
+            Some(String::from("Invalid tree file: unexpected end of file"))
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_fields() {
+        let tree = build_tree();
+        let bytes = encode_tree(&tree);
+        let fen = 8;
+        let variant = fen + tree.get_board().to_fen().len();
+        let root_move = variant + 1 + 8 + 16;
+
+        let mut invalid_fen = bytes.clone();
+        invalid_fen[fen..variant].fill(b' ');
+        assert!(decode_tree(&invalid_fen)
+            .err()
+            .unwrap()
+            .starts_with("Invalid tree file: invalid FEN"));
+
+        let mut invalid_variant = bytes.clone();
+        invalid_variant[variant] = 9;
+        assert_eq!(
+            decode_tree(&invalid_variant).err(),
+            Some(String::from("Invalid tree file: unknown variant 9"))
+        );
+
+        let mut invalid_square = bytes.clone();
+        invalid_square[root_move] = 64;
+        assert_eq!(
+            decode_tree(&invalid_square).err(),
+            Some(String::from(
+                "Invalid tree file: square 64 is off the board"
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_children_which_are_not_contiguous() {
+        let tree = build_tree();
+        let mut bytes = encode_tree(&tree);
+        let nodes = 8 + tree.get_board().to_fen().len() + 1 + 8;
+        let parent = |i: usize| nodes + i * NODE_BYTES + 8;
+
+        // moving node 3 to node 1 leaves a gap in the children of the root
+        bytes[parent(3)..parent(3) + 8].copy_from_slice(&1u64.to_le_bytes());
+        assert_eq!(
+            decode_tree(&bytes).err(),
+            Some(String::from(
+                "Invalid tree file: the children of node 0 are not contiguous"
+            ))
+        );
+    }
+
+    #[test]
+    fn it_rejects_trees_of_other_positions() {
+        let tree = build_tree();
+        let path = std::env::temp_dir()
+            .join(format!("leucippus-other-tree-{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+
+        save_tree(&tree, path);
+        let result = load_tree(path, &Board::new());
+        fs::remove_file(path).unwrap();
+
+        assert!(result
+            .err()
+            .unwrap()
+            .starts_with("Invalid tree file: tree was built for"));
+    }
+
+    #[test]
+    fn it_restores_the_variant_of_an_encoded_tree() {
+        let board = Board::from_fen_with_variant(
+            "r3k2r/pPpppppp/8/8/8/8/PPPPPPPP/R3K2R w KQkq - 0 1",
+            Variant::Standard,
+        );
+        let tree = Tree::new(board);
+        assert_eq!(
+            decode_tree(&encode_tree(&tree))
+                .unwrap()
+                .get_board()
+                .variant,
+            Variant::Standard
+        );
+    }
+
+    #[test]
+    fn it_rejects_trees_of_other_variants() {
+        let tree = build_tree();
+        let path = std::env::temp_dir()
+            .join(format!("leucippus-variant-tree-{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+        let board = Board::from_fen_with_variant(
+            &tree.get_board().to_fen(),
+            Variant::Standard,
+        );
+
+        save_tree(&tree, path);
+        let result = load_tree(path, &board);
+        fs::remove_file(path).unwrap();
+
+        assert!(result
+            .err()
+            .unwrap()
+            .ends_with("but the variant is Standard"));
+    }
+}
@@ -7,19 +7,16 @@ use crate::move_generator::check::is_check;
 use crate::move_generator::legal_moves::generate_move;
 
 /// Draws:
-/// * insufficient material
-///     * kk
-///     * kkb
-///     * kkn
-///     * kkr
-///     * kknn
-/// * repetition
+/// * insufficient material (depends on the variant)
 /// * 50 move rule
 /// * stalemate
 ///
+/// Repetitions depend on the game history, which the board does not know, so
+/// callers check them with a `RepetitionTable` first.
+///
 /// Wins/Losses:
 /// * checkmate
-/// * exploded kings
+/// * game ends of the variant, e.g. exploded kings
 ///
 /// Inconclusive:
 /// * everything else
@@ -27,9 +24,9 @@ pub fn evaluate_board(
     board: &mut Board,
     random: &mut Random,
 ) -> BoardEvaluationResult {
-    if is_simple_win(board) {
+    if let Some(evaluation) = board.variant.evaluate_terminal(board) {
         return BoardEvaluationResult {
-            evaluation: make_win_by_board_color(board),
+            evaluation,
             random_move: None,
         };
     }
@@ -68,7 +65,7 @@ fn check_stalemate_or_checkmate(
 
     if no_moves && !in_check {
         return BoardEvaluationResult {
-            evaluation: BoardEvaluation::Draw,
+            evaluation: board.variant.evaluate_stalemate(board),
             random_move,
         };
     }
@@ -88,8 +85,7 @@ fn make_win_by_board_color(board: &Board) -> BoardEvaluation {
 }
 
 fn is_simple_draw(board: &Board) -> bool {
-    board.draw_by_repetition
-        || is_insufficient_material(board)
+    board.variant.is_insufficient_material(board)
         || is_draw_by_50_moves_rule(board)
 }
 
@@ -97,45 +93,6 @@ fn is_draw_by_50_moves_rule(board: &Board) -> bool {
     board.halfmove == 100
 }
 
-fn is_insufficient_material(board_with_2_kings: &Board) -> bool {
-    // TODO: this function assumes that the board has 2 kings on the board
-    if board_with_2_kings.pieces.our_pawns.len()
-        + board_with_2_kings.pieces.our_queens.len()
-        > 0
-    {
-        return false;
-    }
-
-    if board_with_2_kings.pieces.our_bishops.len()
-        + board_with_2_kings.pieces.our_knights.len()
-        + board_with_2_kings.pieces.our_rooks.len()
-        > 1
-    {
-        return false;
-    }
-
-    let mut total_pieces = 0;
-    let mut total_nbr_pieces = 0;
-    for i in 0..64 {
-        if let Some(piece) = board_with_2_kings.pieces.squares.data[i] {
-            total_pieces += 1;
-            if piece.is_bishop() || piece.is_knight() || piece.is_rook() {
-                total_nbr_pieces += 1;
-            }
-        }
-    }
-
-    if total_pieces <= 3 && total_nbr_pieces <= 1 {
-        return true;
-    }
-
-    false
-}
-
-fn is_simple_win(board: &Board) -> bool {
-    board.pieces.our_kings.is_empty()
-}
-
 #[cfg(test)]
 mod test {
     use crate::common::random::Random;
@@ -177,13 +134,6 @@ mod test {
     mod draw {
         use super::*;
 
-        #[test]
-        fn it_evaluates_a_board_flagged_with_repetition_as_draw() {
-            let mut board = Board::new();
-            board.draw_by_repetition = true;
-            assert_eq!(evaluate_board(&mut board), BoardEvaluation::Draw);
-        }
-
         #[test]
         fn it_evaluates_a_board_with_100_halfmoves_as_draw() {
             let mut board =
@@ -426,4 +376,101 @@ mod test {
             assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinWhite);
         }
     }
+
+    mod standard {
+        use super::*;
+        use crate::model::variant::Variant;
+
+        fn from_fen(fen: &str) -> Board {
+            Board::from_fen_with_variant(fen, Variant::Standard)
+        }
+
+        #[test]
+        fn it_evaluates_a_classic_checkmate_as_win() {
+            let mut board = from_fen("8/8/8/8/6N1/k7/6r1/2K2r2 w - - 0 1");
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinBlack);
+        }
+
+        #[test]
+        fn it_evaluates_a_board_with_one_knight_as_draw() {
+            let mut board = from_fen("8/2k2N2/8/8/8/8/3K4/8 w - - 0 1");
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::Draw);
+        }
+
+        #[test]
+        fn it_evaluates_a_board_with_one_rook_as_inconclusive() {
+            let mut board = from_fen("8/2k2R2/8/8/8/8/3K4/8 w - - 0 1");
+            assert_eq!(
+                evaluate_board(&mut board),
+                BoardEvaluation::Inconclusive
+            );
+        }
+    }
+
+    mod antichess {
+        use super::*;
+        use crate::model::variant::Variant;
+
+        fn from_fen(fen: &str) -> Board {
+            Board::from_fen_with_variant(fen, Variant::Antichess)
+        }
+
+        #[test]
+        fn it_evaluates_losing_all_pieces_as_win() {
+            let mut board = from_fen("8/8/8/8/8/8/8/7r w - - 0 1");
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinWhite);
+        }
+
+        #[test]
+        fn it_evaluates_a_stalemate_as_win_for_the_stalemated_side() {
+            let mut board = from_fen("8/8/8/8/8/p7/P7/8 w - - 0 1");
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinWhite);
+        }
+
+        #[test]
+        fn it_evaluates_a_lone_king_as_inconclusive() {
+            let mut board = from_fen("8/8/8/8/8/8/8/K6k w - - 0 1");
+            assert_eq!(
+                evaluate_board(&mut board),
+                BoardEvaluation::Inconclusive
+            );
+        }
+    }
+
+    mod king_of_the_hill {
+        use super::*;
+        use crate::model::variant::Variant;
+
+        #[test]
+        fn it_evaluates_a_king_on_the_hill_as_win() {
+            let mut board = Board::from_fen_with_variant(
+                "7k/8/8/8/3K4/8/8/8 b - - 0 1",
+                Variant::KingOfTheHill,
+            );
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinWhite);
+        }
+    }
+
+    mod three_check {
+        use super::*;
+        use crate::model::variant::Variant;
+
+        #[test]
+        fn it_evaluates_the_third_check_as_win() {
+            let mut board = Board::from_fen_with_variant(
+                "4k2R/8/8/8/8/8/8/4K3 b - - 0 1 +3+0",
+                Variant::ThreeCheck,
+            );
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::WinWhite);
+        }
+
+        #[test]
+        fn it_evaluates_bare_kings_as_draw() {
+            let mut board = Board::from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K3 w - - 0 1 +0+0",
+                Variant::ThreeCheck,
+            );
+            assert_eq!(evaluate_board(&mut board), BoardEvaluation::Draw);
+        }
+    }
 }
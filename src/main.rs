@@ -2,6 +2,11 @@ use bitboards::r#move::attacks::init_bishop_table;
 use bitboards::r#move::attacks::BishopTable;
 use clap::Parser;
 use model::args::Args;
+use model::command::Command;
+use cli::bench::bench;
+use cli::repl::repl;
+use cli::self_play::self_play;
+use cli::testsuite::testsuite;
 use search::search::search;
 
 use crate::bitboards::r#move::attacks::init_rook_table;
@@ -9,13 +14,11 @@ use crate::bitboards::r#move::attacks::init_rook_table;
 pub mod bitboards {
     pub mod model {
         pub mod bitboard;
-        pub mod move_type;
-        pub mod piece_type;
         pub mod position;
-        pub mod r#move;
     }
 
     pub mod r#move {
+        pub mod attack_maps;
         pub mod attacks;
         pub mod bishop_table;
         pub mod generate;
@@ -25,6 +28,7 @@ pub mod bitboards {
 
 pub mod common {
     pub mod random;
+    pub mod tree_file;
 }
 
 pub mod evaluation {
@@ -33,24 +37,44 @@ pub mod evaluation {
 
 pub mod model {
     pub mod args;
+    pub mod bench_args;
+    pub mod bench_result;
     pub mod board;
     pub mod board_castle;
     pub mod board_evaluation;
     pub mod board_evaluation_result;
     pub mod board_pieces;
     pub mod color;
+    pub mod command;
+    pub mod compact_move;
+    pub mod engine_mode;
+    pub mod epd_position;
     pub mod expansion_result;
+    pub mod file;
+    pub mod game;
+    pub mod match_args;
+    pub mod match_score;
     pub mod metrics_level;
     pub mod r#move;
+    pub mod move_list;
     pub mod piece;
     pub mod piece_type;
+    pub mod playout_policy;
+    pub mod rank;
+    pub mod repetition_table;
     pub mod selection_result;
     pub mod simulation_result;
+    pub mod square;
     pub mod squares;
+    pub mod termination;
+    pub mod testsuite_args;
     pub mod tree;
+    pub mod tree_dump_filter;
+    pub mod tree_dump_format;
     pub mod tree_node;
     pub mod tree_node_metrics;
     pub mod types;
+    pub mod variant;
 }
 
 pub mod move_generator {
@@ -61,11 +85,19 @@ pub mod move_generator {
     pub mod legal_moves;
     pub mod make_move;
     pub mod pawn_moves;
+    pub mod perft;
     pub mod queen_moves;
     pub mod rook_moves;
     pub mod sliding_moves;
 }
 
+pub mod cli {
+    pub mod bench;
+    pub mod repl;
+    pub mod self_play;
+    pub mod testsuite;
+}
+
 pub mod search {
     pub mod backpropagate;
     pub mod expand;
@@ -75,8 +107,22 @@ pub mod search {
     pub mod simulate;
 }
 
+pub mod variants {
+    pub mod antichess;
+    pub mod atomic;
+    pub mod king_of_the_hill;
+    pub mod standard;
+    pub mod three_check;
+}
+
 pub mod view {
+    pub mod dump_tree;
+    pub mod format_pgn;
+    pub mod format_san;
+    pub mod format_uci;
     pub mod print_metrics;
+    pub mod print_metrics_json;
+    pub mod tui;
 }
 
 fn main() {
@@ -92,7 +138,23 @@ fn main() {
     }
 
     let args = Args::parse();
-    search(args);
+    match args.command {
+        Some(Command::Bench(ref bench_args)) => {
+            bench(bench_args);
+        }
+        Some(Command::Match(ref match_args)) => {
+            self_play(&args, match_args);
+        }
+        Some(Command::Repl) => repl(args),
+        Some(Command::Testsuite(ref testsuite_args)) => {
+            if testsuite(&args, testsuite_args) < testsuite_args.min_solved {
+                std::process::exit(1);
+            }
+        }
+        None => {
+            search(args);
+        }
+    }
 }
 
 // rusts' const evaluation interpreter is slow (takes 50s on raspi5), so
@@ -1,23 +1,115 @@
 use clap::Parser;
 
+use super::command::Command;
 use super::metrics_level::MetricsLevel;
+use super::playout_policy::PlayoutPolicy;
+use super::tree_dump_format::TreeDumpFormat;
+use super::variant::Variant;
 
-/// MCTS Atomic chess
+/// MCTS Atomic chess (and other variants)
 #[derive(Clone, Debug, Parser)]
 #[command(author, version, verbatim_doc_comment)]
 pub struct Args {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Starting position as FEN.
     #[arg(short, long, value_parser, default_value_t = String::from("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"))]
     pub fen: String,
 
+    /// Comma separated moves in UCI notation played from the starting
+    /// position before the search, e.g. `--moves e2e4,e7e6`. Repetitions and
+    /// the 50 move rule take these moves into account.
+    #[arg(long, value_parser, value_delimiter = ',')]
+    pub moves: Vec<String>,
+
+    /// Rules the game is played with.
+    #[arg(long, value_enum, default_value_t = Variant::Atomic)]
+    pub variant: Variant,
+
+    /// Writes castling moves as the king taking its own rook, like the UCI
+    /// option `UCI_Chess960`. Positions with Shredder-FEN castling rights or
+    /// kings and rooks off their usual squares use this notation anyway.
+    #[arg(long, value_parser, default_value_t = false)]
+    pub chess960: bool,
+
     /// Maximal number of search iterations to be analyzed.
-    #[arg(short = 'i', long, value_parser, default_value_t = std::u64::MAX)]
+    #[arg(short = 'i', long, value_parser, default_value_t = u64::MAX)]
     pub max_iterations: u64,
 
+    /// Maximal search time in milliseconds.
+    #[arg(long, value_parser)]
+    pub max_time: Option<u64>,
+
+    /// Maximal number of search tree nodes, including the children reserved
+    /// for untried moves. Once the tree grows beyond, the least visited
+    /// subtrees are collapsed back into leaves.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub max_nodes: Option<u64>,
+
+    /// Visits a leaf of the search tree needs before children are added to it.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), default_value_t = 1)]
+    pub expansion_visits: u64,
+
+    /// Number of untried moves added as children when a node is expanded.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), default_value_t = 1)]
+    pub expansion_width: u64,
+
+    /// Weight of the exploration term of the UCT formula.
+    #[arg(long, value_parser, default_value_t = std::f64::consts::SQRT_2)]
+    pub exploration_constant: f64,
+
+    /// How moves are chosen during simulations.
+    #[arg(long, value_enum, default_value_t = PlayoutPolicy::Random)]
+    pub playout_policy: PlayoutPolicy,
+
     /// Level of metrics to show
     #[arg(short, long, value_enum, default_value_t = MetricsLevel::Full)]
     pub metrics_level: MetricsLevel,
 
+    /// Number of search iterations between two metrics reports.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), default_value_t = 50)]
+    pub report_interval: u64,
+
+    /// Number of best root moves to show together with their principal
+    /// variations.
+    #[arg(long = "multipv", value_parser, default_value_t = 1)]
+    pub multi_pv: usize,
+
+    /// File to dump the search tree to after the search has finished.
+    #[arg(long, value_parser)]
+    pub dump_tree: Option<String>,
+
+    /// Format of the tree dump.
+    #[arg(long, value_enum, default_value_t = TreeDumpFormat::Dot)]
+    pub dump_format: TreeDumpFormat,
+
+    /// Only dump tree nodes with at least this many visits.
+    #[arg(long, value_parser, default_value_t = 0)]
+    pub dump_min_visits: u64,
+
+    /// Only dump tree nodes up to this depth, the root has depth 0.
+    #[arg(long, value_parser)]
+    pub dump_max_depth: Option<usize>,
+
+    /// Only dump the principal variation.
+    #[arg(long, value_parser, default_value_t = false)]
+    pub dump_only_pv: bool,
+
+    /// File to resume the search tree from. The tree must have been saved for
+    /// the same starting position.
+    #[arg(long, value_parser)]
+    pub load_tree: Option<String>,
+
+    /// File to save the search tree to, every save interval and after the
+    /// search has finished.
+    #[arg(long, value_parser)]
+    pub save_tree: Option<String>,
+
+    /// Number of search iterations between two saves of the search tree.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), default_value_t = 100000)]
+    pub save_interval: u64,
+
     /// Random number seed used for pseudo random number generation.
     #[arg(short, long, value_parser, default_value_t = 19870826)]
     pub seed: u64,
@@ -29,6 +121,30 @@ pub struct Args {
 // way around.
 impl Default for Args {
     fn default() -> Self {
-        Self::parse_from(Vec::<String>::new().into_iter())
+        Self::parse_from(Vec::<String>::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_comma_separated_moves_before_a_subcommand() {
+        let args =
+            Args::parse_from(["leucippus", "--moves", "e2e4,e7e6", "repl"]);
+        assert_eq!(args.moves, vec!["e2e4", "e7e6"]);
+        assert!(matches!(args.command, Some(Command::Repl)));
+    }
+
+    #[test]
+    fn it_rejects_moves_for_subcommands() {
+        assert!(Args::try_parse_from([
+            "leucippus",
+            "bench",
+            "--moves",
+            "e2e4"
+        ])
+        .is_err());
     }
 }
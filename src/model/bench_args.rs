@@ -0,0 +1,13 @@
+/// Options of the `bench` subcommand. All other options are ignored so that
+/// results stay comparable between runs.
+#[derive(Clone, Debug, clap::Args)]
+pub struct BenchArgs {
+    /// Number of search iterations per bench position.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..), default_value_t = 2000)]
+    pub iterations: u64,
+
+    /// Depth of the perft run per bench position which measures the move
+    /// generation speed.
+    #[arg(long, value_parser, default_value_t = 3)]
+    pub perft_depth: u32,
+}
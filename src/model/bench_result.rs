@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Totals of a `bench` run over all bench positions.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BenchResult {
+    pub move_generation_time: Duration,
+    pub moves_generated: u64,
+    pub nodes: u64,
+    pub playouts: u64,
+    pub random_move_time: Duration,
+    pub random_moves: u64,
+    pub search_time: Duration,
+    /// Hash over the node counts and root visit distributions of all
+    /// searches, it only changes if the search behaviour changes.
+    pub signature: u64,
+    /// Memory allocated by the search trees, see `Tree::get_allocated_bytes`.
+    pub tree_bytes: u64,
+}
+
+impl BenchResult {
+    pub fn get_playouts_per_second(&self) -> f64 {
+        self.playouts as f64 / self.search_time.as_secs_f64().max(1e-9)
+    }
+
+    pub fn get_tree_bytes_per_node(&self) -> f64 {
+        self.tree_bytes as f64 / self.nodes.max(1) as f64
+    }
+
+    pub fn get_random_moves_per_second(&self) -> f64 {
+        self.random_moves as f64 / self.random_move_time.as_secs_f64().max(1e-9)
+    }
+
+    pub fn get_moves_generated_per_second(&self) -> f64 {
+        self.moves_generated as f64
+            / self.move_generation_time.as_secs_f64().max(1e-9)
+    }
+}
@@ -2,23 +2,35 @@ use std::collections::hash_map::DefaultHasher;
 use std::hash::Hash;
 use std::hash::Hasher;
 
+use crate::bitboards::model::bitboard::Bitboard;
+use crate::variants::three_check::format_checks_given;
+use crate::variants::three_check::parse_checks_given;
+
 use super::board_castle::BoardCastle;
 use super::board_pieces::BoardPieces;
 use super::color::Color;
+use super::file::File;
 use super::piece::Piece;
+use super::piece_type::PieceType;
+use super::rank::Rank;
+use super::square::Square;
 use super::squares::Squares;
 use super::types::SquareIndex;
+use super::variant::Variant;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Board {
     pub castle: BoardCastle,
-    pub draw_by_repetition: bool,
+    /// Checks given by each color, indexed by `Color`, only counted in
+    /// variants which need them.
+    pub checks_given: [u8; 2],
     pub en_passant: Option<SquareIndex>,
     pub fullmove: usize,
     pub halfmove: usize,
     pub our_color: Color,
     pub pieces: BoardPieces,
     pub their_color: Color,
+    pub variant: Variant,
 }
 
 impl Board {
@@ -28,37 +40,37 @@ impl Board {
         )
     }
 
-    pub fn clone_without_piece_lists(&self) -> Self {
-        Self {
-            castle: self.castle.clone(),
-            draw_by_repetition: self.draw_by_repetition,
-            en_passant: self.en_passant,
-            fullmove: self.fullmove,
-            halfmove: self.halfmove,
-            our_color: self.our_color,
-            pieces: BoardPieces {
-                our_bishops: Vec::with_capacity(2),
-                our_kings: Vec::with_capacity(2),
-                our_knights: Vec::with_capacity(2),
-                our_pawns: Vec::with_capacity(8),
-                our_queens: Vec::with_capacity(1),
-                our_rooks: Vec::with_capacity(2),
-                squares: self.pieces.squares.clone(),
-            },
-            their_color: self.their_color,
-        }
+    /// Loads an atomic chess position, see `from_fen_with_variant`.
+    pub fn from_fen(fen: &str) -> Self {
+        Board::from_fen_with_variant(fen, Variant::Atomic)
     }
 
-    pub fn from_fen(fen: &str) -> Self {
-        let fen_split = fen.split(' ').collect();
+    /// Loads a position, panics if the FEN is invalid. See
+    /// `try_from_fen_with_variant`.
+    pub fn from_fen_with_variant(fen: &str, variant: Variant) -> Self {
+        Board::try_from_fen_with_variant(fen, variant)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
 
-        let castle = Board::get_fen_castle(&fen_split);
-        let our_color = Board::get_fen_color(&fen_split);
-        let draw_by_repetition = false;
-        let en_passant = Board::get_fen_en_passant(&fen_split);
-        let fullmove = Board::get_fen_fullmove(&fen_split);
-        let halfmove = Board::get_fen_halfmove(&fen_split);
-        let pieces = Board::get_fen_pieces(&fen_split);
+    /// Loads a position, e.g. entered by a user or read from a file, and
+    /// describes what is wrong with an invalid FEN.
+    pub fn try_from_fen_with_variant(
+        fen: &str,
+        variant: Variant,
+    ) -> Result<Self, String> {
+        let fen_split: Vec<&str> = fen.split(' ').collect();
+
+        let our_color = Board::get_fen_color(&fen_split)?;
+        let en_passant = Board::get_fen_en_passant(&fen_split)?;
+        let fullmove = Board::get_fen_fullmove(&fen_split)?;
+        let halfmove = Board::get_fen_halfmove(&fen_split)?;
+        let pieces = Board::get_fen_pieces(&fen_split)?;
+        let castle = if variant.can_castle() {
+            Board::get_fen_castle(&fen_split, &pieces)?
+        } else {
+            BoardCastle::none()
+        };
+        let checks_given = Board::get_fen_checks_given(&fen_split)?;
 
         let their_color = if our_color == Color::Black {
             Color::White
@@ -66,16 +78,17 @@ impl Board {
             Color::Black
         };
 
-        Self {
+        Ok(Self {
             castle,
-            draw_by_repetition,
+            checks_given,
             en_passant,
             fullmove,
             halfmove,
             our_color,
             pieces,
             their_color,
-        }
+            variant,
+        })
     }
 
     pub fn swap_color(&mut self) {
@@ -84,6 +97,16 @@ impl Board {
         self.their_color = c;
     }
 
+    /// The squares of our pieces of the type.
+    pub fn get_our_pieces(&self, piece_type: PieceType) -> Bitboard {
+        self.pieces.get(self.our_color, piece_type)
+    }
+
+    /// The squares of their pieces of the type.
+    pub fn get_their_pieces(&self, piece_type: PieceType) -> Bitboard {
+        self.pieces.get(self.their_color, piece_type)
+    }
+
     pub fn is_empty_at(&self, at: SquareIndex) -> bool {
         self.pieces.squares.data[at as usize].is_none()
     }
@@ -115,59 +138,87 @@ impl Board {
             .is_some_and(|p| p.get_color() == self.their_color)
     }
 
-    fn get_fen_castle(fen_split: &Vec<&str>) -> BoardCastle {
-        let fen_castle = fen_split[2];
-        BoardCastle {
-            black_long: fen_castle.contains("q"),
-            black_short: fen_castle.contains("k"),
-            white_long: fen_castle.contains("Q"),
-            white_short: fen_castle.contains("K"),
-        }
+    fn get_fen_field<'a>(
+        fen_split: &[&'a str],
+        index: usize,
+        name: &str,
+    ) -> Result<&'a str, String> {
+        fen_split
+            .get(index)
+            .copied()
+            .ok_or_else(|| format!("Invalid FEN: {name} is missing"))
+    }
+
+    fn get_fen_castle(
+        fen_split: &[&str],
+        pieces: &BoardPieces,
+    ) -> Result<BoardCastle, String> {
+        let field = Board::get_fen_field(fen_split, 2, "castling")?;
+        BoardCastle::try_from_fen(field, &pieces.squares.data)
     }
 
-    fn get_fen_color(fen_split: &Vec<&str>) -> Color {
-        match fen_split[1] {
-            "b" => Color::Black,
-            "w" => Color::White,
-            _ => panic!("Invalid FEN: active color must be 'b' or 'w'"),
+    fn get_fen_color(fen_split: &[&str]) -> Result<Color, String> {
+        match Board::get_fen_field(fen_split, 1, "active color")? {
+            "b" => Ok(Color::Black),
+            "w" => Ok(Color::White),
+            _ => Err(String::from(
+                "Invalid FEN: active color must be 'b' or 'w'",
+            )),
         }
     }
 
-    fn get_fen_en_passant(fen_split: &Vec<&str>) -> Option<SquareIndex> {
-        let fen_en_passant = fen_split[3];
+    fn get_fen_en_passant(
+        fen_split: &[&str],
+    ) -> Result<Option<SquareIndex>, String> {
+        let fen_en_passant = Board::get_fen_field(fen_split, 3, "en passant")?;
 
         if fen_en_passant == "-" {
-            return None;
+            return Ok(None);
         }
 
-        let fen_en_passant_bytes: Vec<u8> = fen_en_passant.bytes().collect();
-        let file = fen_en_passant_bytes[0] - b'a';
-        let rank = fen_en_passant_bytes[1] - b'1';
+        let square: Square = fen_en_passant
+            .parse()
+            .map_err(|e| format!("Invalid FEN: en passant {e}"))?;
 
-        Some(8 * rank + file)
+        Ok(Some(square.index()))
+    }
+
+    /// The optional check counters after the fullmove number, e.g. `+1+0`.
+    fn get_fen_checks_given(fen_split: &[&str]) -> Result<[u8; 2], String> {
+        match fen_split.get(6) {
+            Some(field) => parse_checks_given(field),
+            None => Ok([0; 2]),
+        }
     }
 
-    fn get_fen_fullmove(fen_split: &Vec<&str>) -> usize {
-        usize::from_str_radix(fen_split[5], 10).unwrap()
+    fn get_fen_fullmove(fen_split: &[&str]) -> Result<usize, String> {
+        Board::get_fen_field(fen_split, 5, "fullmove number")?
+            .parse()
+            .map_err(|_| {
+                String::from("Invalid FEN: fullmove number is no number")
+            })
     }
 
-    fn get_fen_halfmove(fen_split: &Vec<&str>) -> usize {
-        usize::from_str_radix(fen_split[4], 10).unwrap()
+    fn get_fen_halfmove(fen_split: &[&str]) -> Result<usize, String> {
+        Board::get_fen_field(fen_split, 4, "halfmove clock")?
+            .parse()
+            .map_err(|_| {
+                String::from("Invalid FEN: halfmove clock is no number")
+            })
     }
 
-    fn get_fen_pieces(fen_split: &Vec<&str>) -> BoardPieces {
+    fn get_fen_pieces(fen_split: &[&str]) -> Result<BoardPieces, String> {
         let fen_pieces_split = fen_split[0].split('/');
-        let mut active_bishops = Vec::new();
-        let mut active_kings = Vec::new();
-        let mut active_knights = Vec::new();
-        let mut active_pawns = Vec::new();
-        let mut active_queens = Vec::new();
-        let mut active_rooks = Vec::new();
         let mut squares = Squares::new([None; 64]);
 
         let mut y = 8;
 
         for row in fen_pieces_split {
+            if y == 0 {
+                return Err(String::from(
+                    "Invalid FEN: piece data has more than 8 ranks",
+                ));
+            }
             y -= 1;
             let mut x = 0;
             for col in row.bytes() {
@@ -187,9 +238,11 @@ impl Board {
                     b'Q' => squares.data[i] = Some(Piece::WhiteQueen),
                     b'R' => squares.data[i] = Some(Piece::WhiteRook),
                     b'1'..=b'8' => x += (col - b'1') as usize,
-                    _ => panic!(
-                        "Invalid FEN: numbers in piece data must be 1 to 8"
-                    ),
+                    _ => {
+                        return Err(String::from(
+                            "Invalid FEN: numbers in piece data must be 1 to 8",
+                        ))
+                    }
                 }
 
                 x += 1;
@@ -199,35 +252,55 @@ impl Board {
             }
         }
 
-        let active_color = Board::get_fen_color(fen_split);
-
-        for i in 0..64 {
-            if let Some(piece) = squares.data[i] {
-                if piece.is_bishop_of_color(active_color) {
-                    active_bishops.push(i as SquareIndex);
-                } else if piece.is_king_of_color(active_color) {
-                    active_kings.push(i as SquareIndex);
-                } else if piece.is_knight_of_color(active_color) {
-                    active_knights.push(i as SquareIndex);
-                } else if piece.is_pawn_of_color(active_color) {
-                    active_pawns.push(i as SquareIndex);
-                } else if piece.is_queen_of_color(active_color) {
-                    active_queens.push(i as SquareIndex);
-                } else if piece.is_rook_of_color(active_color) {
-                    active_rooks.push(i as SquareIndex);
+        Ok(BoardPieces::from_squares(squares))
+    }
+
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        for rank in Rank::iter().rev() {
+            let mut empty = 0;
+            for file in File::iter() {
+                let square = Square::from_file_rank(file, rank);
+                match self.pieces.squares.data[square.index() as usize] {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.get_fen_char());
+                    }
+                    None => empty += 1,
                 }
             }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank != Rank::FIRST {
+                fen.push('/');
+            }
         }
 
-        BoardPieces {
-            our_bishops: active_bishops,
-            our_kings: active_kings,
-            our_knights: active_knights,
-            our_pawns: active_pawns,
-            our_queens: active_queens,
-            our_rooks: active_rooks,
-            squares,
+        fen.push_str(match self.our_color {
+            Color::Black => " b ",
+            Color::White => " w ",
+        });
+
+        fen.push_str(&self.castle.to_fen(&self.pieces.squares.data));
+
+        match self.en_passant {
+            Some(square) => {
+                fen.push_str(&format!(" {}", Square::from_index(square)));
+            }
+            None => fen.push_str(" -"),
+        }
+
+        fen.push_str(&format!(" {} {}", self.halfmove, self.fullmove));
+        if self.variant.counts_checks() {
+            fen.push(' ');
+            fen.push_str(&format_checks_given(&self.checks_given));
         }
+        fen
     }
 
     /// Used for threefold repetition checks
@@ -237,6 +310,7 @@ impl Board {
         self.pieces.squares.hash(&mut hasher);
         self.en_passant.hash(&mut hasher);
         self.castle.hash(&mut hasher);
+        self.checks_given.hash(&mut hasher);
         hasher.finish()
     }
 }
@@ -321,6 +395,20 @@ mod test {
 
             assert!(left.get_hash() != right.get_hash());
         }
+
+        #[test]
+        fn it_proves_that_hashes_are_different_if_check_counters_differ() {
+            let left = Board::from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K2R w K - 0 1 +1+0",
+                Variant::ThreeCheck,
+            );
+            let right = Board::from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K2R w K - 0 1 +0+0",
+                Variant::ThreeCheck,
+            );
+
+            assert!(left.get_hash() != right.get_hash());
+        }
     }
 
     mod fen {
@@ -340,10 +428,10 @@ mod test {
                 ..
             } = Board::from_fen(fen);
 
-            assert_eq!(castle.black_long, false);
-            assert_eq!(castle.black_short, true);
-            assert_eq!(castle.white_long, false);
-            assert_eq!(castle.white_short, false);
+            assert_eq!(castle.black_long, None);
+            assert_eq!(castle.black_short, Some(File::H));
+            assert_eq!(castle.white_long, None);
+            assert_eq!(castle.white_short, None);
 
             assert_eq!(color, Color::Black);
             assert_eq!(en_passant, None);
@@ -365,12 +453,30 @@ mod test {
                 ]),
             );
 
-            assert_eq!(pieces.our_bishops, vec![58, 61]);
-            assert_eq!(pieces.our_kings, vec![60]);
-            assert_eq!(pieces.our_knights, vec![30, 57]);
-            assert_eq!(pieces.our_pawns, vec![41, 44, 45, 46, 51, 55]);
-            assert_eq!(pieces.our_queens, vec![59]);
-            assert_eq!(pieces.our_rooks, vec![32, 63]);
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Bishop)),
+                vec![58, 61]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::King)),
+                vec![60]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Knight)),
+                vec![30, 57]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Pawn)),
+                vec![41, 44, 45, 46, 51, 55]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Queen)),
+                vec![59]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Rook)),
+                vec![32, 63]
+            );
         }
 
         #[test]
@@ -387,10 +493,10 @@ mod test {
                 ..
             } = Board::from_fen(fen);
 
-            assert_eq!(castle.black_long, true);
-            assert_eq!(castle.black_short, true);
-            assert_eq!(castle.white_long, true);
-            assert_eq!(castle.white_short, true);
+            assert_eq!(castle.black_long, Some(File::A));
+            assert_eq!(castle.black_short, Some(File::H));
+            assert_eq!(castle.white_long, Some(File::A));
+            assert_eq!(castle.white_short, Some(File::H));
 
             assert_eq!(color, Color::White);
             assert_eq!(en_passant, Some(43));
@@ -412,12 +518,64 @@ mod test {
                 ]),
             );
 
-            assert_eq!(pieces.our_bishops, vec![2, 5]);
-            assert_eq!(pieces.our_kings, vec![4]);
-            assert_eq!(pieces.our_knights, vec![1, 6]);
-            assert_eq!(pieces.our_pawns, vec![8, 9, 10, 11, 13, 14, 15, 36]);
-            assert_eq!(pieces.our_queens, vec![3]);
-            assert_eq!(pieces.our_rooks, vec![0, 7]);
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Bishop)),
+                vec![2, 5]
+            );
+            assert_eq!(squares_of(pieces.get(color, PieceType::King)), vec![4]);
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Knight)),
+                vec![1, 6]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Pawn)),
+                vec![8, 9, 10, 11, 13, 14, 15, 36]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Queen)),
+                vec![3]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Rook)),
+                vec![0, 7]
+            );
+        }
+
+        #[test]
+        #[should_panic(
+            expected = "Invalid FEN: en passant 'j6' is not a square"
+        )]
+        fn it_panics_on_en_passant_squares_off_the_board() {
+            Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - j6 0 1");
+        }
+
+        #[test]
+        fn it_describes_what_is_wrong_with_an_invalid_fen() {
+            let error = |fen| {
+                Board::try_from_fen_with_variant(fen, Variant::Standard)
+                    .err()
+                    .unwrap()
+            };
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/4K3 w -"),
+                "Invalid FEN: en passant is missing"
+            );
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"),
+                "Invalid FEN: active color must be 'b' or 'w'"
+            );
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/4K3 w - - 0 x"),
+                "Invalid FEN: fullmove number is no number"
+            );
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/8/4K3 w - - 0 1"),
+                "Invalid FEN: piece data has more than 8 ranks"
+            );
+            assert_eq!(
+                error("4k3/8/8/8/8/8/8/4K3 w X - 0 1"),
+                "Invalid FEN: unknown castling right 'X'"
+            );
         }
 
         #[test]
@@ -434,10 +592,10 @@ mod test {
                 ..
             } = Board::from_fen(fen);
 
-            assert_eq!(castle.black_long, true);
-            assert_eq!(castle.black_short, true);
-            assert_eq!(castle.white_long, true);
-            assert_eq!(castle.white_short, true);
+            assert_eq!(castle.black_long, Some(File::A));
+            assert_eq!(castle.black_short, Some(File::H));
+            assert_eq!(castle.white_long, Some(File::A));
+            assert_eq!(castle.white_short, Some(File::H));
 
             assert_eq!(color, Color::White);
             assert_eq!(en_passant, None);
@@ -459,12 +617,67 @@ mod test {
                 ]),
             );
 
-            assert_eq!(pieces.our_bishops, vec![2, 5]);
-            assert_eq!(pieces.our_kings, vec![4]);
-            assert_eq!(pieces.our_knights, vec![1, 6]);
-            assert_eq!(pieces.our_pawns, vec![8, 9, 10, 11, 12, 13, 14, 15]);
-            assert_eq!(pieces.our_queens, vec![3]);
-            assert_eq!(pieces.our_rooks, vec![0, 7]);
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Bishop)),
+                vec![2, 5]
+            );
+            assert_eq!(squares_of(pieces.get(color, PieceType::King)), vec![4]);
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Knight)),
+                vec![1, 6]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Pawn)),
+                vec![8, 9, 10, 11, 12, 13, 14, 15]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Queen)),
+                vec![3]
+            );
+            assert_eq!(
+                squares_of(pieces.get(color, PieceType::Rook)),
+                vec![0, 7]
+            );
+        }
+
+        #[test]
+        fn it_converts_boards_back_to_fen() {
+            let fens = [
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "rnbqkbnr/1pp1pppp/p7/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 3",
+                "1nbqkb1r/3p3p/1p2ppp1/r7/3N2n1/NP2P2P/P1PP1PP1/R1B2RK1 b k - 0 11",
+                "7k/7p/5N1P/8/8/8/2q5/K7 w - - 12 40",
+            ];
+
+            for fen in fens {
+                assert_eq!(Board::from_fen(fen).to_fen(), fen);
+            }
+        }
+
+        #[test]
+        fn it_converts_three_check_boards_back_to_fen_with_check_counters() {
+            let fen = "4k3/8/8/8/8/8/8/4K2R w K - 0 1 +2+1";
+            let board = Board::from_fen_with_variant(fen, Variant::ThreeCheck);
+            assert_eq!(board.to_fen(), fen);
+
+            let board = Board::from_fen_with_variant(
+                "4k3/8/8/8/8/8/8/4K2R w K - 0 1",
+                Variant::ThreeCheck,
+            );
+            assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K2R w K - 0 1 +0+0");
+        }
+
+        #[test]
+        fn it_drops_the_castling_rights_of_antichess_boards() {
+            let board = Board::from_fen_with_variant(
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                Variant::Antichess,
+            );
+            assert_eq!(board.castle, BoardCastle::none());
+        }
+
+        fn squares_of(bb: Bitboard) -> Vec<SquareIndex> {
+            bb.into_iter().collect()
         }
 
         fn bb() -> Option<Piece> {
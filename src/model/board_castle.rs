@@ -1,7 +1,346 @@
-#[derive(Clone, Debug, Hash, PartialEq)]
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::str::FromStr;
+
+use super::color::Color;
+use super::file::File;
+use super::piece::Piece;
+use super::r#move::MoveSpecial;
+use super::rank::Rank;
+use super::square::Square;
+
+/// Castling rights given by the file of the castling rook, so that Chess960
+/// positions with kings and rooks on any file of the back rank work too.
+/// `chess960` selects the UCI notation of castling moves (king takes rook) and
+/// `shredder` keeps the notation of the FEN the rights were parsed from. They
+/// are not part of the position, so comparisons and hashes ignore them.
+#[derive(Clone, Debug)]
 pub struct BoardCastle {
-    pub black_long: bool,
-    pub black_short: bool,
-    pub white_long: bool,
-    pub white_short: bool,
+    pub black_long: Option<File>,
+    pub black_short: Option<File>,
+    pub chess960: bool,
+    pub shredder: bool,
+    pub white_long: Option<File>,
+    pub white_short: Option<File>,
+}
+
+impl BoardCastle {
+    pub fn none() -> Self {
+        BoardCastle {
+            black_long: None,
+            black_short: None,
+            chess960: false,
+            shredder: false,
+            white_long: None,
+            white_short: None,
+        }
+    }
+
+    /// Parses the castling field of a FEN. `KQkq` refer to the outermost rook
+    /// on the side of the king (X-FEN), the rook files `A`-`H` and `a`-`h`
+    /// (Shredder-FEN) may be used as well. Panics on invalid castling rights,
+    /// see `try_from_fen`.
+    pub fn from_fen(field: &str, squares: &[Option<Piece>; 64]) -> Self {
+        BoardCastle::try_from_fen(field, squares)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    pub fn try_from_fen(
+        field: &str,
+        squares: &[Option<Piece>; 64],
+    ) -> Result<Self, String> {
+        let mut castle = BoardCastle::none();
+        if field == "-" {
+            return Ok(castle);
+        }
+        castle.shredder = !field.contains(['K', 'Q', 'k', 'q']);
+
+        for c in field.chars() {
+            let color = if c.is_ascii_uppercase() {
+                Color::White
+            } else {
+                Color::Black
+            };
+            let Some(king_file) = find_king_file(squares, color) else {
+                // without a king on the back rank there is nothing to castle
+                continue;
+            };
+
+            let rook_file = match c.to_ascii_lowercase() {
+                'k' => {
+                    find_outermost_rook_file(squares, color, king_file, true)
+                }
+                'q' => {
+                    find_outermost_rook_file(squares, color, king_file, false)
+                }
+                'a'..='h' => {
+                    castle.chess960 = true;
+                    File::from_str(&c.to_ascii_lowercase().to_string()).ok()
+                }
+                _ => {
+                    return Err(format!(
+                        "Invalid FEN: unknown castling right '{c}'"
+                    ))
+                }
+            };
+            let Some(rook_file) = rook_file else {
+                // without a rook there is nothing to castle with
+                continue;
+            };
+
+            if rook_file == king_file {
+                return Err(format!(
+                    "Invalid FEN: castling rook on the king file '{c}'"
+                ));
+            }
+            if king_file != File::E
+                || (rook_file != File::A && rook_file != File::H)
+            {
+                castle.chess960 = true;
+            }
+
+            let right = match (color, rook_file < king_file) {
+                (Color::Black, true) => &mut castle.black_long,
+                (Color::Black, false) => &mut castle.black_short,
+                (Color::White, true) => &mut castle.white_long,
+                (Color::White, false) => &mut castle.white_short,
+            };
+            *right = Some(rook_file);
+        }
+
+        Ok(castle)
+    }
+
+    /// Writes the castling field of a FEN in the notation it was parsed from.
+    /// Shredder-FEN gives the file of every castling rook, X-FEN is the usual
+    /// `KQkq` for standard chess and only gives the rook file when the
+    /// castling rook is not the outermost one.
+    pub fn to_fen(&self, squares: &[Option<Piece>; 64]) -> String {
+        let rights = [
+            (Color::White, self.white_short, true),
+            (Color::White, self.white_long, false),
+            (Color::Black, self.black_short, true),
+            (Color::Black, self.black_long, false),
+        ];
+
+        let mut fen = String::new();
+        for (color, rook_file, is_short) in rights {
+            let Some(rook_file) = rook_file else {
+                continue;
+            };
+
+            let outermost_rook_file =
+                find_king_file(squares, color).and_then(|k| {
+                    find_outermost_rook_file(squares, color, k, is_short)
+                });
+            let is_outermost =
+                !self.shredder && outermost_rook_file == Some(rook_file);
+            let c = match (is_outermost, is_short) {
+                (true, true) => 'K',
+                (true, false) => 'Q',
+                (false, _) => char::from(b'A' + rook_file.index()),
+            };
+
+            fen.push(match color {
+                Color::Black => c.to_ascii_lowercase(),
+                Color::White => c,
+            });
+        }
+
+        if fen.is_empty() {
+            fen.push('-');
+        }
+        fen
+    }
+
+    pub fn get_rook_file(&self, special: MoveSpecial) -> Option<File> {
+        match special {
+            MoveSpecial::CastleLongBlack => self.black_long,
+            MoveSpecial::CastleShortBlack => self.black_short,
+            MoveSpecial::CastleLongWhite => self.white_long,
+            MoveSpecial::CastleShortWhite => self.white_short,
+            _ => None,
+        }
+    }
+
+    pub fn remove_right(&mut self, special: MoveSpecial) {
+        match special {
+            MoveSpecial::CastleLongBlack => self.black_long = None,
+            MoveSpecial::CastleShortBlack => self.black_short = None,
+            MoveSpecial::CastleLongWhite => self.white_long = None,
+            MoveSpecial::CastleShortWhite => self.white_short = None,
+            _ => {}
+        }
+    }
+
+    pub fn remove_rights(&mut self, color: Color) {
+        match color {
+            Color::Black => {
+                self.black_long = None;
+                self.black_short = None;
+            }
+            Color::White => {
+                self.white_long = None;
+                self.white_short = None;
+            }
+        }
+    }
+
+    fn get_rights(&self) -> [Option<File>; 4] {
+        [
+            self.black_long,
+            self.black_short,
+            self.white_long,
+            self.white_short,
+        ]
+    }
+}
+
+impl PartialEq for BoardCastle {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_rights() == other.get_rights()
+    }
+}
+
+impl Hash for BoardCastle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.get_rights().hash(state);
+    }
+}
+
+fn get_back_rank_piece(
+    squares: &[Option<Piece>; 64],
+    color: Color,
+    file: File,
+) -> Option<Piece> {
+    squares
+        [Square::from_file_rank(file, Rank::back_rank(color)).index() as usize]
+}
+
+fn find_king_file(squares: &[Option<Piece>; 64], color: Color) -> Option<File> {
+    File::iter().find(|f| {
+        get_back_rank_piece(squares, color, *f)
+            .is_some_and(|p| p.is_king_of_color(color))
+    })
+}
+
+fn find_outermost_rook_file(
+    squares: &[Option<Piece>; 64],
+    color: Color,
+    king_file: File,
+    is_short: bool,
+) -> Option<File> {
+    let is_rook = |f: &File| {
+        get_back_rank_piece(squares, color, *f)
+            .is_some_and(|p| p.is_rook_of_color(color))
+    };
+
+    if is_short {
+        File::iter()
+            .rev()
+            .take_while(|f| *f > king_file)
+            .find(is_rook)
+    } else {
+        File::iter().take_while(|f| *f < king_file).find(is_rook)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::board::Board;
+
+    use super::*;
+
+    fn parse(fen: &str) -> (BoardCastle, [Option<Piece>; 64]) {
+        let board = Board::from_fen(fen);
+        (board.castle, board.pieces.squares.data)
+    }
+
+    #[test]
+    fn it_parses_x_fen_castling_rights_of_the_starting_position() {
+        let (castle, squares) =
+            parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+        assert_eq!(castle.white_short, Some(File::H));
+        assert_eq!(castle.white_long, Some(File::A));
+        assert_eq!(castle.black_short, Some(File::H));
+        assert_eq!(castle.black_long, Some(File::A));
+        assert!(!castle.chess960);
+        assert_eq!(castle.to_fen(&squares), "KQkq");
+    }
+
+    #[test]
+    fn it_parses_shredder_fen_castling_rights() {
+        let (castle, squares) =
+            parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+
+        assert_eq!(castle.white_short, Some(File::H));
+        assert_eq!(castle.white_long, Some(File::A));
+        assert_eq!(castle.black_short, Some(File::H));
+        assert_eq!(castle.black_long, Some(File::A));
+        assert!(castle.chess960);
+        assert_eq!(castle.to_fen(&squares), "HAha");
+    }
+
+    #[test]
+    fn it_writes_back_the_notation_of_the_castling_rights() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1",
+            "bqnbnrkr/pppppppp/8/8/8/8/PPPPPPPP/BQNBNRKR w KQkq - 0 1",
+            "bqnbnrkr/pppppppp/8/8/8/8/PPPPPPPP/BQNBNRKR w HFhf - 0 1",
+            "4k3/8/8/8/8/8/8/RR2K2R w KB - 0 1",
+            "4k3/8/8/8/8/8/8/RR2K2R w HB - 0 1",
+        ] {
+            assert_eq!(Board::from_fen(fen).to_fen(), fen);
+        }
+    }
+
+    #[test]
+    fn it_writes_the_file_of_a_castling_rook_which_is_not_the_outermost() {
+        let (castle, squares) = parse("4k3/8/8/8/8/8/8/RR2K2R w KB - 0 1");
+
+        assert_eq!(castle.white_short, Some(File::H));
+        assert_eq!(castle.white_long, Some(File::B));
+        assert_eq!(castle.to_fen(&squares), "KB");
+    }
+
+    #[test]
+    fn it_finds_the_rooks_of_a_chess960_starting_position() {
+        let (castle, squares) =
+            parse("bqnbnrkr/pppppppp/8/8/8/8/PPPPPPPP/BQNBNRKR w KQkq - 0 1");
+
+        assert_eq!(castle.white_short, Some(File::H));
+        assert_eq!(castle.white_long, Some(File::F));
+        assert_eq!(castle.black_short, Some(File::H));
+        assert_eq!(castle.black_long, Some(File::F));
+        assert!(castle.chess960);
+        assert_eq!(castle.to_fen(&squares), "KQkq");
+    }
+
+    #[test]
+    fn it_ignores_castling_rights_without_a_rook() {
+        let (castle, squares) = parse("4k3/8/8/8/8/8/8/4K2R w KQ - 0 1");
+
+        assert_eq!(castle.white_short, Some(File::H));
+        assert_eq!(castle.white_long, None);
+        assert_eq!(castle.to_fen(&squares), "K");
+    }
+
+    #[test]
+    fn it_ignores_the_notation_when_comparing_castling_rights() {
+        let (x_fen, _) =
+            parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+        let (shredder_fen, _) =
+            parse("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1");
+
+        assert_ne!(x_fen.chess960, shredder_fen.chess960);
+        assert_eq!(x_fen, shredder_fen);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid FEN")]
+    fn it_panics_on_unknown_castling_rights() {
+        Board::from_fen("4k3/8/8/8/8/8/8/4K2R w X - 0 1");
+    }
 }
@@ -1,114 +1,111 @@
-use std::collections::HashSet;
-use std::hash::Hash;
+use crate::bitboards::model::bitboard::Bitboard;
 
+use super::color::Color;
+use super::piece::Piece;
+use super::piece_type::PieceType;
 use super::squares::Squares;
 use super::types::SquareIndex;
 
-#[derive(Clone, Debug)]
+/// The pieces of both colors, as a mailbox and as the set of squares of each
+/// piece. All changes go through `put` and `remove`, which keep both in sync.
+#[derive(Clone, Debug, PartialEq)]
 pub struct BoardPieces {
-    pub our_bishops: Vec<SquareIndex>,
-    pub our_kings: Vec<SquareIndex>,
-    pub our_knights: Vec<SquareIndex>,
-    pub our_pawns: Vec<SquareIndex>,
-    pub our_queens: Vec<SquareIndex>,
-    pub our_rooks: Vec<SquareIndex>,
+    /// The squares of each piece, indexed by `Piece`.
+    pub sets: [Bitboard; 12],
     pub squares: Squares,
 }
 
-impl PartialEq for BoardPieces {
-    fn eq(&self, other: &Self) -> bool {
-        make_set(&self.our_bishops) == make_set(&other.our_bishops)
-            && make_set(&self.our_kings) == make_set(&other.our_kings)
-            && make_set(&self.our_knights) == make_set(&other.our_knights)
-            && make_set(&self.our_pawns) == make_set(&other.our_pawns)
-            && make_set(&self.our_queens) == make_set(&other.our_queens)
-            && make_set(&self.our_rooks) == make_set(&other.our_rooks)
-            && self.squares == other.squares
+impl BoardPieces {
+    pub fn from_squares(squares: Squares) -> Self {
+        let mut sets = [Bitboard(0); 12];
+        for (square, piece) in squares.data.iter().enumerate() {
+            if let Some(piece) = piece {
+                sets[*piece as usize].set(square as SquareIndex);
+            }
+        }
+
+        Self { sets, squares }
     }
-}
 
-fn make_set<T: Eq + Hash + Clone>(v: &Vec<T>) -> HashSet<T> {
-    let set: HashSet<_> = v.iter().cloned().collect();
-    set
+    /// The squares of the pieces of the color and type.
+    pub fn get(&self, color: Color, piece_type: PieceType) -> Bitboard {
+        self.sets[Piece::new(color, piece_type) as usize]
+    }
+
+    /// Places the piece on the square, replacing whatever stood there.
+    pub fn put(&mut self, square: SquareIndex, piece: Piece) {
+        self.remove(square);
+        self.squares.data[square as usize] = Some(piece);
+        self.sets[piece as usize].set(square);
+    }
+
+    pub fn remove(&mut self, square: SquareIndex) -> Option<Piece> {
+        let piece = self.squares.data[square as usize].take()?;
+        self.sets[piece as usize].clear(square);
+        Some(piece)
+    }
+
+    /// Moves the piece on `from` to `to`, replacing whatever stood there.
+    pub fn move_piece(&mut self, from: SquareIndex, to: SquareIndex) {
+        if let Some(piece) = self.remove(from) {
+            self.put(to, piece);
+        }
+    }
+
+    /// Whether the piece sets match the mailbox, used in debug builds after
+    /// every move.
+    pub fn is_consistent(&self) -> bool {
+        self.sets == BoardPieces::from_squares(self.squares.clone()).sets
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::types::square_names::*;
+
     use super::*;
 
+    fn squares(bb: Bitboard) -> Vec<SquareIndex> {
+        bb.into_iter().collect()
+    }
+
     #[test]
-    fn it_determines_the_inequality_of_board_pieces_when_vec_sizes_dont_match()
-    {
-        let left = BoardPieces {
-            our_bishops: vec![1, 2, 3],
-            our_kings: Vec::new(),
-            our_knights: Vec::new(),
-            our_pawns: Vec::new(),
-            our_queens: Vec::new(),
-            our_rooks: Vec::new(),
-            squares: Squares::new([None; 64]),
-        };
-
-        let right = BoardPieces {
-            our_bishops: vec![1, 2, 3, 4],
-            our_kings: Vec::new(),
-            our_knights: Vec::new(),
-            our_pawns: Vec::new(),
-            our_queens: Vec::new(),
-            our_rooks: Vec::new(),
-            squares: Squares::new([None; 64]),
-        };
-
-        assert!(left != right);
+    fn it_collects_the_squares_of_each_piece() {
+        let mut data = [None; 64];
+        data[A1 as usize] = Some(Piece::WhiteRook);
+        data[H1 as usize] = Some(Piece::WhiteRook);
+        data[E8 as usize] = Some(Piece::BlackKing);
+        let pieces = BoardPieces::from_squares(Squares::new(data));
+
+        assert_eq!(
+            squares(pieces.get(Color::White, PieceType::Rook)),
+            [A1, H1]
+        );
+        assert_eq!(squares(pieces.get(Color::Black, PieceType::King)), [E8]);
+        assert!(pieces.get(Color::Black, PieceType::Rook).is_empty());
     }
 
     #[test]
-    fn it_determines_the_inequality_of_board_pieces_when_elements_dont_match() {
-        let left = BoardPieces {
-            our_bishops: vec![1, 2, 3],
-            our_kings: Vec::new(),
-            our_knights: Vec::new(),
-            our_pawns: Vec::new(),
-            our_queens: Vec::new(),
-            our_rooks: Vec::new(),
-            squares: Squares::new([None; 64]),
-        };
-
-        let right = BoardPieces {
-            our_bishops: vec![1, 2, 4],
-            our_kings: Vec::new(),
-            our_knights: Vec::new(),
-            our_pawns: Vec::new(),
-            our_queens: Vec::new(),
-            our_rooks: Vec::new(),
-            squares: Squares::new([None; 64]),
-        };
-
-        assert!(left != right);
+    fn it_keeps_the_sets_in_sync_with_the_squares() {
+        let mut pieces = BoardPieces::from_squares(Squares::new([None; 64]));
+
+        pieces.put(E2, Piece::WhitePawn);
+        pieces.put(D3, Piece::BlackKnight);
+        pieces.move_piece(E2, D3);
+        assert!(pieces.is_consistent());
+        assert_eq!(squares(pieces.get(Color::White, PieceType::Pawn)), [D3]);
+        assert!(pieces.get(Color::Black, PieceType::Knight).is_empty());
+
+        assert_eq!(pieces.remove(D3), Some(Piece::WhitePawn));
+        assert_eq!(pieces.remove(D3), None);
+        assert!(pieces.is_consistent());
+        assert!(pieces.sets.iter().all(|s| s.is_empty()));
     }
 
     #[test]
-    fn it_determines_the_equality_of_board_pieces_with_mixed_order() {
-        let left = BoardPieces {
-            our_bishops: vec![1, 2, 3, 4],
-            our_kings: vec![1, 2],
-            our_knights: vec![10, 100, 99],
-            our_pawns: vec![7, 8, 9],
-            our_queens: vec![2, 3, 4, 5, 6, 7],
-            our_rooks: vec![1],
-            squares: Squares::new([None; 64]),
-        };
-
-        let right = BoardPieces {
-            our_bishops: vec![2, 3, 4, 1],
-            our_kings: vec![2, 1],
-            our_knights: vec![100, 99, 10],
-            our_pawns: vec![9, 8, 7],
-            our_queens: vec![3, 5, 7, 2, 4, 6],
-            our_rooks: vec![1],
-            squares: Squares::new([None; 64]),
-        };
-
-        assert_eq!(left, right);
+    fn it_detects_sets_out_of_sync_with_the_squares() {
+        let mut pieces = BoardPieces::from_squares(Squares::new([None; 64]));
+        pieces.squares.data[A1 as usize] = Some(Piece::WhiteRook);
+        assert!(!pieces.is_consistent());
     }
 }
@@ -0,0 +1,19 @@
+use clap::Subcommand;
+
+use super::bench_args::BenchArgs;
+use super::match_args::MatchArgs;
+use super::testsuite_args::TestsuiteArgs;
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum Command {
+    /// Searches a fixed set of positions and reports speed and a signature
+    /// of the search behaviour.
+    Bench(BenchArgs),
+    /// Plays games between two engine configurations.
+    Match(Box<MatchArgs>),
+    /// Interactive shell to explore positions, see `help` inside the shell.
+    Repl,
+    /// Runs the search on the positions of an EPD file and reports which
+    /// were solved.
+    Testsuite(TestsuiteArgs),
+}
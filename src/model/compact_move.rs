@@ -0,0 +1,103 @@
+use std::fmt::Display;
+
+use super::r#move::Move;
+use super::r#move::MoveSpecial;
+use super::types::SquareIndex;
+
+const SQUARE_MASK: u16 = 0b11_1111;
+const FROM_SHIFT: u16 = 6;
+const SPECIAL_SHIFT: u16 = 12;
+
+/// A `Move` packed into 16 bits for storage in the search tree:
+/// bits 0-5 hold the target square, bits 6-11 the origin square and
+/// bits 12-15 the special, `0` for none and the discriminant plus one
+/// otherwise. Converting to and from `Move` is lossless.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct CompactMove(u16);
+
+impl CompactMove {
+    /// From a1 to a1, which is never a legal move, used to end move lists.
+    pub const NULL: CompactMove = CompactMove(0);
+
+    pub const fn get_from(self) -> SquareIndex {
+        ((self.0 >> FROM_SHIFT) & SQUARE_MASK) as SquareIndex
+    }
+
+    pub const fn get_to(self) -> SquareIndex {
+        (self.0 & SQUARE_MASK) as SquareIndex
+    }
+
+    pub const fn get_special(self) -> Option<MoveSpecial> {
+        match self.0 >> SPECIAL_SHIFT {
+            0 => None,
+            s => Some(MoveSpecial::ALL[s as usize - 1]),
+        }
+    }
+}
+
+impl From<Move> for CompactMove {
+    fn from(m: Move) -> Self {
+        debug_assert!(m.from < 64 && m.to < 64, "Invalid move {m:?}");
+        let special = m.special.map_or(0, |s| s as u16 + 1);
+        CompactMove(
+            (special << SPECIAL_SHIFT)
+                | ((m.from as u16) << FROM_SHIFT)
+                | m.to as u16,
+        )
+    }
+}
+
+impl From<CompactMove> for Move {
+    fn from(m: CompactMove) -> Self {
+        Move {
+            from: m.get_from(),
+            to: m.get_to(),
+            special: m.get_special(),
+        }
+    }
+}
+
+impl Display for CompactMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", Move::from(*self))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::size_of;
+
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_packs_moves_into_two_bytes() {
+        assert_eq!(size_of::<CompactMove>(), 2);
+        assert_eq!(size_of::<Option<CompactMove>>(), 4);
+        assert_eq!(size_of::<Move>(), 3);
+    }
+
+    #[test]
+    fn it_converts_every_move_losslessly() {
+        let specials =
+            std::iter::once(None).chain(MoveSpecial::ALL.into_iter().map(Some));
+        for special in specials {
+            for from in A1..=H8 {
+                for to in A1..=H8 {
+                    let m = Move { from, to, special };
+                    let compact = CompactMove::from(m);
+                    assert_eq!(Move::from(compact), m);
+                    assert_eq!(compact.to_string(), m.to_string());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_lists_the_specials_by_discriminant() {
+        for (i, special) in MoveSpecial::ALL.into_iter().enumerate() {
+            assert_eq!(special as usize, i);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq)]
+pub enum EngineMode {
+    /// Monte Carlo tree search.
+    Mcts,
+    /// Random legal moves, useful as a baseline.
+    Random,
+}
@@ -0,0 +1,145 @@
+/// A test position in the Extended Position Description format, e.g.
+/// `7k/7p/5N1P/8/8/8/2q5/K7 w - - bm Nxh7; id "mate.1";`.
+/// Supported opcodes are `bm` (best moves), `am` (moves to avoid), `dm`
+/// (direct mate in n, negative if the side to move gets mated), `id`, `hmvc`
+/// and `fmvn`, others are ignored.
+/// See: https://www.chessprogramming.org/Extended_Position_Description
+#[derive(Clone, Debug, PartialEq)]
+pub struct EpdPosition {
+    pub avoid_moves: Vec<String>,
+    pub best_moves: Vec<String>,
+    pub direct_mate: Option<i64>,
+    pub fen: String,
+    pub id: Option<String>,
+}
+
+impl EpdPosition {
+    pub fn from_epd(epd: &str) -> Self {
+        let fields: Vec<&str> = epd.split_whitespace().take(4).collect();
+        if fields.len() != 4 {
+            panic!("Invalid EPD: expected 4 position fields in '{epd}'");
+        }
+
+        let mut position = EpdPosition {
+            avoid_moves: Vec::new(),
+            best_moves: Vec::new(),
+            direct_mate: None,
+            fen: String::new(),
+            id: None,
+        };
+        let mut halfmove = String::from("0");
+        let mut fullmove = String::from("1");
+
+        for operation in split_operations(skip_fields(epd, 4)) {
+            let (opcode, operands) = operation
+                .split_once(char::is_whitespace)
+                .unwrap_or((operation, ""));
+            let operands = operands.trim();
+
+            match opcode {
+                "am" => position.avoid_moves = split_moves(operands),
+                "bm" => position.best_moves = split_moves(operands),
+                "dm" => {
+                    position.direct_mate =
+                        Some(operands.parse().unwrap_or_else(|_| {
+                            panic!("Invalid EPD: dm '{operands}' in '{epd}'")
+                        }))
+                }
+                "fmvn" => fullmove = String::from(operands),
+                "hmvc" => halfmove = String::from(operands),
+                "id" => {
+                    position.id = Some(String::from(operands.trim_matches('"')))
+                }
+                _ => {}
+            }
+        }
+
+        position.fen = format!("{} {halfmove} {fullmove}", fields.join(" "));
+        position
+    }
+
+    /// The id of the position or its FEN if it has none.
+    pub fn get_name(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.fen)
+    }
+}
+
+fn skip_fields(epd: &str, count: usize) -> &str {
+    let mut rest = epd.trim_start();
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        rest = rest[end..].trim_start();
+    }
+    rest
+}
+
+/// Splits at semicolons which are not part of a quoted string.
+fn split_operations(operations: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut start = 0;
+    let mut is_quoted = false;
+
+    for (i, c) in operations.char_indices() {
+        match c {
+            '"' => is_quoted = !is_quoted,
+            ';' if !is_quoted => {
+                result.push(operations[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(operations[start..].trim());
+
+    result.into_iter().filter(|o| !o.is_empty()).collect()
+}
+
+fn split_moves(operands: &str) -> Vec<String> {
+    operands.split_whitespace().map(String::from).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_the_position_and_the_operations() {
+        let position = EpdPosition::from_epd(
+            "rnbqkbnr/1ppppppp/pB6/8/8/2P2P2/PP1PP1PP/RNB1K1NR b KQkq - bm cxb6; am c5 c6; id \"a; b\";",
+        );
+
+        assert_eq!(
+            position,
+            EpdPosition {
+                avoid_moves: vec![String::from("c5"), String::from("c6")],
+                best_moves: vec![String::from("cxb6")],
+                direct_mate: None,
+                fen: String::from(
+                    "rnbqkbnr/1ppppppp/pB6/8/8/2P2P2/PP1PP1PP/RNB1K1NR b KQkq - 0 1"
+                ),
+                id: Some(String::from("a; b")),
+            }
+        );
+        assert_eq!(position.get_name(), "a; b");
+    }
+
+    #[test]
+    fn it_parses_move_counters_and_mates() {
+        let position = EpdPosition::from_epd(
+            "5r1r/p2R1P2/1k5p/1pp1B1p1/4P3/3p2P1/P2P3P/R5K1 b - - dm -2; hmvc 3; fmvn 26",
+        );
+
+        assert_eq!(position.direct_mate, Some(-2));
+        assert_eq!(
+            position.fen,
+            "5r1r/p2R1P2/1k5p/1pp1B1p1/4P3/3p2P1/P2P3P/R5K1 b - - 3 26"
+        );
+        assert_eq!(position.get_name(), position.fen);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid EPD: expected 4 position fields")]
+    fn it_rejects_incomplete_positions() {
+        EpdPosition::from_epd("8/8/8/8/8/8/8/8 w");
+    }
+}
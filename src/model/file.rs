@@ -0,0 +1,85 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A file of the board, `a` to `h`, which can only be created in range.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct File(u8);
+
+impl File {
+    pub const A: File = File(0);
+    pub const B: File = File(1);
+    pub const C: File = File(2);
+    pub const D: File = File(3);
+    pub const E: File = File(4);
+    pub const F: File = File(5);
+    pub const G: File = File(6);
+    pub const H: File = File(7);
+
+    pub const fn new(index: u8) -> Option<File> {
+        if index < 8 {
+            Some(File(index))
+        } else {
+            None
+        }
+    }
+
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    /// The file `files` to the east (or to the west if negative), if it is
+    /// still on the board.
+    pub const fn offset(self, files: i8) -> Option<File> {
+        let index = self.0 as i8 + files;
+        if 0 <= index && index < 8 {
+            Some(File(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// All files from `a` to `h`.
+    pub fn iter() -> impl DoubleEndedIterator<Item = File> {
+        (0..8).map(File)
+    }
+}
+
+impl FromStr for File {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [c @ b'a'..=b'h'] => Ok(File(c - b'a')),
+            _ => Err(format!("'{s}' is not a file")),
+        }
+    }
+}
+
+impl Display for File {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(b'a' + self.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_creates_files_only_on_the_board() {
+        assert_eq!(File::new(7), Some(File::H));
+        assert_eq!(File::new(8), None);
+        assert_eq!(File::B.offset(-1), Some(File::A));
+        assert_eq!(File::B.offset(-2), None);
+        assert_eq!(File::G.offset(2), None);
+    }
+
+    #[test]
+    fn it_converts_files_from_and_to_letters() {
+        let names: Vec<String> = File::iter().map(|f| f.to_string()).collect();
+        assert_eq!(names.concat(), "abcdefgh");
+        assert_eq!("e".parse(), Ok(File::E));
+        assert!("i".parse::<File>().is_err());
+        assert!("ab".parse::<File>().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use crate::common::random::Random;
+use crate::evaluation::evaluate_board::evaluate_board;
+use crate::move_generator::legal_moves::find_legal_move;
+use crate::move_generator::make_move::make_move;
+
+use super::args::Args;
+use super::board::Board;
+use super::board_evaluation::BoardEvaluation;
+use super::r#move::Move;
+use super::repetition_table::RepetitionTable;
+use super::termination::Termination;
+
+/// A game from its start position: the moves played so far, the hashes of all
+/// positions that occurred (needed to detect repetitions) and, once it has
+/// ended, the result and why.
+#[derive(Clone, Debug)]
+pub struct Game {
+    pub start_board: Board,
+    pub moves: Vec<Move>,
+    pub hashes: Vec<u64>,
+    pub result: BoardEvaluation,
+    pub termination: Option<Termination>,
+    board: Board,
+}
+
+impl Game {
+    pub fn new(start_board: Board) -> Self {
+        Self {
+            hashes: vec![start_board.get_hash()],
+            moves: Vec::new(),
+            result: BoardEvaluation::Inconclusive,
+            termination: None,
+            board: start_board.clone(),
+            start_board,
+        }
+    }
+
+    /// Sets up the position of `--fen` and plays the `--moves` on it.
+    pub fn from_args(args: &Args) -> Self {
+        let mut board = Board::from_fen_with_variant(&args.fen, args.variant);
+        board.castle.chess960 |= args.chess960;
+
+        let mut game = Game::new(board);
+        for uci in &args.moves {
+            if let Err(message) = game.play_uci(uci) {
+                panic!("Invalid moves: {message}");
+            }
+        }
+
+        game
+    }
+
+    pub fn get_start_fen(&self) -> String {
+        self.start_board.to_fen()
+    }
+
+    /// The current position.
+    pub fn get_board(&self) -> &Board {
+        &self.board
+    }
+
+    /// The hashes of the positions before the current one, oldest first.
+    pub fn get_previous_hashes(&self) -> &[u64] {
+        &self.hashes[..self.hashes.len() - 1]
+    }
+
+    pub fn play(&mut self, m: &Move) {
+        make_move(&mut self.board, m);
+        self.moves.push(*m);
+        self.hashes.push(self.board.get_hash());
+    }
+
+    /// Plays a move given in UCI notation, e.g. "e2e4".
+    pub fn play_uci(&mut self, uci: &str) -> Result<Move, String> {
+        let m = find_legal_move(&mut self.board, uci)
+            .ok_or(format!("'{uci}' is not a legal move"))?;
+        self.play(&m);
+        Ok(m)
+    }
+
+    /// Takes back the last move by replaying the others from the start.
+    pub fn undo(&mut self) -> Option<Move> {
+        let m = self.moves.pop()?;
+        self.hashes.pop();
+        self.board = self.start_board.clone();
+        for m in &self.moves {
+            make_move(&mut self.board, m);
+        }
+        Some(m)
+    }
+
+    /// Whether the current position occurred for the third time.
+    pub fn is_repetition(&self) -> bool {
+        RepetitionTable::new(&self.hashes, self.board.halfmove).is_threefold()
+    }
+
+    /// Evaluates the current position with the game history in mind and ends
+    /// the game if it is over.
+    pub fn evaluate(&mut self, random: &mut Random) -> BoardEvaluation {
+        if self.is_repetition() {
+            self.finish(BoardEvaluation::Draw, Termination::Repetition);
+            return BoardEvaluation::Draw;
+        }
+
+        let mut board = self.board.clone();
+        let evaluation = evaluate_board(&mut board, random).evaluation;
+        if evaluation.is_conclusive() {
+            let termination = if evaluation != BoardEvaluation::Draw {
+                Termination::Adjudicated
+            } else if board.halfmove >= 100 {
+                Termination::FiftyMoveRule
+            } else {
+                Termination::Adjudicated
+            };
+            self.finish(evaluation, termination);
+        }
+
+        evaluation
+    }
+
+    pub fn finish(
+        &mut self,
+        result: BoardEvaluation,
+        termination: Termination,
+    ) {
+        self.result = result;
+        self.termination = Some(termination);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::variant::Variant;
+
+    use super::*;
+
+    fn play(game: &mut Game, moves: &str) {
+        for uci in moves.split_whitespace() {
+            game.play_uci(uci).unwrap();
+        }
+    }
+
+    #[test]
+    fn it_applies_the_moves_of_the_args_after_the_fen() {
+        let game = Game::from_args(&Args {
+            moves: vec![String::from("e2e4"), String::from("e7e6")],
+            ..Args::default()
+        });
+
+        assert_eq!(game.moves.len(), 2);
+        assert_eq!(game.hashes.len(), 3);
+        assert_eq!(
+            game.get_board().to_fen(),
+            "rnbqkbnr/pppp1ppp/4p3/8/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2"
+        );
+        assert_eq!(
+            game.get_start_fen(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid moves: 'e2e5' is not a legal move")]
+    fn it_panics_on_illegal_moves_in_the_args() {
+        Game::from_args(&Args {
+            moves: vec![String::from("e2e5")],
+            ..Args::default()
+        });
+    }
+
+    #[test]
+    fn it_detects_a_threefold_repetition() {
+        let mut game = Game::new(Board::new());
+        play(&mut game, "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1");
+        assert!(!game.is_repetition());
+
+        play(&mut game, "f6g8");
+        assert!(game.is_repetition());
+
+        let mut random = Random::from_seed(0);
+        assert_eq!(game.evaluate(&mut random), BoardEvaluation::Draw);
+        assert_eq!(game.result, BoardEvaluation::Draw);
+        assert_eq!(game.termination, Some(Termination::Repetition));
+    }
+
+    #[test]
+    fn it_ends_a_game_by_the_fifty_move_rule() {
+        let mut game = Game::new(Board::from_fen_with_variant(
+            "4k3/8/8/8/8/8/8/R3K3 w - - 99 80",
+            Variant::Standard,
+        ));
+        play(&mut game, "a1a2");
+
+        let mut random = Random::from_seed(0);
+        assert_eq!(game.evaluate(&mut random), BoardEvaluation::Draw);
+        assert_eq!(game.termination, Some(Termination::FiftyMoveRule));
+    }
+
+    #[test]
+    fn it_takes_back_moves() {
+        let mut game = Game::new(Board::new());
+        play(&mut game, "e2e4 e7e5");
+
+        assert!(game.undo().is_some());
+        assert_eq!(game.moves.len(), 1);
+        assert_eq!(game.hashes.len(), 2);
+        assert_eq!(game.get_previous_hashes(), &[Board::new().get_hash()]);
+
+        assert!(game.undo().is_some());
+        assert!(game.undo().is_none());
+        assert_eq!(game.get_board(), &Board::new());
+    }
+}
@@ -0,0 +1,63 @@
+use super::engine_mode::EngineMode;
+use super::playout_policy::PlayoutPolicy;
+
+/// Options of the `match` subcommand. Engine settings which are not given
+/// fall back to the general options, e.g. `--max-iterations`.
+#[derive(Clone, Debug, clap::Args)]
+pub struct MatchArgs {
+    /// Number of games to play, colors alternate every game.
+    #[arg(long, value_parser, default_value_t = 10)]
+    pub games: u64,
+
+    /// File with one start position as FEN per line, each position is played
+    /// with both colors. Defaults to the position given by `--fen`.
+    #[arg(long, value_parser)]
+    pub openings: Option<String>,
+
+    /// Number of plies after which a game is adjudicated as a draw.
+    #[arg(long, value_parser, default_value_t = 300)]
+    pub max_plies: u64,
+
+    /// File to write the games to in PGN.
+    #[arg(long, value_parser)]
+    pub pgn: Option<String>,
+
+    #[arg(long, value_enum, default_value_t = EngineMode::Mcts)]
+    pub a_mode: EngineMode,
+    #[arg(long, value_parser)]
+    pub a_exploration_constant: Option<f64>,
+    #[arg(long, value_enum)]
+    pub a_playout_policy: Option<PlayoutPolicy>,
+    #[arg(long, value_parser)]
+    pub a_max_iterations: Option<u64>,
+    #[arg(long, value_parser)]
+    pub a_max_time: Option<u64>,
+
+    #[arg(long, value_enum, default_value_t = EngineMode::Mcts)]
+    pub b_mode: EngineMode,
+    #[arg(long, value_parser)]
+    pub b_exploration_constant: Option<f64>,
+    #[arg(long, value_enum)]
+    pub b_playout_policy: Option<PlayoutPolicy>,
+    #[arg(long, value_parser)]
+    pub b_max_iterations: Option<u64>,
+    #[arg(long, value_parser)]
+    pub b_max_time: Option<u64>,
+
+    /// Elo difference of the null hypothesis of the SPRT, the test is only run
+    /// if both `--sprt-elo0` and `--sprt-elo1` are given.
+    #[arg(long, value_parser)]
+    pub sprt_elo0: Option<f64>,
+
+    /// Elo difference of the alternative hypothesis of the SPRT.
+    #[arg(long, value_parser)]
+    pub sprt_elo1: Option<f64>,
+
+    /// Probability of a false positive of the SPRT.
+    #[arg(long, value_parser, default_value_t = 0.05)]
+    pub sprt_alpha: f64,
+
+    /// Probability of a false negative of the SPRT.
+    #[arg(long, value_parser, default_value_t = 0.05)]
+    pub sprt_beta: f64,
+}
@@ -0,0 +1,169 @@
+/// Game results of a match from the point of view of the first engine.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MatchScore {
+    pub draws: u64,
+    pub losses: u64,
+    pub wins: u64,
+}
+
+impl MatchScore {
+    pub fn get_games(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// Average points per game, a draw counts half.
+    pub fn get_score(&self) -> f64 {
+        if self.get_games() == 0 {
+            return 0.5;
+        }
+        (self.wins as f64 + 0.5 * self.draws as f64) / self.get_games() as f64
+    }
+
+    /// Elo difference implied by the score, infinite if all games were won or
+    /// lost.
+    pub fn get_elo(&self) -> f64 {
+        score_to_elo(self.get_score())
+    }
+
+    /// Half width of the 95% confidence interval of `get_elo`, infinite if
+    /// the Elo itself is or if the interval of the score reaches 0 or 1, as
+    /// happens after few games.
+    pub fn get_elo_error(&self) -> f64 {
+        let games = self.get_games() as f64;
+        let score = self.get_score();
+        if games == 0.0 || score == 0.0 || score == 1.0 {
+            return f64::INFINITY;
+        }
+
+        let deviation = (self.get_variance() / games).sqrt();
+        let upper = score + 1.96 * deviation;
+        let lower = score - 1.96 * deviation;
+        if lower <= 0.0 || upper >= 1.0 {
+            return f64::INFINITY;
+        }
+
+        (score_to_elo(upper) - score_to_elo(lower)) / 2.0
+    }
+
+    /// Log likelihood ratio of the hypothesis that the Elo difference is
+    /// `elo1` against the hypothesis that it is `elo0`, using the normal
+    /// approximation of the trinomial game outcome.
+    /// See: https://www.chessprogramming.org/Sequential_Probability_Ratio_Test
+    pub fn get_sprt_llr(&self, elo0: f64, elo1: f64) -> f64 {
+        let variance = self.get_variance();
+        if self.get_games() == 0 || variance == 0.0 {
+            return 0.0;
+        }
+
+        let score = self.get_score();
+        let score0 = elo_to_score(elo0);
+        let score1 = elo_to_score(elo1);
+
+        (score1 - score0) * (2.0 * score - score0 - score1)
+            / (2.0 * variance / self.get_games() as f64)
+    }
+
+    /// Per game variance of the points.
+    fn get_variance(&self) -> f64 {
+        let games = self.get_games() as f64;
+        let score = self.get_score();
+
+        (self.wins as f64 * (1.0 - score).powi(2)
+            + self.draws as f64 * (0.5 - score).powi(2)
+            + self.losses as f64 * score.powi(2))
+            / games
+    }
+}
+
+/// Lower and upper log likelihood ratio bounds, the test accepts `elo0` below
+/// the lower and `elo1` above the upper bound.
+pub fn get_sprt_bounds(alpha: f64, beta: f64) -> (f64, f64) {
+    ((beta / (1.0 - alpha)).ln(), ((1.0 - beta) / alpha).ln())
+}
+
+fn score_to_elo(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+fn elo_to_score(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_calculates_the_elo_difference() {
+        let even = MatchScore {
+            draws: 2,
+            losses: 4,
+            wins: 4,
+        };
+        assert_eq!(even.get_elo(), 0.0);
+
+        let better = MatchScore {
+            draws: 0,
+            losses: 1,
+            wins: 3,
+        };
+        assert!((better.get_elo() - 190.85).abs() < 0.01);
+
+        let perfect = MatchScore {
+            draws: 0,
+            losses: 0,
+            wins: 3,
+        };
+        assert_eq!(perfect.get_elo(), f64::INFINITY);
+        assert_eq!(perfect.get_elo_error(), f64::INFINITY);
+    }
+
+    #[test]
+    fn it_shrinks_the_error_bars_with_more_games() {
+        let few = MatchScore {
+            draws: 10,
+            losses: 10,
+            wins: 10,
+        };
+        let many = MatchScore {
+            draws: 1000,
+            losses: 1000,
+            wins: 1000,
+        };
+
+        assert!(few.get_elo_error() > many.get_elo_error());
+        assert!((many.get_elo_error() - 10.15).abs() < 0.01);
+    }
+
+    #[test]
+    fn it_gives_infinite_error_bars_if_the_interval_leaves_the_scores() {
+        let one_each = MatchScore {
+            draws: 0,
+            losses: 1,
+            wins: 1,
+        };
+        assert_eq!(one_each.get_elo(), 0.0);
+        assert_eq!(one_each.get_elo_error(), f64::INFINITY);
+    }
+
+    #[test]
+    fn it_calculates_the_sprt_log_likelihood_ratio() {
+        let (lower, upper) = get_sprt_bounds(0.05, 0.05);
+        assert!((lower + 2.944).abs() < 0.001);
+        assert!((upper - 2.944).abs() < 0.001);
+
+        let strong = MatchScore {
+            draws: 100,
+            losses: 100,
+            wins: 200,
+        };
+        assert!(strong.get_sprt_llr(0.0, 10.0) > upper);
+
+        let weak = MatchScore {
+            draws: 100,
+            losses: 200,
+            wins: 100,
+        };
+        assert!(weak.get_sprt_llr(0.0, 10.0) < lower);
+    }
+}
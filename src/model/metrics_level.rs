@@ -6,4 +6,6 @@ pub enum MetricsLevel {
     Minimal,
     Reduced,
     Full,
+    Json,
+    Tui,
 }
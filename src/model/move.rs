@@ -1,3 +1,8 @@
+use std::fmt::Display;
+
+use super::file::File;
+use super::square::Square;
+use super::types::square_names::*;
 use super::types::SquareIndex;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -15,6 +20,29 @@ pub enum MoveSpecial {
     PromoteKnightWhite = 10,
     PromoteQueenWhite = 11,
     PromoteRookWhite = 12,
+    PromoteKingBlack = 13,
+    PromoteKingWhite = 14,
+}
+
+impl MoveSpecial {
+    /// All specials, each at the index of its discriminant.
+    pub const ALL: [MoveSpecial; 15] = [
+        MoveSpecial::CastleLongBlack,
+        MoveSpecial::CastleShortBlack,
+        MoveSpecial::CastleLongWhite,
+        MoveSpecial::CastleShortWhite,
+        MoveSpecial::EnPassant,
+        MoveSpecial::PromoteBishopBlack,
+        MoveSpecial::PromoteKnightBlack,
+        MoveSpecial::PromoteQueenBlack,
+        MoveSpecial::PromoteRookBlack,
+        MoveSpecial::PromoteBishopWhite,
+        MoveSpecial::PromoteKnightWhite,
+        MoveSpecial::PromoteQueenWhite,
+        MoveSpecial::PromoteRookWhite,
+        MoveSpecial::PromoteKingBlack,
+        MoveSpecial::PromoteKingWhite,
+    ];
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -33,34 +61,55 @@ impl Move {
         }
     }
 
+    /// Castling with the king on `king`, which ends up on the c-file (long)
+    /// or on the g-file (short), in Chess960 as well.
+    pub fn castle(special: MoveSpecial, king: SquareIndex) -> Move {
+        let file = match special {
+            MoveSpecial::CastleLongBlack | MoveSpecial::CastleLongWhite => {
+                File::C
+            }
+            MoveSpecial::CastleShortBlack | MoveSpecial::CastleShortWhite => {
+                File::G
+            }
+            _ => panic!("Move should be castling"),
+        };
+        let rank = Square::from_index(king).rank();
+
+        Move {
+            from: king,
+            to: Square::from_file_rank(file, rank).index(),
+            special: Some(special),
+        }
+    }
+
     pub fn castle_long_black() -> Move {
         Move {
-            from: 60,
-            to: 58,
+            from: E8,
+            to: C8,
             special: Some(MoveSpecial::CastleLongBlack),
         }
     }
 
     pub fn castle_short_black() -> Move {
         Move {
-            from: 60,
-            to: 62,
+            from: E8,
+            to: G8,
             special: Some(MoveSpecial::CastleShortBlack),
         }
     }
 
     pub fn castle_long_white() -> Move {
         Move {
-            from: 4,
-            to: 2,
+            from: E1,
+            to: C1,
             special: Some(MoveSpecial::CastleLongWhite),
         }
     }
 
     pub fn castle_short_white() -> Move {
         Move {
-            from: 4,
-            to: 6,
+            from: E1,
+            to: G1,
             special: Some(MoveSpecial::CastleShortWhite),
         }
     }
@@ -129,6 +178,22 @@ impl Move {
         }
     }
 
+    pub fn promote_king_black(from: SquareIndex, to: SquareIndex) -> Move {
+        Move {
+            from,
+            to,
+            special: Some(MoveSpecial::PromoteKingBlack),
+        }
+    }
+
+    pub fn promote_king_white(from: SquareIndex, to: SquareIndex) -> Move {
+        Move {
+            from,
+            to,
+            special: Some(MoveSpecial::PromoteKingWhite),
+        }
+    }
+
     pub fn en_passant(from: SquareIndex, to: SquareIndex) -> Move {
         Move {
             from,
@@ -150,6 +215,33 @@ impl Move {
     }
 }
 
+impl Display for Move {
+    /// Formats the move in UCI long algebraic notation, e.g. "e2e4" or "a7a8q"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let promotion = match self.special {
+            Some(MoveSpecial::PromoteBishopBlack)
+            | Some(MoveSpecial::PromoteBishopWhite) => "b",
+            Some(MoveSpecial::PromoteKnightBlack)
+            | Some(MoveSpecial::PromoteKnightWhite) => "n",
+            Some(MoveSpecial::PromoteQueenBlack)
+            | Some(MoveSpecial::PromoteQueenWhite) => "q",
+            Some(MoveSpecial::PromoteRookBlack)
+            | Some(MoveSpecial::PromoteRookWhite) => "r",
+            Some(MoveSpecial::PromoteKingBlack)
+            | Some(MoveSpecial::PromoteKingWhite) => "k",
+            _ => "",
+        };
+
+        write!(
+            f,
+            "{}{}{}",
+            Square::from_index(self.from),
+            Square::from_index(self.to),
+            promotion
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -207,4 +299,14 @@ mod test {
         assert!(!Move::castle_long_white().is_promotion());
         assert!(!Move::castle_short_white().is_promotion());
     }
+
+    #[test]
+    fn it_formats_moves_in_uci_notation() {
+        assert_eq!(Move::from_to(12, 28).to_string(), "e2e4");
+        assert_eq!(Move::castle_short_white().to_string(), "e1g1");
+        assert_eq!(Move::castle_long_black().to_string(), "e8c8");
+        assert_eq!(Move::en_passant(36, 43).to_string(), "e5d6");
+        assert_eq!(Move::promote_queen_white(48, 56).to_string(), "a7a8q");
+        assert_eq!(Move::promote_knight_black(9, 0).to_string(), "b2a1n");
+    }
 }
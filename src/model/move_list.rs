@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+use std::ops::ControlFlow;
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::r#move::Move;
+
+/// Capacity of a move list on the stack. No reachable position has more than
+/// 218 legal moves, but a crafted FEN may give a side any number of promoted
+/// pieces and exceed it with pseudo legal moves.
+pub const MAX_MOVES: usize = 256;
+
+/// A list of moves which lives on the stack, so that generating moves does
+/// not allocate. Beyond `MAX_MOVES` the moves move to the heap. It
+/// dereferences to a slice of its moves.
+#[derive(Clone)]
+pub struct MoveList {
+    len: usize,
+    moves: [Move; MAX_MOVES],
+    spilled: Vec<Move>,
+}
+
+impl MoveList {
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            moves: [Move::from_to(0, 0); MAX_MOVES],
+            spilled: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, m: Move) {
+        if self.len < MAX_MOVES {
+            self.moves[self.len] = m;
+            self.len += 1;
+            return;
+        }
+
+        if self.spilled.is_empty() {
+            self.spilled.extend_from_slice(&self.moves);
+        }
+        self.spilled.push(m);
+    }
+
+    /// A visitor for the `visit` functions of the move generator, which pushes
+    /// every move it is passed to the list.
+    pub fn visitor(&mut self) -> impl FnMut(Move) -> ControlFlow<()> + '_ {
+        |m| {
+            self.push(m);
+            ControlFlow::Continue(())
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.spilled.clear();
+    }
+
+    /// Keeps only the moves for which `f` returns true, in their order.
+    pub fn retain<F: FnMut(&Move) -> bool>(&mut self, mut f: F) {
+        if !self.spilled.is_empty() {
+            self.spilled.retain(f);
+            if self.spilled.len() <= MAX_MOVES {
+                self.len = self.spilled.len();
+                self.moves[..self.len].copy_from_slice(&self.spilled);
+                self.spilled.clear();
+            }
+            return;
+        }
+
+        let mut len = 0;
+        for i in 0..self.len {
+            if f(&self.moves[i]) {
+                self.moves[len] = self.moves[i];
+                len += 1;
+            }
+        }
+        self.len = len;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        if self.spilled.is_empty() {
+            &self.moves[..self.len]
+        } else {
+            &self.spilled
+        }
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        if self.spilled.is_empty() {
+            &mut self.moves[..self.len]
+        } else {
+            &mut self.spilled
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl PartialEq<Vec<Move>> for MoveList {
+    fn eq(&self, other: &Vec<Move>) -> bool {
+        **self == **other
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::random::Random;
+
+    use super::*;
+
+    fn list(moves: &[Move]) -> MoveList {
+        let mut list = MoveList::new();
+        for m in moves {
+            list.push(*m);
+        }
+        list
+    }
+
+    #[test]
+    fn it_pushes_moves_and_dereferences_to_them() {
+        let mut moves = MoveList::new();
+        assert!(moves.is_empty());
+
+        moves.push(Move::from_to(8, 16));
+        moves.push(Move::from_to(8, 24));
+
+        assert_eq!(moves.len(), 2);
+        assert_eq!(moves[1], Move::from_to(8, 24));
+        assert_eq!(moves, vec![Move::from_to(8, 16), Move::from_to(8, 24)]);
+        assert_eq!((&moves).into_iter().count(), 2);
+
+        moves.clear();
+        assert!(moves.is_empty());
+    }
+
+    #[test]
+    fn it_retains_moves_in_order() {
+        let mut moves = list(&[
+            Move::from_to(1, 2),
+            Move::from_to(3, 4),
+            Move::from_to(5, 6),
+        ]);
+        moves.retain(|m| m.from != 3);
+        assert_eq!(moves, vec![Move::from_to(1, 2), Move::from_to(5, 6)]);
+    }
+
+    #[test]
+    fn it_shuffles_the_moves() {
+        let ordered: Vec<Move> = (0..20).map(|i| Move::from_to(i, i)).collect();
+        let mut moves = list(&ordered);
+        Random::from_seed(0).shuffle(&mut moves);
+
+        assert_ne!(moves, ordered);
+        let mut sorted = moves.to_vec();
+        sorted.sort_by_key(|m| m.from);
+        assert_eq!(sorted, ordered);
+    }
+
+    #[test]
+    fn it_keeps_the_moves_beyond_the_capacity() {
+        let ordered: Vec<Move> = (0..300)
+            .map(|i| Move::from_to(i as u8, (i / 256) as u8))
+            .collect();
+        let mut moves = list(&ordered);
+        assert_eq!(moves, ordered);
+
+        moves.retain(|m| m.to == 0 && m.from < 100);
+        assert_eq!(moves, ordered[..100].to_vec());
+
+        moves.push(Move::from_to(1, 1));
+        assert_eq!(moves.len(), 101);
+
+        moves.clear();
+        assert!(moves.is_empty());
+    }
+}
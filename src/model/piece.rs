@@ -1,4 +1,5 @@
 use super::color::Color;
+use super::piece_type::PieceType;
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, PartialOrd)]
 pub enum Piece {
@@ -17,6 +18,23 @@ pub enum Piece {
 }
 
 impl Piece {
+    pub const fn new(color: Color, piece_type: PieceType) -> Self {
+        match (color, piece_type) {
+            (Color::Black, PieceType::Bishop) => Piece::BlackBishop,
+            (Color::Black, PieceType::King) => Piece::BlackKing,
+            (Color::Black, PieceType::Knight) => Piece::BlackKnight,
+            (Color::Black, PieceType::Pawn) => Piece::BlackPawn,
+            (Color::Black, PieceType::Queen) => Piece::BlackQueen,
+            (Color::Black, PieceType::Rook) => Piece::BlackRook,
+            (Color::White, PieceType::Bishop) => Piece::WhiteBishop,
+            (Color::White, PieceType::King) => Piece::WhiteKing,
+            (Color::White, PieceType::Knight) => Piece::WhiteKnight,
+            (Color::White, PieceType::Pawn) => Piece::WhitePawn,
+            (Color::White, PieceType::Queen) => Piece::WhiteQueen,
+            (Color::White, PieceType::Rook) => Piece::WhiteRook,
+        }
+    }
+
     pub const fn get_color(&self) -> Color {
         if (*self as u8) < 6 {
             Color::Black
@@ -98,6 +116,24 @@ impl Piece {
     pub fn is_white(self) -> bool {
         self as u32 >= 6
     }
+
+    /// The letter used for the piece in FEN, uppercase for white.
+    pub fn get_fen_char(self) -> char {
+        match self {
+            Piece::BlackBishop => 'b',
+            Piece::BlackKing => 'k',
+            Piece::BlackKnight => 'n',
+            Piece::BlackPawn => 'p',
+            Piece::BlackQueen => 'q',
+            Piece::BlackRook => 'r',
+            Piece::WhiteBishop => 'B',
+            Piece::WhiteKing => 'K',
+            Piece::WhiteKnight => 'N',
+            Piece::WhitePawn => 'P',
+            Piece::WhiteQueen => 'Q',
+            Piece::WhiteRook => 'R',
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,9 @@
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, Debug, ValueEnum, PartialEq)]
+pub enum PlayoutPolicy {
+    /// Play uniformly random legal moves.
+    Random,
+    /// Play a random capture if there is one, otherwise a random move.
+    CapturesFirst,
+}
@@ -0,0 +1,95 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::color::Color;
+
+/// A rank of the board, `1` to `8`, which can only be created in range.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Rank(u8);
+
+impl Rank {
+    pub const FIRST: Rank = Rank(0);
+    pub const SECOND: Rank = Rank(1);
+    pub const THIRD: Rank = Rank(2);
+    pub const FOURTH: Rank = Rank(3);
+    pub const FIFTH: Rank = Rank(4);
+    pub const SIXTH: Rank = Rank(5);
+    pub const SEVENTH: Rank = Rank(6);
+    pub const EIGHTH: Rank = Rank(7);
+
+    pub const fn new(index: u8) -> Option<Rank> {
+        if index < 8 {
+            Some(Rank(index))
+        } else {
+            None
+        }
+    }
+
+    pub const fn index(self) -> u8 {
+        self.0
+    }
+
+    /// The rank `ranks` to the north (or to the south if negative), if it is
+    /// still on the board.
+    pub const fn offset(self, ranks: i8) -> Option<Rank> {
+        let index = self.0 as i8 + ranks;
+        if 0 <= index && index < 8 {
+            Some(Rank(index as u8))
+        } else {
+            None
+        }
+    }
+
+    /// The rank the pieces of the color start on.
+    pub const fn back_rank(color: Color) -> Rank {
+        match color {
+            Color::Black => Rank::EIGHTH,
+            Color::White => Rank::FIRST,
+        }
+    }
+
+    /// All ranks from `1` to `8`.
+    pub fn iter() -> impl DoubleEndedIterator<Item = Rank> {
+        (0..8).map(Rank)
+    }
+}
+
+impl FromStr for Rank {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            [c @ b'1'..=b'8'] => Ok(Rank(c - b'1')),
+            _ => Err(format!("'{s}' is not a rank")),
+        }
+    }
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", char::from(b'1' + self.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_creates_ranks_only_on_the_board() {
+        assert_eq!(Rank::new(0), Some(Rank::FIRST));
+        assert_eq!(Rank::new(8), None);
+        assert_eq!(Rank::SEVENTH.offset(1), Some(Rank::EIGHTH));
+        assert_eq!(Rank::SEVENTH.offset(2), None);
+        assert_eq!(Rank::SECOND.offset(-2), None);
+    }
+
+    #[test]
+    fn it_converts_ranks_from_and_to_digits() {
+        let names: Vec<String> = Rank::iter().map(|r| r.to_string()).collect();
+        assert_eq!(names.concat(), "12345678");
+        assert_eq!("3".parse(), Ok(Rank::THIRD));
+        assert!("0".parse::<Rank>().is_err());
+        assert!("9".parse::<Rank>().is_err());
+    }
+}
@@ -0,0 +1,179 @@
+/// Positions the table holds at most. The game is drawn once the halfmove
+/// clock reaches 100, so older positions never take part in a repetition.
+const CAPACITY: usize = 101;
+
+/// The positions since the last capture or pawn move. Positions before an
+/// irreversible move cannot occur again, so the table never grows beyond the
+/// halfmove clock and fits in a fixed array, which is cheap to copy. The tree
+/// keeps the table up to its root and every iteration extends a copy along
+/// the selected path and the playout.
+#[derive(Clone, Debug)]
+pub struct RepetitionTable {
+    hashes: [u64; CAPACITY],
+    len: usize,
+}
+
+impl RepetitionTable {
+    /// Creates the table from the hashes of a game, oldest first and ending
+    /// with the current position whose halfmove clock is `halfmove`.
+    pub fn new(hashes: &[u64], halfmove: usize) -> Self {
+        let start = hashes.len().saturating_sub(halfmove + 1);
+        let mut table = Self::default();
+        for hash in &hashes[start..] {
+            table.add(*hash);
+        }
+        table
+    }
+
+    /// Adds the position reached by the next move.
+    pub fn push(&mut self, hash: u64, halfmove: usize) {
+        if halfmove == 0 {
+            self.len = 0;
+        }
+        self.add(hash);
+    }
+
+    /// Whether the current position occurred for the third time. The hash
+    /// includes the side to move, so equal hashes are the same position and
+    /// only every second position needs to be compared.
+    pub fn is_threefold(&self) -> bool {
+        let Some(current) = self.get_hashes().last() else {
+            return false;
+        };
+
+        self.get_hashes()
+            .iter()
+            .rev()
+            .step_by(2)
+            .filter(|hash| *hash == current)
+            .count()
+            >= 3
+    }
+
+    fn get_hashes(&self) -> &[u64] {
+        &self.hashes[..self.len]
+    }
+
+    fn add(&mut self, hash: u64) {
+        if self.len == CAPACITY {
+            self.hashes.copy_within(1.., 0);
+            self.len -= 1;
+        }
+        self.hashes[self.len] = hash;
+        self.len += 1;
+    }
+}
+
+impl Default for RepetitionTable {
+    fn default() -> Self {
+        Self {
+            hashes: [0; CAPACITY],
+            len: 0,
+        }
+    }
+}
+
+impl PartialEq for RepetitionTable {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_hashes() == other.get_hashes()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::common::random::Random;
+    use crate::model::board::Board;
+    use crate::move_generator::legal_moves::find_legal_move;
+    use crate::move_generator::legal_moves::generate_move;
+    use crate::move_generator::make_move::make_move;
+
+    use super::*;
+
+    #[test]
+    fn it_finds_no_repetition_in_an_empty_table() {
+        assert!(!RepetitionTable::default().is_threefold());
+    }
+
+    #[test]
+    fn it_finds_the_third_occurrence_of_the_current_position() {
+        let table = RepetitionTable::new(&[3, 1, 3, 2, 3], 4);
+        assert!(table.is_threefold());
+
+        let table = RepetitionTable::new(&[3, 1, 3, 2, 3], 3);
+        assert!(!table.is_threefold());
+    }
+
+    #[test]
+    fn it_forgets_positions_before_irreversible_moves() {
+        let mut table = RepetitionTable::new(&[1, 2, 1, 2], 3);
+        table.push(1, 4);
+        assert!(table.is_threefold());
+
+        table.push(2, 0);
+        assert_eq!(table.get_hashes(), [2]);
+        table.push(1, 1);
+        assert!(!table.is_threefold());
+    }
+
+    #[test]
+    fn it_detects_the_repetition_of_the_knight_shuffle() {
+        let mut board = Board::new();
+        let mut table = RepetitionTable::new(&[board.get_hash()], 0);
+
+        let moves = ["g1f3", "g8f6", "f3g1", "f6g8"].repeat(2);
+        for (i, uci) in moves.iter().enumerate() {
+            let m = find_legal_move(&mut board, uci).unwrap();
+            make_move(&mut board, &m);
+            table.push(board.get_hash(), board.halfmove);
+
+            // the starting position occurs for the third time after 8 moves
+            assert_eq!(table.is_threefold(), i == 7);
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_positions_of_the_last_hundred_halfmoves() {
+        let mut table = RepetitionTable::new(&[7], 0);
+        for halfmove in 1..=200 {
+            table.push(halfmove as u64 % 2, halfmove);
+        }
+
+        assert_eq!(table.get_hashes().len(), CAPACITY);
+        assert!(!table.get_hashes().contains(&7));
+        assert!(table.is_threefold());
+    }
+
+    #[test]
+    fn it_agrees_with_a_full_scan_during_random_playouts() {
+        let mut random = Random::from_seed(0);
+        let mut repetitions = 0;
+
+        for _ in 0..50 {
+            // the position of the threefold repetition playout in `simulate`
+            let mut board = Board::from_fen(
+                "1kb5/1p1p4/rP1P4/1P6/8/1p1p4/1P1P4/1KB5 w - - 0 1",
+            );
+            let mut hashes = vec![board.get_hash()];
+            let mut table = RepetitionTable::new(&hashes, board.halfmove);
+
+            for _ in 0..100 {
+                let Some(m) = generate_move(&mut board, &mut random) else {
+                    break;
+                };
+                make_move(&mut board, &m);
+                hashes.push(board.get_hash());
+                table.push(board.get_hash(), board.halfmove);
+
+                let occurrences =
+                    hashes.iter().filter(|h| **h == board.get_hash()).count();
+                assert_eq!(table.is_threefold(), occurrences >= 3);
+                assert!(table.get_hashes().len() <= board.halfmove + 1);
+                if table.is_threefold() {
+                    repetitions += 1;
+                }
+            }
+        }
+
+        assert!(repetitions > 0);
+    }
+}
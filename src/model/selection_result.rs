@@ -1,8 +1,11 @@
 use super::board::Board;
+use super::repetition_table::RepetitionTable;
 use super::types::TreeNodeIndex;
 
 #[derive(Debug, PartialEq)]
 pub struct SelectionResult {
     pub board: Board,
     pub node_index: TreeNodeIndex,
+    /// The positions of the game up to the selected node.
+    pub repetitions: RepetitionTable,
 }
@@ -0,0 +1,141 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+use super::file::File;
+use super::rank::Rank;
+use super::types::SquareIndex;
+
+/// A square of the board, a1 = 0 to h8 = 63, which can only be created on the
+/// board. Moves and lookup tables store the bare `SquareIndex`, arithmetic
+/// on squares should go through `file`, `rank` and `offset`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Square(SquareIndex);
+
+impl Square {
+    pub const fn new(index: SquareIndex) -> Option<Square> {
+        if index < 64 {
+            Some(Square(index))
+        } else {
+            None
+        }
+    }
+
+    /// The square of an index known to be on the board, e.g. from a move or a
+    /// bitboard, panics otherwise.
+    pub const fn from_index(index: SquareIndex) -> Square {
+        assert!(index < 64, "Invalid square index");
+        Square(index)
+    }
+
+    pub const fn from_file_rank(file: File, rank: Rank) -> Square {
+        Square(8 * rank.index() + file.index())
+    }
+
+    pub const fn index(self) -> SquareIndex {
+        self.0
+    }
+
+    pub const fn file(self) -> File {
+        match File::new(self.0 % 8) {
+            Some(file) => file,
+            None => unreachable!(),
+        }
+    }
+
+    pub const fn rank(self) -> Rank {
+        match Rank::new(self.0 / 8) {
+            Some(rank) => rank,
+            None => unreachable!(),
+        }
+    }
+
+    /// The square `files` to the east and `ranks` to the north (west and south
+    /// if negative), if it is still on the board.
+    pub const fn offset(self, files: i8, ranks: i8) -> Option<Square> {
+        match (self.file().offset(files), self.rank().offset(ranks)) {
+            (Some(file), Some(rank)) => {
+                Some(Square::from_file_rank(file, rank))
+            }
+            _ => None,
+        }
+    }
+
+    /// All squares from a1 to h8, rank by rank.
+    pub fn iter() -> impl DoubleEndedIterator<Item = Square> {
+        (0..64).map(Square)
+    }
+}
+
+impl FromStr for Square {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || format!("'{s}' is not a square");
+        if !s.is_ascii() || s.len() != 2 {
+            return Err(error());
+        }
+
+        let file: File = s[..1].parse().map_err(|_| error())?;
+        let rank: Rank = s[1..].parse().map_err(|_| error())?;
+        Ok(Square::from_file_rank(file, rank))
+    }
+}
+
+impl Display for Square {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.file(), self.rank())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_creates_squares_only_on_the_board() {
+        assert_eq!(Square::new(H8), Some(Square::from_index(H8)));
+        assert_eq!(Square::new(64), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid square index")]
+    fn it_panics_on_indices_off_the_board() {
+        Square::from_index(64);
+    }
+
+    #[test]
+    fn it_splits_squares_into_file_and_rank() {
+        let square = Square::from_index(E4);
+        assert_eq!(square.file(), File::E);
+        assert_eq!(square.rank(), Rank::FOURTH);
+        assert_eq!(Square::from_file_rank(File::E, Rank::FOURTH), square);
+    }
+
+    #[test]
+    fn it_offsets_squares_without_wrapping_around_the_board() {
+        let a1 = Square::from_index(A1);
+        assert_eq!(a1.offset(1, 2), Some(Square::from_index(B3)));
+        assert_eq!(a1.offset(-1, 0), None);
+        assert_eq!(a1.offset(0, -1), None);
+        assert_eq!(Square::from_index(H4).offset(1, 0), None);
+        assert_eq!(
+            Square::from_index(H7).offset(0, 1),
+            Some(Square::from_index(H8))
+        );
+    }
+
+    #[test]
+    fn it_converts_squares_from_and_to_algebraic_notation() {
+        for (square, name) in Square::iter().zip(SQUARE_NAMES) {
+            assert_eq!(square.to_string(), name);
+            assert_eq!(name.parse(), Ok(square));
+        }
+
+        assert!("i1".parse::<Square>().is_err());
+        assert!("a9".parse::<Square>().is_err());
+        assert!("a10".parse::<Square>().is_err());
+        assert!("ä1".parse::<Square>().is_err());
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::Display;
+
+/// Why a game has ended.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Termination {
+    /// Decided by the rules of the variant on the board, e.g. a mate, an
+    /// exploded king, a stalemate or insufficient material.
+    Adjudicated,
+    FiftyMoveRule,
+    MaxPlies,
+    Repetition,
+}
+
+impl Display for Termination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Termination::Adjudicated => "adjudicated",
+            Termination::FiftyMoveRule => "50 move rule",
+            Termination::MaxPlies => "max plies",
+            Termination::Repetition => "repetition",
+        };
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,14 @@
+/// Options of the `testsuite` subcommand. The search budget per position is
+/// given by the general options `--max-iterations` and `--max-time`.
+#[derive(Clone, Debug, clap::Args)]
+pub struct TestsuiteArgs {
+    /// EPD file with one test position per line.
+    #[arg(value_parser)]
+    pub file: String,
+
+    /// Minimal number of positions which have to be solved, otherwise the
+    /// command exits with code 1. Use the result of a previous run to detect
+    /// regressions.
+    #[arg(long, value_parser, default_value_t = 0)]
+    pub min_solved: u64,
+}
@@ -1,33 +1,138 @@
+use std::cmp::Reverse;
 use std::f64::consts::SQRT_2;
+use std::mem::size_of;
+use std::ops::Range;
 
+use super::args::Args;
 use super::board::Board;
 use super::board_evaluation::BoardEvaluation;
 use super::color::Color;
 use super::r#move::Move;
+use super::repetition_table::RepetitionTable;
 use super::tree_node::TreeNode;
 use super::tree_node::TreeNodeScore;
+use super::tree_node::MOVES_NOT_GENERATED;
 use super::types::TreeNodeIndex;
 use super::types::TREE_NODE_ROOT_INDEX;
 
+/// The search tree. Nodes are stored in one vector and addressed by their
+/// index, the children of a node are always stored in one contiguous block.
+/// As children are added lazily, the block is reserved for all moves of a
+/// node on its first expansion, see `set_untried_moves`. The scores live in a
+/// parallel vector, as they are the only data updated on every iteration.
 pub struct Tree {
     board: Board,
+    expansion_visits: u64,
+    expansion_width: usize,
+    exploration_constant: f64,
+    max_size: Option<usize>,
     nodes: Vec<TreeNode>,
+    prunings: u64,
+    repetitions: RepetitionTable,
+    scores: Vec<TreeNodeScore>,
 }
 
 impl Tree {
     pub fn new(board: Board) -> Self {
-        let b = board.clone();
+        let root = Tree::construct_node(
+            board.clone(),
+            Move::from_to(0, 0), // TODO: make Option?
+            None,
+        );
+        let repetitions =
+            RepetitionTable::new(&[root.board_hash], board.halfmove);
         Self {
             board,
-            nodes: vec![Tree::construct_node(
-                b,
-                Move::from_to(0, 0), // TODO: make Option?
-                None,
-                TREE_NODE_ROOT_INDEX,
-            )],
+            expansion_visits: 1,
+            expansion_width: 1,
+            exploration_constant: SQRT_2,
+            max_size: None,
+            nodes: vec![root],
+            prunings: 0,
+            repetitions,
+            scores: vec![TreeNodeScore::default()],
         }
     }
 
+    /// Creates a tree from already linked nodes and their scores, e.g. loaded
+    /// from disk.
+    pub fn from_nodes(
+        board: Board,
+        nodes: Vec<TreeNode>,
+        scores: Vec<TreeNodeScore>,
+    ) -> Self {
+        assert!(!nodes.is_empty(), "A tree needs at least a root node.");
+        assert_eq!(nodes.len(), scores.len(), "Every node needs a score.");
+        let repetitions =
+            RepetitionTable::new(&[nodes[0].board_hash], board.halfmove);
+        Self {
+            board,
+            expansion_visits: 1,
+            expansion_width: 1,
+            exploration_constant: SQRT_2,
+            max_size: None,
+            nodes,
+            prunings: 0,
+            repetitions,
+            scores,
+        }
+    }
+
+    /// Applies the search options of the args.
+    pub fn configure(&mut self, args: &Args) {
+        self.set_exploration_constant(args.exploration_constant);
+        self.set_max_size(args.max_nodes.map(|n| n as usize));
+        self.set_expansion_visits(args.expansion_visits);
+        self.set_expansion_width(args.expansion_width as usize);
+    }
+
+    /// Weight of the exploration term in `calculate_uct`, defaults to sqrt(2).
+    pub fn set_exploration_constant(&mut self, exploration_constant: f64) {
+        self.exploration_constant = exploration_constant;
+    }
+
+    /// Visits a leaf needs before children are added to it, defaults to 1.
+    pub fn set_expansion_visits(&mut self, expansion_visits: u64) {
+        self.expansion_visits = expansion_visits;
+    }
+
+    pub fn get_expansion_visits(&self) -> u64 {
+        self.expansion_visits
+    }
+
+    /// Untried moves turned into children per expansion, defaults to 1.
+    pub fn set_expansion_width(&mut self, expansion_width: usize) {
+        self.expansion_width = expansion_width;
+    }
+
+    pub fn get_expansion_width(&self) -> usize {
+        self.expansion_width
+    }
+
+    /// Hashes of the positions of the game before the root, so that
+    /// repetitions are detected across the root.
+    pub fn set_history(&mut self, history: &[u64]) {
+        let mut hashes = history.to_vec();
+        hashes.push(self.get_node(TREE_NODE_ROOT_INDEX).board_hash);
+        self.repetitions = RepetitionTable::new(&hashes, self.board.halfmove);
+    }
+
+    /// The positions of the game up to the root, see `set_history`.
+    pub fn get_repetitions(&self) -> &RepetitionTable {
+        &self.repetitions
+    }
+
+    /// Maximal number of nodes, see `prune_if_full`. Unlimited by default.
+    pub fn set_max_size(&mut self, max_size: Option<usize>) {
+        self.max_size = max_size;
+    }
+
+    /// How often nodes were removed by `prune`, node indices obtained before
+    /// a pruning are invalid afterwards.
+    pub fn get_prunings(&self) -> u64 {
+        self.prunings
+    }
+
     pub fn get_board(&self) -> &Board {
         &self.board
     }
@@ -36,50 +141,297 @@ impl Tree {
         self.nodes.len()
     }
 
+    /// Heap memory held by the nodes and scores, including reserved but
+    /// unused capacity.
+    pub fn get_allocated_bytes(&self) -> usize {
+        self.nodes.capacity() * size_of::<TreeNode>()
+            + self.scores.capacity() * size_of::<TreeNodeScore>()
+    }
+
     pub fn get_root(&self) -> &TreeNode {
-        &self.nodes[TREE_NODE_ROOT_INDEX]
+        self.get_node(TREE_NODE_ROOT_INDEX)
     }
 
     pub fn get_node(&self, index: TreeNodeIndex) -> &TreeNode {
-        &self.nodes[index]
+        &self.nodes[index as usize]
     }
 
     pub fn get_node_mut(&mut self, index: TreeNodeIndex) -> &mut TreeNode {
-        &mut self.nodes[index]
+        &mut self.nodes[index as usize]
+    }
+
+    /// The score of a node, which is not a field of the node itself, so that
+    /// the scores updated on every iteration are stored densely.
+    pub fn get_score(&self, index: TreeNodeIndex) -> &TreeNodeScore {
+        &self.scores[index as usize]
+    }
+
+    pub fn get_score_mut(
+        &mut self,
+        index: TreeNodeIndex,
+    ) -> &mut TreeNodeScore {
+        &mut self.scores[index as usize]
     }
 
     pub fn get_parent(&self, index: TreeNodeIndex) -> Option<&TreeNode> {
-        self.nodes[index].parent_index.map(|pi| &self.nodes[pi])
+        self.get_node(index)
+            .parent_index
+            .map(|pi| self.get_node(pi))
     }
 
     pub fn get_parent_mut(
         &mut self,
         index: TreeNodeIndex,
     ) -> Option<&mut TreeNode> {
-        self.nodes[index].parent_index.map(|pi| &mut self.nodes[pi])
+        self.get_node(index)
+            .parent_index
+            .map(|pi| self.get_node_mut(pi))
     }
 
     pub fn get_sibling_indices(
         &self,
         index: TreeNodeIndex,
-    ) -> &[TreeNodeIndex] {
-        self.get_parent(index).map_or(&[], |p| &p.child_indices)
+    ) -> Range<TreeNodeIndex> {
+        self.get_parent(index)
+            .map_or(index..index, |p| p.get_child_indices())
     }
 
+    /// Adds a child to the parent. If the parent has reserved children, the
+    /// next of them becomes the child and has to be reserved for the same
+    /// move. Otherwise the child is appended, so the children of a node have
+    /// to be added one after another, without adding nodes elsewhere in
+    /// between.
     pub fn add_node(
         &mut self,
         board: Board,
         last_move: Move,
         parent_index: TreeNodeIndex,
     ) {
-        let node_index = self.nodes.len();
-        self.nodes.push(Tree::construct_node(
-            board,
-            last_move,
-            Some(parent_index),
-            node_index,
-        ));
-        self.nodes[parent_index].child_indices.push(node_index);
+        let node = Tree::construct_node(board, last_move, Some(parent_index));
+        let parent = self.get_node(parent_index);
+        let child_index = parent.get_child_indices().end;
+        let num_children = parent
+            .num_children
+            .checked_add(1)
+            .filter(|n| *n != MOVES_NOT_GENERATED)
+            .expect("A node cannot have more than u16::MAX - 1 children.");
+
+        if parent.get_untried_child_indices().is_empty() {
+            assert!(
+                !parent.has_untried_moves_generated(),
+                "Node {parent_index} has no untried move left."
+            );
+            let node_index = TreeNodeIndex::try_from(self.nodes.len())
+                .expect("The tree cannot hold more than u32::MAX nodes.");
+            if parent.has_children() {
+                assert_eq!(
+                    child_index, node_index,
+                    "The children of node {parent_index} are not contiguous."
+                );
+            } else {
+                self.get_node_mut(parent_index).first_child = node_index;
+            }
+            self.nodes.push(node);
+            self.scores.push(TreeNodeScore::default());
+        } else {
+            let reserved = self.get_node_mut(child_index);
+            assert_eq!(
+                reserved.last_move, node.last_move,
+                "Node {parent_index} reserved a child for another move."
+            );
+            *reserved = node;
+        }
+
+        self.get_node_mut(parent_index).num_children = num_children;
+    }
+
+    /// Reserves a child for each move of the node which has no child yet,
+    /// right behind its children, which therefore have to be the last nodes
+    /// of the tree. Until they are added with `add_node`, reserved children
+    /// only hold their move. The moves are tried in the given order. Without
+    /// moves the node is fully expanded.
+    pub fn set_untried_moves(&mut self, index: TreeNodeIndex, moves: &[Move]) {
+        let size = self.nodes.len();
+        TreeNodeIndex::try_from(size + moves.len())
+            .expect("The tree cannot hold more than u32::MAX nodes.");
+        let node = self.get_node_mut(index);
+        assert!(
+            !node.has_untried_moves_generated(),
+            "The moves of node {index} were already generated."
+        );
+        node.num_moves =
+            u16::try_from(node.num_children as usize + moves.len())
+                .ok()
+                .filter(|n| *n != MOVES_NOT_GENERATED)
+                .expect("A node cannot have more than u16::MAX - 1 moves.");
+
+        if moves.is_empty() {
+            return;
+        }
+        if node.has_children() {
+            assert_eq!(
+                node.get_child_indices().end as usize,
+                size,
+                "The children of node {index} are not contiguous."
+            );
+        } else {
+            node.first_child = size as TreeNodeIndex;
+        }
+
+        let our_color = match node.our_color {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        };
+        for m in moves {
+            self.nodes.push(TreeNode {
+                board_hash: 0,
+                evaluation: BoardEvaluation::Inconclusive,
+                first_child: 0,
+                last_move: (*m).into(),
+                num_children: 0,
+                num_moves: MOVES_NOT_GENERATED,
+                our_color,
+                parent_index: Some(index),
+            });
+            self.scores.push(TreeNodeScore::default());
+        }
+    }
+
+    pub fn get_untried_moves(&self, index: TreeNodeIndex) -> Vec<Move> {
+        self.get_node(index)
+            .get_untried_child_indices()
+            .map(|i| self.get_node(i).get_last_move())
+            .collect()
+    }
+
+    /// The move of the next reserved child of the node, see `add_node`.
+    pub fn get_next_untried_move(&self, index: TreeNodeIndex) -> Option<Move> {
+        self.get_node(index)
+            .get_untried_child_indices()
+            .next()
+            .map(|i| self.get_node(i).get_last_move())
+    }
+
+    /// Prunes the tree down to three quarters of the maximal size once it has
+    /// grown beyond it, so that not every new node triggers a pruning.
+    pub fn prune_if_full(&mut self) {
+        if let Some(max_size) = self.max_size {
+            if self.nodes.len() > max_size {
+                self.prune(max_size - max_size / 4);
+            }
+        }
+    }
+
+    /// Collapses expanded nodes back into leaves until at most `target_size`
+    /// nodes are left, the least visited and deepest nodes first. Collapsed
+    /// nodes keep their scores and evaluations and are expanded again once
+    /// they are selected. Subtrees containing proven nodes are only collapsed
+    /// if their root is proven itself, so no proven evaluation gets lost. The
+    /// root is never collapsed, so fewer nodes than `target_size` may be left.
+    pub fn prune(&mut self, target_size: usize) {
+        let size = self.nodes.len();
+        if size <= target_size {
+            return;
+        }
+
+        let mut depths: Vec<u32> = vec![0; size];
+        for i in 1..size {
+            depths[i] = depths[self.get_parent_index(i)] + 1;
+        }
+
+        let mut contains_proven = vec![false; size];
+        for i in (1..size).rev() {
+            if self.nodes[i].evaluation.is_conclusive() || contains_proven[i] {
+                contains_proven[self.get_parent_index(i)] = true;
+            }
+        }
+
+        let mut candidates: Vec<usize> = (1..size)
+            .filter(|i| {
+                let node = &self.nodes[*i];
+                node.get_num_slots() > 0
+                    && (node.evaluation.is_conclusive() || !contains_proven[*i])
+            })
+            .collect();
+        candidates.sort_by_key(|i| {
+            (self.scores[*i].get_visits(), Reverse(depths[*i]))
+        });
+
+        let mut removed = vec![false; size];
+        let mut removed_count = 0;
+        for candidate in candidates {
+            if size - removed_count <= target_size {
+                break;
+            }
+            if removed[candidate] {
+                continue;
+            }
+
+            let mut stack = vec![candidate as TreeNodeIndex];
+            while let Some(i) = stack.pop() {
+                let node = self.get_node(i);
+                let end = node.first_child + node.get_num_slots() as u32;
+                for child in node.first_child..end {
+                    if !removed[child as usize] {
+                        removed[child as usize] = true;
+                        removed_count += 1;
+                        stack.push(child);
+                    }
+                }
+            }
+        }
+
+        if removed_count > 0 {
+            self.compact(&removed);
+            self.prunings += 1;
+        }
+    }
+
+    /// Drops the removed nodes and moves the others to the front, keeping
+    /// their order. Collapsed nodes generate their moves again on their next
+    /// expansion.
+    fn compact(&mut self, removed: &[bool]) {
+        let mut new_indices = vec![0; removed.len()];
+        let mut new_size = 0;
+        for (i, is_removed) in removed.iter().enumerate() {
+            if !is_removed {
+                new_indices[i] = new_size as TreeNodeIndex;
+                new_size += 1;
+            }
+        }
+
+        let mut write = 0;
+        for read in 0..removed.len() {
+            if removed[read] {
+                continue;
+            }
+
+            let mut node = self.nodes[read].clone();
+            node.parent_index =
+                node.parent_index.map(|p| new_indices[p as usize]);
+            if node.get_num_slots() > 0 {
+                if removed[node.first_child as usize] {
+                    node.first_child = 0;
+                    node.num_children = 0;
+                    node.num_moves = MOVES_NOT_GENERATED;
+                } else {
+                    node.first_child = new_indices[node.first_child as usize];
+                }
+            }
+
+            self.nodes[write] = node;
+            self.scores.swap(write, read);
+            write += 1;
+        }
+
+        self.nodes.truncate(new_size);
+        self.scores.truncate(new_size);
+    }
+
+    fn get_parent_index(&self, index: usize) -> usize {
+        self.nodes[index]
+            .parent_index
+            .expect("only the root has no parent") as usize
     }
 
     /// Calculates the upper confidence bound for trees
@@ -87,45 +439,42 @@ impl Tree {
     /// https://en.wikipedia.org/wiki/Monte_Carlo_tree_search
     /// https://www.chessprogramming.org/UCT
     pub fn calculate_uct(&self, node_index: TreeNodeIndex) -> u32 {
-        let node = &self.nodes[node_index];
+        let node = self.get_node(node_index);
+        let score = self.get_score(node_index);
         let our_color = &node.our_color;
 
         if let Some(win_color) = node.evaluation.get_win_color() {
             if win_color != node.our_color {
                 // always select proven wins
-                return std::u32::MAX;
+                return u32::MAX;
             } else {
                 // never select proven losses
-                return std::u32::MIN;
+                return u32::MIN;
             }
         }
 
-        let node_visits = (node.score.draws
-            + node.score.wins_black
-            + node.score.wins_white) as f64;
+        let node_visits = score.get_visits() as f64;
 
         if node_visits == 0.0 {
             // select unvisited nodes, but prefer proven wins
-            return std::u32::MAX - 1;
+            return u32::MAX - 1;
         }
 
         let parent_index = node
             .parent_index
             .expect("UCT calculation is not applicable to root nodes.");
-        let parent = &self.nodes[parent_index];
-        let parent_visits = (parent.score.draws
-            + parent.score.wins_black
-            + parent.score.wins_white) as f64;
+        let parent_visits = self.get_score(parent_index).get_visits() as f64;
 
         // use minmax rule to factor in point of view
         let node_win_ratio = if *our_color == Color::Black {
-            (node.score.wins_white as f64) / node_visits
+            (score.wins_white as f64) / node_visits
         } else {
-            (node.score.wins_black as f64) / node_visits
+            (score.wins_black as f64) / node_visits
         };
 
-        let uct =
-            node_win_ratio + SQRT_2 * (parent_visits.ln() / node_visits).sqrt();
+        let uct = node_win_ratio
+            + self.exploration_constant
+                * (parent_visits.ln() / node_visits).sqrt();
 
         (uct * 10000.0) as u32
     }
@@ -134,32 +483,43 @@ impl Tree {
         board: Board,
         last_move: Move,
         parent_index: Option<TreeNodeIndex>,
-        self_index: TreeNodeIndex,
     ) -> TreeNode {
         let our_color = board.our_color;
         let board_hash = board.get_hash();
 
         TreeNode {
             board_hash,
-            child_indices: Vec::new(),
             evaluation: BoardEvaluation::Inconclusive,
-            last_move,
+            first_child: 0,
+            last_move: last_move.into(),
+            num_children: 0,
+            num_moves: MOVES_NOT_GENERATED,
             our_color,
             parent_index,
-            score: TreeNodeScore {
-                draws: 0,
-                wins_black: 0,
-                wins_white: 0,
-            },
-            self_index,
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::args::Args;
+    use crate::model::types::square_names::*;
+    use crate::move_generator::make_move::make_move;
+
     use super::*;
 
+    #[test]
+    fn it_configures_the_search_with_the_args() {
+        let mut tree = Tree::new(Board::new());
+        tree.configure(&Args {
+            expansion_visits: 3,
+            expansion_width: 4,
+            ..Args::default()
+        });
+        assert_eq!(tree.get_expansion_visits(), 3);
+        assert_eq!(tree.get_expansion_width(), 4);
+    }
+
     #[test]
     fn it_returns_no_parent_node_for_root() {
         let tree = Tree::new(Board::new());
@@ -170,13 +530,13 @@ mod test {
     fn it_returns_the_parent_node() {
         let mut tree = Tree::new(Board::new());
         tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
-        assert_eq!(tree.get_parent(1).unwrap().child_indices, vec![1]);
+        assert_eq!(tree.get_parent(1).unwrap().get_child_indices(), 1..2);
     }
 
     #[test]
     fn it_proves_that_root_has_no_siblings() {
         let tree = Tree::new(Board::new());
-        assert_eq!(tree.get_sibling_indices(TREE_NODE_ROOT_INDEX), vec![]);
+        assert!(tree.get_sibling_indices(TREE_NODE_ROOT_INDEX).is_empty());
     }
 
     #[test]
@@ -185,8 +545,146 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
         tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
         tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
-        assert_eq!(tree.get_sibling_indices(1), vec![1, 2, 3]);
-        assert_eq!(tree.get_sibling_indices(2), vec![1, 2, 3]);
-        assert_eq!(tree.get_sibling_indices(3), vec![1, 2, 3]);
+        assert_eq!(tree.get_sibling_indices(1), 1..4);
+        assert_eq!(tree.get_sibling_indices(2), 1..4);
+        assert_eq!(tree.get_sibling_indices(3), 1..4);
+    }
+
+    #[test]
+    #[should_panic(expected = "The children of node 0 are not contiguous.")]
+    fn it_panics_when_children_are_not_added_contiguously() {
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
+        tree.add_node(Board::new(), Move::from_to(0, 0), 1);
+        tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
+    }
+
+    #[test]
+    fn it_adds_the_reserved_children_in_order() {
+        let mut tree = Tree::new(Board::new());
+        assert!(!tree.get_root().is_fully_expanded());
+        assert_eq!(tree.get_next_untried_move(0), None);
+
+        let moves = [Move::from_to(E2, E4), Move::from_to(D2, D4)];
+        tree.set_untried_moves(0, &moves);
+        assert_eq!(tree.get_size(), 3);
+        assert_eq!(tree.get_untried_moves(0), moves);
+        assert!(!tree.get_root().has_children());
+
+        for (i, m) in moves.into_iter().enumerate() {
+            assert_eq!(tree.get_next_untried_move(0), Some(m));
+            let mut board = Board::new();
+            make_move(&mut board, &m);
+            let hash = board.get_hash();
+            tree.add_node(board, m, 0);
+            assert_eq!(tree.get_root().get_child_indices(), 1..i as u32 + 2);
+            assert_eq!(tree.get_node(i as u32 + 1).board_hash, hash);
+        }
+
+        assert_eq!(tree.get_size(), 3);
+        assert_eq!(tree.get_next_untried_move(0), None);
+        assert!(tree.get_root().is_fully_expanded());
+    }
+
+    #[test]
+    fn it_reserves_the_untried_moves_behind_the_children() {
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(Board::new(), Move::from_to(E2, E4), 0);
+        tree.set_untried_moves(0, &[Move::from_to(D2, D4)]);
+
+        assert_eq!(tree.get_root().get_child_indices(), 1..2);
+        assert_eq!(tree.get_root().get_untried_child_indices(), 2..3);
+        assert_eq!(tree.get_untried_moves(0), vec![Move::from_to(D2, D4)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Node 0 reserved a child for another move.")]
+    fn it_panics_when_a_child_is_not_added_for_the_reserved_move() {
+        let mut tree = Tree::new(Board::new());
+        tree.set_untried_moves(0, &[Move::from_to(E2, E4)]);
+        tree.add_node(Board::new(), Move::from_to(D2, D4), 0);
+    }
+
+    /// Root with the children 1 and 2, node 1 with the children 3 and 4,
+    /// node 2 with the child 5 and node 5 with the child 6. Every node has
+    /// a distinct number of white wins.
+    fn build_prunable_tree() -> Tree {
+        let mut tree = Tree::new(Board::new());
+        for parent_index in [0, 0, 1, 1, 2, 5] {
+            tree.add_node(Board::new(), Move::from_to(0, 0), parent_index);
+        }
+        for (i, visits) in [20, 2, 8, 1, 1, 7, 6].into_iter().enumerate() {
+            tree.get_score_mut(i as TreeNodeIndex).wins_white = visits;
+        }
+        tree
+    }
+
+    #[test]
+    fn it_prunes_the_least_visited_subtrees_and_compacts_the_nodes() {
+        let mut tree = build_prunable_tree();
+        tree.prune(5);
+
+        assert_eq!(tree.get_size(), 5);
+        assert_eq!(tree.get_prunings(), 1);
+        assert!(!tree.get_node(1).has_children());
+        assert_eq!(tree.get_node(0).get_child_indices(), 1..3);
+        assert_eq!(tree.get_node(2).get_child_indices(), 3..4);
+        assert_eq!(tree.get_node(3).parent_index, Some(2));
+        assert_eq!(tree.get_node(3).get_child_indices(), 4..5);
+        assert_eq!(tree.get_node(4).parent_index, Some(3));
+        let wins: Vec<u64> =
+            (0..5).map(|i| tree.get_score(i).wins_white).collect();
+        assert_eq!(wins, vec![20, 2, 8, 7, 6]);
+    }
+
+    #[test]
+    fn it_keeps_proven_evaluations_when_pruning() {
+        let mut tree = build_prunable_tree();
+        tree.get_node_mut(4).evaluation = BoardEvaluation::WinWhite;
+        tree.get_node_mut(2).evaluation = BoardEvaluation::Draw;
+        tree.prune(5);
+
+        assert_eq!(tree.get_size(), 5);
+        assert_eq!(tree.get_node(1).get_child_indices(), 3..5);
+        assert_eq!(tree.get_node(4).evaluation, BoardEvaluation::WinWhite);
+        assert!(!tree.get_node(2).has_children());
+        assert_eq!(tree.get_node(2).evaluation, BoardEvaluation::Draw);
+    }
+
+    #[test]
+    fn it_keeps_the_untried_moves_when_pruning() {
+        let mut tree = build_prunable_tree();
+        tree.set_untried_moves(6, &[Move::from_to(B2, B3)]);
+        tree.set_untried_moves(4, &[Move::from_to(A2, A3)]);
+        tree.prune(6);
+
+        assert_eq!(tree.get_size(), 6);
+        assert!(!tree.get_node(1).has_children());
+        assert!(!tree.get_node(1).has_untried_moves_generated());
+        assert_eq!(tree.get_untried_moves(4), vec![Move::from_to(B2, B3)]);
+        assert_eq!(tree.get_node(5).parent_index, Some(4));
+    }
+
+    #[test]
+    fn it_never_prunes_the_root() {
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
+        tree.add_node(Board::new(), Move::from_to(0, 0), TREE_NODE_ROOT_INDEX);
+        tree.prune(1);
+
+        assert_eq!(tree.get_size(), 3);
+        assert_eq!(tree.get_prunings(), 0);
+    }
+
+    #[test]
+    fn it_prunes_only_once_the_maximal_size_is_exceeded() {
+        let mut tree = build_prunable_tree();
+        tree.set_max_size(Some(7));
+        tree.prune_if_full();
+        assert_eq!(tree.get_size(), 7);
+
+        tree.set_max_size(Some(6));
+        tree.prune_if_full();
+        assert_eq!(tree.get_size(), 5);
     }
 }
@@ -0,0 +1,23 @@
+use super::args::Args;
+
+/// Restricts which nodes of a tree are dumped. The root is always dumped,
+/// a child only if it passes all filters and its parent was dumped too.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeDumpFilter {
+    /// Nodes with less visits are skipped.
+    pub min_visits: u64,
+    /// Nodes deeper than this are skipped, the root has depth 0.
+    pub max_depth: Option<usize>,
+    /// Only follow the principal variation starting at the root.
+    pub only_pv: bool,
+}
+
+impl From<&Args> for TreeDumpFilter {
+    fn from(args: &Args) -> Self {
+        Self {
+            min_visits: args.dump_min_visits,
+            max_depth: args.dump_max_depth,
+            only_pv: args.dump_only_pv,
+        }
+    }
+}
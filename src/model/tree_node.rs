@@ -1,29 +1,99 @@
+use std::ops::Range;
+
 use super::board_evaluation::BoardEvaluation;
 use super::color::Color;
+use super::compact_move::CompactMove;
 use super::r#move::Move;
 use super::types::TreeNodeIndex;
 
-#[derive(Debug)]
+/// A node of the search tree. The children of a node are allocated
+/// contiguously, so only the first of them and their count are stored. On
+/// the first expansion of a node a child is reserved for each of its moves,
+/// the reserved children only hold their move until they are added with
+/// `Tree::add_node`. The scores are kept apart in the tree, see
+/// `Tree::get_score`.
+#[derive(Clone, Debug)]
 pub struct TreeNode {
     pub board_hash: u64,
-    pub child_indices: Vec<TreeNodeIndex>,
     pub evaluation: BoardEvaluation,
-    pub last_move: Move,
+    pub first_child: TreeNodeIndex,
+    pub last_move: CompactMove,
+    pub num_children: u16,
+    /// Number of children including the reserved ones, `MOVES_NOT_GENERATED`
+    /// until the moves of the node are generated on its first expansion.
+    pub num_moves: u16,
     pub our_color: Color,
     pub parent_index: Option<TreeNodeIndex>,
-    pub score: TreeNodeScore,
-    pub self_index: TreeNodeIndex, // TODO: remove?
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+pub const MOVES_NOT_GENERATED: u16 = u16::MAX;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct TreeNodeScore {
     pub draws: u64,
     pub wins_black: u64,
     pub wins_white: u64,
 }
 
-impl TreeNode {
+impl TreeNodeScore {
+    pub fn get_visits(&self) -> u64 {
+        self.draws + self.wins_black + self.wins_white
+    }
+
     pub fn is_not_visited(&self) -> bool {
-        0 == self.score.draws + self.score.wins_black + self.score.wins_white
+        0 == self.get_visits()
+    }
+}
+
+impl TreeNode {
+    pub fn get_last_move(&self) -> Move {
+        self.last_move.into()
+    }
+
+    pub fn get_child_indices(&self) -> Range<TreeNodeIndex> {
+        self.first_child..self.first_child + self.num_children as TreeNodeIndex
+    }
+
+    /// The children reserved for the untried moves of the node.
+    pub fn get_untried_child_indices(&self) -> Range<TreeNodeIndex> {
+        let end = self.first_child + self.get_num_slots() as TreeNodeIndex;
+        self.get_child_indices().end..end
+    }
+
+    /// Number of children including the reserved ones.
+    pub fn get_num_slots(&self) -> u16 {
+        if self.has_untried_moves_generated() {
+            self.num_moves
+        } else {
+            self.num_children
+        }
+    }
+
+    pub fn has_children(&self) -> bool {
+        self.num_children > 0
+    }
+
+    /// Whether the moves of the node were generated on its first expansion.
+    pub fn has_untried_moves_generated(&self) -> bool {
+        self.num_moves != MOVES_NOT_GENERATED
+    }
+
+    /// Whether every legal move of the node has a child.
+    pub fn is_fully_expanded(&self) -> bool {
+        self.num_moves == self.num_children
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::size_of;
+
+    use super::*;
+
+    #[test]
+    fn it_keeps_tree_nodes_small() {
+        // a million nodes should stay below 60 MB including their scores
+        assert_eq!(size_of::<TreeNode>(), 32);
+        assert_eq!(size_of::<TreeNodeScore>(), 24);
     }
 }
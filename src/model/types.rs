@@ -1,9 +1,11 @@
 use self::square_names::*;
 // TODO: move to common
+/// The bare index of a square as stored in moves and lookup tables, see
+/// `Square` for checked arithmetic on squares.
 pub type SquareIndex = u8;
-pub type TreeNodeIndex = usize;
+pub type TreeNodeIndex = u32;
 
-pub const TREE_NODE_ROOT_INDEX: usize = 0;
+pub const TREE_NODE_ROOT_INDEX: TreeNodeIndex = 0;
 
 #[rustfmt::skip]
 pub const EN_PASSANT_CANDIDATES: [(Option<SquareIndex>, Option<SquareIndex>); 64] = [
@@ -0,0 +1,138 @@
+use clap::ValueEnum;
+
+use crate::bitboards::r#move::attack_maps::AttackMaps;
+use crate::variants::antichess;
+use crate::variants::atomic;
+use crate::variants::king_of_the_hill;
+use crate::variants::standard;
+use crate::variants::three_check;
+
+use super::board::Board;
+use super::board_evaluation::BoardEvaluation;
+use super::color::Color;
+use super::r#move::Move;
+use super::types::SquareIndex;
+
+/// The rule set a board is played with. Move generation and search are the
+/// same for every variant, only the rules below differ.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, ValueEnum)]
+pub enum Variant {
+    /// Captures explode the surrounding pieces, kings cannot capture.
+    Atomic,
+    /// Ordinary chess.
+    Standard,
+    /// Bringing the king to one of the four centre squares wins.
+    KingOfTheHill,
+    /// Giving check for the third time wins.
+    ThreeCheck,
+    /// Captures are compulsory and losing all pieces wins, the king is an
+    /// ordinary piece.
+    Antichess,
+}
+
+impl Variant {
+    /// Moves the capturing piece of `m` and removes whatever the capture
+    /// removes from the board. En passant pawns are removed by the caller.
+    pub fn resolve_capture(self, board: &mut Board, m: &Move) {
+        match self {
+            Variant::Atomic => atomic::resolve_capture(board, m),
+            Variant::Standard
+            | Variant::KingOfTheHill
+            | Variant::ThreeCheck
+            | Variant::Antichess => standard::resolve_capture(board, m),
+        }
+    }
+
+    pub fn can_king_capture(self) -> bool {
+        self != Variant::Atomic
+    }
+
+    pub fn can_castle(self) -> bool {
+        self != Variant::Antichess
+    }
+
+    /// Whether the king has to be protected from checks. Without a royal
+    /// king, kings can be captured and pawns can promote to kings.
+    pub fn has_royal_king(self) -> bool {
+        self != Variant::Antichess
+    }
+
+    /// Whether a capture has to be played if there is one.
+    pub fn forces_captures(self) -> bool {
+        self == Variant::Antichess
+    }
+
+    /// Whether pawns capturing on the last rank promote. In atomic chess the
+    /// capturing pawn explodes, so there is nothing left to promote.
+    pub fn promotes_on_capture(self) -> bool {
+        self != Variant::Atomic
+    }
+
+    /// Whether the checks given by each side are counted on the board.
+    pub fn counts_checks(self) -> bool {
+        self == Variant::ThreeCheck
+    }
+
+    /// Whether a king on `square` would be in check by the pieces of color
+    /// `by`, used for checks and the squares a king castles through.
+    pub fn is_square_attacked(
+        self,
+        maps: &AttackMaps,
+        square: SquareIndex,
+        by: Color,
+    ) -> bool {
+        match self {
+            Variant::Atomic => atomic::is_square_attacked(maps, square, by),
+            Variant::Standard
+            | Variant::KingOfTheHill
+            | Variant::ThreeCheck => {
+                standard::is_square_attacked(maps, square, by)
+            }
+            Variant::Antichess => false,
+        }
+    }
+
+    /// Evaluates the game ends the variant adds to checkmate, stalemate and
+    /// the draw rules, `None` if the variant has nothing to add.
+    pub fn evaluate_terminal(self, board: &Board) -> Option<BoardEvaluation> {
+        match self {
+            Variant::Atomic => atomic::evaluate_terminal(board),
+            Variant::Standard => standard::evaluate_terminal(board),
+            Variant::KingOfTheHill => {
+                king_of_the_hill::evaluate_terminal(board)
+            }
+            Variant::ThreeCheck => three_check::evaluate_terminal(board),
+            Variant::Antichess => antichess::evaluate_terminal(board),
+        }
+    }
+
+    /// Evaluates a position where the side to move has no legal move and is
+    /// not in check.
+    pub fn evaluate_stalemate(self, board: &Board) -> BoardEvaluation {
+        match self {
+            Variant::Antichess => antichess::evaluate_stalemate(board),
+            _ => BoardEvaluation::Draw,
+        }
+    }
+
+    pub fn is_insufficient_material(self, board: &Board) -> bool {
+        match self {
+            Variant::Atomic => atomic::is_insufficient_material(board),
+            Variant::Standard => standard::is_insufficient_material(board),
+            Variant::ThreeCheck => three_check::is_insufficient_material(board),
+            // a lone king can still walk to the hill or lose its last pieces
+            Variant::KingOfTheHill | Variant::Antichess => false,
+        }
+    }
+
+    /// The value of the PGN `Variant` tag.
+    pub fn get_pgn_name(self) -> &'static str {
+        match self {
+            Variant::Atomic => "Atomic",
+            Variant::Standard => "Standard",
+            Variant::KingOfTheHill => "King of the Hill",
+            Variant::ThreeCheck => "Three-check",
+            Variant::Antichess => "Antichess",
+        }
+    }
+}
@@ -1,33 +1,42 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
+use crate::model::square::Square;
 
 use super::sliding_moves::add_sliding_moves;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(10);
-
-    for from in &board.pieces.our_bishops {
-        let from_x = (from % 8) as i8;
-        let from_y = (from / 8) as i8;
+/// Passes the bishop moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    for from in board.get_our_pieces(PieceType::Bishop) {
+        let from = Square::from_index(from);
 
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, 1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, 1));
+        add_sliding_moves(board, visitor, from, (-1, -1))?;
+        add_sliding_moves(board, visitor, from, (1, -1))?;
+        add_sliding_moves(board, visitor, from, (-1, 1))?;
+        add_sliding_moves(board, visitor, from, (1, 1))?;
     }
 
-    moves
+    ControlFlow::Continue(())
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
 
     #[test]
     fn it_generates_bishop_moves_from_the_starting_position() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 0);
     }
 
@@ -35,7 +44,8 @@ mod test {
     fn it_generates_black_bishop_moves_on_an_empty_board() {
         let fen = "8/8/8/8/2b2b2/8/8/8 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 22);
         assert_eq!(
             moves,
@@ -70,7 +80,8 @@ mod test {
     fn it_generates_white_bishop_moves_on_an_empty_board() {
         let fen = "8/8/8/8/2B2B2/8/8/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 22);
         assert_eq!(
             moves,
@@ -105,7 +116,8 @@ mod test {
     fn it_generates_black_bishop_capture_moves_on_an_otherwise_empty_board() {
         let fen = "1N4N1/8/N6N/8/2b2b2/8/N6N/2N2N2 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 22);
         assert_eq!(
             moves,
@@ -140,7 +152,8 @@ mod test {
     fn it_generates_white_bishop_capture_moves_on_an_otherwise_empty_board() {
         let fen = "1n4n1/8/n6n/8/2B2B2/8/n6n/2n2n2 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 22);
         assert_eq!(
             moves,
@@ -175,7 +188,8 @@ mod test {
     fn it_generates_black_bishop_in_the_giuoco_piano() {
         let fen = "r1bq1rk1/pppp1ppp/2n2n2/2b1p3/2B1P3/2PP1N2/PP3PPP/RNBQ1RK1 b - - 0 6";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 8);
         assert_eq!(
             moves,
@@ -196,7 +210,8 @@ mod test {
     fn it_generates_white_bishop_in_the_giuoco_piano() {
         let fen = "r1bq1rk1/pppp1ppp/2n2n2/2b1p3/2B1P3/3P1N2/PPP2PPP/RNBQ1RK1 w - - 1 6";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 11);
         assert_eq!(
             moves,
@@ -1,70 +1,52 @@
+use crate::bitboards::r#move::attack_maps::AttackMaps;
 use crate::model::board::Board;
+use crate::model::color::Color;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
-use crate::model::r#move::MoveSpecial;
-use crate::model::types::SQUARES_TOUCH;
-use crate::move_generator::make_move::make_null_move;
-
-use super::legal_moves;
 
 pub fn is_check(board: &Board) -> bool {
-    let our_king_index = board.pieces.our_kings[0];
-    let mut board_clone = board.clone_without_piece_lists();
-
-    make_null_move(&mut board_clone);
-    let Some(their_king_index) = board_clone.pieces.our_kings.get(0) else {
-        // king might have exploded already
+    let Some(our_king_index) = board.get_our_pieces(PieceType::King).first()
+    else {
+        // there is no royal king in antichess
         return false;
     };
+    let maps = AttackMaps::from_board(board);
 
-    if SQUARES_TOUCH[our_king_index as usize][*their_king_index as usize] == 1 {
-        return false;
-    }
+    board.variant.is_square_attacked(
+        &maps,
+        our_king_index,
+        get_their_color(board),
+    )
+}
 
-    let moves = legal_moves::generate_moves_pseudo_legal_without_kings(
-        &mut board_clone,
-    );
-    for m in moves {
-        if m.to == our_king_index {
-            return true;
-        }
+pub fn is_legal_castling(board: &Board, m: &Move) -> bool {
+    if !m.is_castle() {
+        return false;
     }
 
-    false
-}
+    // all squares of the king's way including start and destination
+    let mut castling_checks_squares = m.from.min(m.to)..=m.from.max(m.to);
 
-pub fn is_legal_castling(board: &mut Board, m: &Move) -> bool {
-    let castling_checks_squares: [u8; 3] = match m.special {
-        Some(MoveSpecial::CastleLongBlack) => [58, 59, 60],
-        Some(MoveSpecial::CastleShortBlack) => [60, 61, 62],
-        Some(MoveSpecial::CastleLongWhite) => [2, 3, 4],
-        Some(MoveSpecial::CastleShortWhite) => [4, 5, 6],
-        _ => return false,
-    };
+    let maps = AttackMaps::from_board(board);
+    let their_color = get_their_color(board);
 
-    make_null_move(board);
-    let their_king_index = board.pieces.our_kings[0];
+    !castling_checks_squares
+        .any(|c| board.variant.is_square_attacked(&maps, c, their_color))
+}
 
-    let moves = legal_moves::generate_moves_pseudo_legal_without_kings(board);
-    for c in castling_checks_squares {
-        if SQUARES_TOUCH[their_king_index as usize][c as usize] == 1 {
-            // their king touches a castling square so it can't be attacked
-            continue;
-        }
-        for m in &moves {
-            if m.to == c {
-                // a relevant castling square is attacked
-                make_null_move(board);
-                return false;
-            }
-        }
+fn get_their_color(board: &Board) -> Color {
+    match board.our_color {
+        Color::Black => Color::White,
+        Color::White => Color::Black,
     }
-
-    make_null_move(board);
-    true
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::r#move::MoveSpecial;
+    use crate::model::types::square_names::*;
+    use crate::model::variant::Variant;
+
     use super::*;
 
     mod is_check {
@@ -111,6 +93,14 @@ mod test {
             assert!(is_check(&board));
         }
 
+        #[test]
+        fn it_ignores_checks_while_the_kings_touch_only_in_atomic_chess() {
+            let fen = "8/8/8/8/8/3qk3/4K3/8 w - - 0 1";
+            let standard = Board::from_fen_with_variant(fen, Variant::Standard);
+            assert!(!is_check(&Board::from_fen(fen)));
+            assert!(is_check(&standard));
+        }
+
         #[test]
         fn it_proves_the_board_is_not_changed_after_check_evaluation() {
             let fen =
@@ -125,34 +115,176 @@ mod test {
         use super::*;
         #[test]
         fn it_allows_black_castling_short() {
-            let mut board = Board::from_fen(
+            let board = Board::from_fen(
                 "r3k2r/8/1K6/2R1b3/4p1p1/BPP2p2/P2P1PP1/8 b kq - 29 36",
             );
-            assert!(is_legal_castling(&mut board, &Move::castle_short_black()));
+            assert!(is_legal_castling(&board, &Move::castle_short_black()));
         }
 
         #[test]
         fn it_disallows_black_castling_long() {
-            let mut board = Board::from_fen(
+            let board = Board::from_fen(
                 "r3k2r/8/1K6/2R1b3/4p1p1/BPP2p2/P2P1PP1/8 b kq - 29 36",
             );
-            assert!(!is_legal_castling(&mut board, &Move::castle_long_black()));
+            assert!(!is_legal_castling(&board, &Move::castle_long_black()));
         }
 
         #[test]
         fn it_allows_black_castling_long_because_of_their_king_touching() {
-            let mut board = Board::from_fen(
+            let board = Board::from_fen(
                 "r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35",
             );
-            assert!(is_legal_castling(&mut board, &Move::castle_long_black()));
+            assert!(is_legal_castling(&board, &Move::castle_long_black()));
+        }
+
+        #[test]
+        fn it_disallows_white_castling_long_because_a_pawn_attacks_an_empty_square(
+        ) {
+            // the pawn cannot capture on c1 now, but it attacks the square
+            let board = Board::from_fen("4k3/8/8/8/8/8/1p6/R3K3 w Q - 0 1");
+            assert!(!is_legal_castling(&board, &Move::castle_long_white()));
         }
 
         #[test]
         fn it_is_not_a_legal_castling_if_no_move_special_is_set() {
-            let mut board = Board::from_fen(
+            let board = Board::from_fen(
                 "r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35",
             );
-            assert!(!is_legal_castling(&mut board, &Move::from_to(0, 0)));
+            assert!(!is_legal_castling(&board, &Move::from_to(0, 0)));
+        }
+
+        #[test]
+        fn it_disallows_chess960_castling_through_an_attacked_square() {
+            let board = Board::from_fen("4r1k1/8/8/8/8/8/8/1K5R w H - 0 1");
+            let m = Move::castle(MoveSpecial::CastleShortWhite, B1);
+            assert!(!is_legal_castling(&board, &m));
+        }
+
+        #[test]
+        fn it_allows_chess960_castling_with_an_attacked_rook() {
+            let board = Board::from_fen("4k2r/8/8/8/8/8/8/1K5R w H - 0 1");
+            let m = Move::castle(MoveSpecial::CastleShortWhite, B1);
+            assert!(is_legal_castling(&board, &m));
+        }
+    }
+
+    /// Compares the bitboard implementation with the former mailbox one,
+    /// which generates the pseudo legal moves of the opponent after a null
+    /// move.
+    mod cross_check {
+        use crate::bitboards::r#move::attacks::KING_PSEUDO_ATTACKS;
+        use crate::model::move_list::MoveList;
+        use crate::model::r#move::MoveSpecial;
+        use crate::move_generator::king_moves;
+        use crate::move_generator::legal_moves::generate_moves;
+        use crate::move_generator::legal_moves::generate_moves_pseudo_legal;
+        use crate::move_generator::make_move::make_move;
+        use crate::move_generator::make_move::make_null_move;
+
+        use super::*;
+
+        fn get_attacked_squares_mailbox(board: &Board) -> Vec<u8> {
+            let mut board = board.clone();
+            make_null_move(&mut board);
+
+            generate_moves_pseudo_legal(&mut board)
+                .into_iter()
+                .filter(|m| !board.has_king_at(m.from))
+                .map(|m| m.to)
+                .collect()
+        }
+
+        fn is_check_mailbox(board: &Board) -> bool {
+            let our_king_index =
+                board.get_our_pieces(PieceType::King).first().unwrap();
+            let mut their_board = board.clone();
+            make_null_move(&mut their_board);
+            let Some(their_king_index) =
+                their_board.get_our_pieces(PieceType::King).first()
+            else {
+                return false;
+            };
+
+            if KING_PSEUDO_ATTACKS[our_king_index as usize]
+                .contains(their_king_index)
+            {
+                return false;
+            }
+
+            get_attacked_squares_mailbox(board).contains(&our_king_index)
+        }
+
+        fn is_legal_castling_mailbox(board: &Board, m: &Move) -> bool {
+            let squares: [u8; 3] = match m.special {
+                Some(MoveSpecial::CastleLongBlack) => [C8, D8, E8],
+                Some(MoveSpecial::CastleShortBlack) => [E8, F8, G8],
+                Some(MoveSpecial::CastleLongWhite) => [C1, D1, E1],
+                Some(MoveSpecial::CastleShortWhite) => [E1, F1, G1],
+                _ => return false,
+            };
+            let mut their_board = board.clone();
+            make_null_move(&mut their_board);
+            let their_king_index =
+                their_board.get_our_pieces(PieceType::King).first().unwrap();
+            let attacked_squares = get_attacked_squares_mailbox(board);
+
+            squares.iter().all(|c| {
+                KING_PSEUDO_ATTACKS[*c as usize].contains(their_king_index)
+                    || !attacked_squares.contains(c)
+            })
+        }
+
+        fn cross_check_positions(fen: &str, depth: u32) -> u64 {
+            let board = Board::from_fen(fen);
+            let mut checked = 0;
+
+            if board.get_our_pieces(PieceType::King).is_empty() {
+                return checked;
+            }
+
+            assert_eq!(is_check(&board), is_check_mailbox(&board), "{fen}");
+            checked += 1;
+
+            let mut moves = MoveList::new();
+            let _ = king_moves::visit(&board, &mut moves.visitor());
+            for m in &moves {
+                if m.is_castle() {
+                    assert_eq!(
+                        is_legal_castling(&board, m),
+                        is_legal_castling_mailbox(&board, m),
+                        "{m} in {fen}"
+                    );
+                }
+            }
+
+            if depth == 0 {
+                return checked;
+            }
+
+            for m in &generate_moves(&mut board.clone()) {
+                let mut next_board = board.clone();
+                make_move(&mut next_board, m);
+                checked +=
+                    cross_check_positions(&next_board.to_fen(), depth - 1);
+            }
+
+            checked
+        }
+
+        #[test]
+        fn it_agrees_with_the_mailbox_implementation_in_reachable_positions() {
+            let positions = [
+                ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 3),
+                ("r3k2r/8/1K6/2R1b3/4p1p1/BPP2p2/P2P1PP1/8 b kq - 29 36", 2),
+                ("r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35", 2),
+                ("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", 2),
+                ("3R1r1r/p1k2P2/7p/1pp1B1p1/4P3/3p2P1/P2P3P/R5K1 b - - 1 25", 2),
+                ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 3),
+            ];
+
+            for (fen, depth) in positions {
+                assert!(cross_check_positions(fen, depth) > 0);
+            }
         }
     }
 }
@@ -1,84 +1,140 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
 use crate::model::color::Color;
+use crate::model::file::File;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
+use crate::model::r#move::MoveSpecial;
+use crate::model::rank::Rank;
+use crate::model::square::Square;
 use crate::model::types::SquareIndex;
 use crate::model::types::SQUARE_NEIGHBORHOODS;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(8);
-
-    for from in &board.pieces.our_kings {
-        for to in SQUARE_NEIGHBORHOODS[*from as usize] {
+/// Passes the king moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    for from in board.get_our_pieces(PieceType::King) {
+        for to in SQUARE_NEIGHBORHOODS[from as usize] {
             let Some(to) = to else {
                 break;
             };
-            add_move(board, &mut moves, *from, to);
+            add_move(board, visitor, from, to)?;
         }
 
-        add_castles(board, &mut moves);
+        add_castles(board, visitor, from)?;
     }
 
-    moves
+    ControlFlow::Continue(())
 }
 
-fn add_move(
+fn add_move<F: FnMut(Move) -> ControlFlow<()>>(
     board: &Board,
-    moves: &mut Vec<Move>,
+    visitor: &mut F,
     from: SquareIndex,
     to: SquareIndex,
-) {
-    if board.is_empty_at(to) {
-        moves.push(Move::from_to(from, to));
+) -> ControlFlow<()> {
+    let is_capture =
+        board.variant.can_king_capture() && board.has_their_color_at(to);
+
+    if board.is_empty_at(to) || is_capture {
+        visitor(Move::from_to(from, to))?;
     }
+
+    ControlFlow::Continue(())
 }
 
-fn add_castles(board: &Board, moves: &mut Vec<Move>) {
-    match board.our_color {
+/// The squares the castling rook of `m` moves from and to.
+pub fn get_castle_rook_squares(
+    board: &Board,
+    m: &Move,
+) -> (SquareIndex, SquareIndex) {
+    let special = m.special.expect("Move should be castling");
+    let rook_file = board
+        .castle
+        .get_rook_file(special)
+        .expect("Castling needs a castling right");
+    let rank = Square::from_index(m.from).rank();
+    let rook_to = match Square::from_index(m.to).file() {
+        File::C => File::D,
+        _ => File::F,
+    };
+
+    (
+        Square::from_file_rank(rook_file, rank).index(),
+        Square::from_file_rank(rook_to, rank).index(),
+    )
+}
+
+fn add_castles<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+    king: SquareIndex,
+) -> ControlFlow<()> {
+    let specials = match board.our_color {
         Color::Black => {
-            if board.castle.black_long
-                && board.pieces.squares.data[57] == None
-                && board.pieces.squares.data[58] == None
-                && board.pieces.squares.data[59] == None
-            {
-                moves.push(Move::castle_long_black());
-            }
-
-            if board.castle.black_short
-                && board.pieces.squares.data[61] == None
-                && board.pieces.squares.data[62] == None
-            {
-                moves.push(Move::castle_short_black());
-            }
+            [MoveSpecial::CastleLongBlack, MoveSpecial::CastleShortBlack]
         }
         Color::White => {
-            if board.castle.white_long
-                && board.pieces.squares.data[1] == None
-                && board.pieces.squares.data[2] == None
-                && board.pieces.squares.data[3] == None
-            {
-                moves.push(Move::castle_long_white());
-            }
-
-            if board.castle.white_short
-                && board.pieces.squares.data[5] == None
-                && board.pieces.squares.data[6] == None
-            {
-                moves.push(Move::castle_short_white());
-            }
+            [MoveSpecial::CastleLongWhite, MoveSpecial::CastleShortWhite]
         }
+    };
+
+    if Square::from_index(king).rank() != Rank::back_rank(board.our_color) {
+        return ControlFlow::Continue(());
     }
+
+    for special in specials {
+        if board.castle.get_rook_file(special).is_none() {
+            continue;
+        }
+
+        let m = Move::castle(special, king);
+        let (rook_from, rook_to) = get_castle_rook_squares(board, &m);
+
+        let is_our_rook = board.pieces.squares.data[rook_from as usize]
+            .is_some_and(|p| p.is_rook_of_color(board.our_color));
+        if is_our_rook && is_castling_path_empty(board, &m, rook_from, rook_to)
+        {
+            visitor(m)?;
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+/// All squares between (and including) the start and end squares of king and
+/// rook have to be empty, except for the king and the rook themselves.
+fn is_castling_path_empty(
+    board: &Board,
+    m: &Move,
+    rook_from: SquareIndex,
+    rook_to: SquareIndex,
+) -> bool {
+    let first = m.from.min(m.to).min(rook_from).min(rook_to);
+    let last = m.from.max(m.to).max(rook_from).max(rook_to);
+
+    (first..=last)
+        .all(|s| s == m.from || s == rook_from || board.is_empty_at(s))
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
     use crate::model::types::square_names::*;
+    use crate::model::variant::Variant;
 
     #[test]
     fn it_generates_no_king_moves_from_the_starting_position() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 0);
     }
 
@@ -86,7 +142,8 @@ mod test {
     fn it_generates_one_move_almost_surrounded_by_queens() {
         let fen = "8/8/8/8/8/QQQ5/QKQ5/1QQ5 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 1);
         assert_eq!(moves[0], Move::from_to(9, 0));
     }
@@ -95,7 +152,8 @@ mod test {
     fn it_generates_8_moves_when_alone() {
         let fen = "8/8/8/8/8/8/1K6/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 8);
         assert_eq!(
             moves,
@@ -116,7 +174,8 @@ mod test {
     fn it_generates_zero_moves_when_surrounded_by_enemies() {
         let fen = "8/8/8/8/8/qqq5/qKq5/qqq5 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 0);
     }
 
@@ -124,7 +183,8 @@ mod test {
     fn it_generates_one_move_when_one_neighborhood_square_empty() {
         let fen = "8/8/8/8/8/qq6/qKq5/qqq5 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 1);
         assert!(moves.contains(&Move::from_to(B2, C3)));
     }
@@ -133,7 +193,8 @@ mod test {
     fn it_generates_3_moves_when_in_a_corner() {
         let fen = "8/8/8/8/8/8/8/K7 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 3);
         assert_eq!(
             moves,
@@ -149,7 +210,8 @@ mod test {
     fn it_generates_moves_in_a_complex_position() {
         let fen = "8/8/8/6b1/6Nk/6P1/8/8 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 2);
         assert!(moves.contains(&Move::from_to(H4, H3)));
         assert!(moves.contains(&Move::from_to(H4, H5)));
@@ -160,7 +222,8 @@ mod test {
         let fen =
             "rnbqkb1r/pp1pp1pp/2p2p1n/1B6/8/4PN2/PPPP1PPP/RNBQK2R w KQkq - 0 4";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 3);
         assert_eq!(
             moves,
@@ -177,7 +240,8 @@ mod test {
         let fen =
             "rnb1k2r/pp4pp/1qpp1p1n/1Bb1p3/8/BPN1PN2/P1PPQPPP/R3K2R w KQkq - 0 8";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 4);
         assert_eq!(
             moves,
@@ -195,7 +259,8 @@ mod test {
         let fen =
             "rn2k2r/pp4pp/1qppbp1n/1Bb1p3/8/BPN1PN2/P1PPQPPP/2KR3R w kq - 2 9";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 2);
         assert_eq!(moves, vec![Move::from_to(2, 1), Move::from_to(2, 9),]);
     }
@@ -204,7 +269,8 @@ mod test {
     fn it_generates_both_black_castles() {
         let fen = "r3k2r/pp4pp/3p1p1n/8/8/BPN1P3/P1PPQPPP/2KR3R b kq - 0 10";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 7);
         assert_eq!(
             moves,
@@ -219,4 +285,56 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn it_generates_chess960_castles_with_the_king_next_to_the_rook() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/RK5R w HA - 0 1");
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
+        assert!(moves.contains(&Move::castle(MoveSpecial::CastleLongWhite, B1)));
+        assert!(
+            moves.contains(&Move::castle(MoveSpecial::CastleShortWhite, B1))
+        );
+        assert_eq!(
+            get_castle_rook_squares(
+                &board,
+                &Move::castle(MoveSpecial::CastleLongWhite, B1)
+            ),
+            (A1, D1)
+        );
+    }
+
+    #[test]
+    fn it_generates_chess960_castles_where_the_king_does_not_move() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/6KR w H - 0 1");
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
+        assert!(
+            moves.contains(&Move::castle(MoveSpecial::CastleShortWhite, G1))
+        );
+        assert_eq!(Move::castle(MoveSpecial::CastleShortWhite, G1).to, G1);
+    }
+
+    #[test]
+    fn it_generates_no_chess960_castles_when_the_rook_path_is_blocked() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/1RKB4 w B - 0 1");
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
+        assert!(moves.iter().all(|m| !m.is_castle()));
+    }
+
+    #[test]
+    fn it_generates_king_captures_in_standard_chess() {
+        let fen = "4k3/8/8/8/8/8/3p4/4K3 w - - 0 1";
+        let atomic = Board::from_fen(fen);
+        let standard = Board::from_fen_with_variant(fen, Variant::Standard);
+
+        let mut moves = MoveList::new();
+        let _ = visit(&atomic, &mut moves.visitor());
+        assert!(!moves.contains(&Move::from_to(E1, D2)));
+
+        moves.clear();
+        let _ = visit(&standard, &mut moves.visitor());
+        assert!(moves.contains(&Move::from_to(E1, D2)));
+    }
 }
@@ -1,56 +1,67 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
-use crate::model::types::SquareIndex;
+use crate::model::square::Square;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(10);
+/// The file and rank offsets of the knight jumps.
+const KNIGHT_JUMPS: [(i8, i8); 8] = [
+    (-1, -2),
+    (1, -2),
+    (-2, -1),
+    (2, -1),
+    (-2, 1),
+    (2, 1),
+    (-1, 2),
+    (1, 2),
+];
 
-    for from in &board.pieces.our_knights {
-        let from_x = (from % 8) as i8;
-        let from_y = (from / 8) as i8;
+/// Passes the knight moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    for from in board.get_our_pieces(PieceType::Knight) {
+        let from = Square::from_index(from);
 
-        add_move(board, &mut moves, *from, from_x - 1, from_y - 2);
-        add_move(board, &mut moves, *from, from_x + 1, from_y - 2);
-        add_move(board, &mut moves, *from, from_x - 2, from_y - 1);
-        add_move(board, &mut moves, *from, from_x + 2, from_y - 1);
-        add_move(board, &mut moves, *from, from_x - 2, from_y + 1);
-        add_move(board, &mut moves, *from, from_x + 2, from_y + 1);
-        add_move(board, &mut moves, *from, from_x - 1, from_y + 2);
-        add_move(board, &mut moves, *from, from_x + 1, from_y + 2);
+        for (files, ranks) in KNIGHT_JUMPS {
+            if let Some(to) = from.offset(files, ranks) {
+                add_move(board, visitor, from, to)?;
+            }
+        }
     }
 
-    moves
+    ControlFlow::Continue(())
 }
 
-fn add_move(
+fn add_move<F: FnMut(Move) -> ControlFlow<()>>(
     board: &Board,
-    moves: &mut Vec<Move>,
-    from: SquareIndex,
-    to_x: i8,
-    to_y: i8,
-) {
-    if to_x < 0 || to_x > 7 || to_y < 0 || to_y > 7 {
-        return;
-    }
-
-    let to = (8 * to_y + to_x) as u8;
-    if let Some(piece) = board.pieces.squares.data[to as usize] {
+    visitor: &mut F,
+    from: Square,
+    to: Square,
+) -> ControlFlow<()> {
+    if let Some(piece) = board.pieces.squares.data[to.index() as usize] {
         if piece.get_color() == board.our_color {
-            return;
+            return ControlFlow::Continue(());
         }
     }
 
-    moves.push(Move::from_to(from, to));
+    visitor(Move::from_to(from.index(), to.index()))
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
 
     #[test]
     fn it_generates_knight_moves_from_the_starting_position() {
         let board = Board::new();
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 4);
         assert_eq!(
             moves,
@@ -67,7 +78,8 @@ mod test {
     fn it_generates_knight_moves_with_4_black_knights() {
         let fen = "rnbqkbnr/pppppppp/8/8/2n5/n7/PPPPPPPP/RNBQKBNR b KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 14);
         assert_eq!(
             moves,
@@ -1,6 +1,12 @@
+use std::ops::ControlFlow;
+
 use crate::common::random::Random;
 use crate::model::board::Board;
+use crate::model::move_list::MoveList;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
+use crate::view::format_uci::format_king_takes_rook;
+use crate::view::format_uci::format_uci;
 
 use super::bishop_moves;
 use super::check::is_check;
@@ -12,78 +18,133 @@ use super::pawn_moves;
 use super::queen_moves;
 use super::rook_moves;
 
-// TODO: provide function which stops move generation for the first legal move
+pub fn generate_moves_pseudo_legal(board: &mut Board) -> MoveList {
+    let mut moves = MoveList::new();
+    let _ = visit_moves_pseudo_legal(board, &mut moves.visitor());
+    moves
+}
 
-pub fn generate_moves_pseudo_legal_without_kings(
-    board: &mut Board,
-) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(40);
+/// Number of uniformly random picks `generate_move` tries before it samples
+/// among all legal moves.
+const RANDOM_MOVE_PICKS: u32 = 4;
+
+/// Passes the pseudo legal moves one by one to the visitor, in the same order
+/// as `generate_moves_pseudo_legal` but without collecting them.
+pub fn visit_moves_pseudo_legal<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    bishop_moves::visit(board, visitor)?;
+    king_moves::visit(board, visitor)?;
+    knight_moves::visit(board, visitor)?;
+    pawn_moves::visit(board, visitor)?;
+    queen_moves::visit(board, visitor)?;
+    rook_moves::visit(board, visitor)
+}
 
-    moves.append(&mut bishop_moves::generate(board));
-    moves.append(&mut knight_moves::generate(board));
-    moves.append(&mut pawn_moves::generate(board));
-    moves.append(&mut queen_moves::generate(board));
-    moves.append(&mut rook_moves::generate(board));
+/// Generates a random legal move, every legal move is equally likely. The
+/// pseudo legal moves are generated once into a move list on the stack and
+/// picked at random until one is legal, so usually only a single move is
+/// checked for legality. If a few picks are all illegal, one of the legal
+/// moves is reservoir sampled, which also detects positions without legal
+/// moves. Both steps pick uniformly among the legal moves.
+pub fn generate_move(board: &mut Board, random: &mut Random) -> Option<Move> {
+    if board.variant.forces_captures() {
+        // without a capture all legal moves are quiet ones, so the search
+        // below does not need to know about forced captures
+        if let Some(capture) = generate_capture(board, random) {
+            return Some(capture);
+        }
+    }
 
-    moves
+    let moves = generate_moves_pseudo_legal(board);
+    if moves.is_empty() {
+        return None;
+    }
+
+    for _ in 0..RANDOM_MOVE_PICKS {
+        let m = moves[random.next_range(0..moves.len() as u32) as usize];
+        if is_legal(board, &m) {
+            return Some(m);
+        }
+    }
+
+    let mut legal_move = None;
+    for (i, m) in moves.iter().filter(|m| is_legal(board, m)).enumerate() {
+        if random.next_range(0..i as u32 + 1) == 0 {
+            legal_move = Some(*m);
+        }
+    }
+
+    legal_move
 }
 
-pub fn generate_moves_pseudo_legal(board: &mut Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(40);
+/// Generates a random legal capture (en passant included) by reservoir
+/// sampling, so every legal capture is equally likely and no move list is
+/// needed.
+pub fn generate_capture(board: &Board, random: &mut Random) -> Option<Move> {
+    let mut capture = None;
+    let mut count = 0;
 
-    moves.append(&mut bishop_moves::generate(board));
-    moves.append(&mut king_moves::generate(board));
-    moves.append(&mut knight_moves::generate(board));
-    moves.append(&mut pawn_moves::generate(board));
-    moves.append(&mut queen_moves::generate(board));
-    moves.append(&mut rook_moves::generate(board));
+    let _ = visit_moves_pseudo_legal(board, &mut |m| {
+        if is_capture(board, &m) && is_legal(board, &m) {
+            count += 1;
+            if random.next_range(0..count) == 0 {
+                capture = Some(m);
+            }
+        }
+        ControlFlow::Continue(())
+    });
 
-    moves
+    capture
 }
 
-pub fn generate_move(board: &mut Board, random: &mut Random) -> Option<Move> {
+pub fn generate_moves(board: &mut Board) -> MoveList {
     let mut moves = generate_moves_pseudo_legal(board);
-    random.shuffle(&mut moves);
+    moves.retain(|m| is_legal(board, m));
 
-    for i in 0..moves.len() {
-        let m = moves[i];
+    if board.variant.forces_captures()
+        && moves.iter().any(|m| is_capture(board, m))
+    {
+        moves.retain(|m| is_capture(board, m));
+    }
 
-        if is_king_capture(board, &m) {
-            continue;
-        }
+    moves
+}
 
-        if is_illegal_castle(board, &m) {
-            continue;
+/// Finds the legal move given in UCI notation, e.g. "e2e4" or "b7b8q".
+/// Castling is accepted as the king taking its own rook, e.g. "e1h1", and
+/// outside of Chess960 also by the king's destination, e.g. "e1g1".
+pub fn find_legal_move(board: &mut Board, uci: &str) -> Option<Move> {
+    generate_moves(board).iter().copied().find(|m| {
+        if m.is_castle() && format_king_takes_rook(board, m) == uci {
+            return true;
         }
+        format_uci(board, m) == uci
+    })
+}
 
-        if leaves_king_in_check(board, &m) {
-            continue;
-        }
+fn is_legal(board: &Board, m: &Move) -> bool {
+    if is_king_capture(board, m) {
+        return false;
+    }
 
-        return Some(m);
+    if is_illegal_castle(board, m) {
+        return false;
     }
 
-    None
+    !leaves_king_in_check(board, m)
 }
 
-pub fn generate_moves(board: &mut Board) -> Vec<Move> {
-    let moves = generate_moves_pseudo_legal(board);
-
-    moves
-        .into_iter()
-        .filter(|m| {
-            true && !is_king_capture(board, m)
-                && !is_illegal_castle(board, m)
-                && !leaves_king_in_check(board, m)
-        })
-        .collect()
+fn is_capture(board: &Board, m: &Move) -> bool {
+    m.is_en_passant() || board.has_their_color_at(m.to)
 }
 
 fn is_king_capture(board: &Board, m: &Move) -> bool {
-    board.has_king_at(m.to)
+    board.variant.has_royal_king() && board.has_king_at(m.to)
 }
 
-fn is_illegal_castle(board: &mut Board, m: &Move) -> bool {
+fn is_illegal_castle(board: &Board, m: &Move) -> bool {
     if !m.is_castle() {
         return false;
     }
@@ -92,11 +153,15 @@ fn is_illegal_castle(board: &mut Board, m: &Move) -> bool {
 }
 
 fn leaves_king_in_check(board: &Board, m: &Move) -> bool {
+    if !board.variant.has_royal_king() {
+        return false;
+    }
+
     let mut board_clone = board.clone();
 
     move_piece(&mut board_clone, m);
 
-    if board_clone.pieces.our_kings.is_empty() {
+    if board_clone.get_our_pieces(PieceType::King).is_empty() {
         // our king is no more, so all moves are illegal or "leave the king in check"
         return true;
     }
@@ -106,6 +171,8 @@ fn leaves_king_in_check(board: &Board, m: &Move) -> bool {
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MAX_MOVES;
+    use crate::model::r#move::MoveSpecial;
     use crate::model::types::square_names::*;
 
     use super::*;
@@ -139,6 +206,44 @@ mod test {
         assert!(moves.contains(&Move::from_to(15, 31)));
     }
 
+    #[test]
+    fn it_finds_legal_moves_given_in_uci_notation() {
+        let mut board = Board::from_fen(
+            "r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35",
+        );
+
+        assert_eq!(
+            find_legal_move(&mut board, "e8c8"),
+            Some(Move::castle_long_black())
+        );
+        assert_eq!(
+            find_legal_move(&mut board, "d4c3"),
+            Some(Move::from_to(D4, C3))
+        );
+        assert_eq!(find_legal_move(&mut board, "e8d8x"), None);
+        assert_eq!(find_legal_move(&mut board, "a1a2"), None);
+    }
+
+    #[test]
+    fn it_finds_castles_given_as_the_king_taking_its_rook() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+        assert_eq!(
+            find_legal_move(&mut board, "e1h1"),
+            Some(Move::castle_short_white())
+        );
+        assert_eq!(
+            find_legal_move(&mut board, "e1g1"),
+            Some(Move::castle_short_white())
+        );
+
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/1K5R w H - 0 1");
+        assert_eq!(
+            find_legal_move(&mut board, "b1h1"),
+            Some(Move::castle(MoveSpecial::CastleShortWhite, B1))
+        );
+        assert_eq!(find_legal_move(&mut board, "b1g1"), None);
+    }
+
     #[test]
     fn it_generates_all_legal_moves_from_a_position_with_complex_castling() {
         let fen = "r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35";
@@ -192,6 +297,66 @@ mod test {
         assert!(moves.contains(&Move::from_to(G4, G3)));
     }
 
+    #[test]
+    fn it_stops_visiting_pseudo_legal_moves_when_the_visitor_breaks() {
+        let mut board = Board::new();
+        let mut visited = Vec::new();
+
+        let result = visit_moves_pseudo_legal(&board, &mut |m| {
+            visited.push(m);
+            if visited.len() == 3 {
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(result, ControlFlow::Break(()));
+        assert_eq!(visited, generate_moves_pseudo_legal(&mut board)[..3]);
+    }
+
+    #[test]
+    fn it_generates_random_moves_which_are_all_legal_and_cover_every_legal_move(
+    ) {
+        let fen = "r3k2r/1K6/8/2R5/3bp1p1/BPP2p2/P2P1PP1/8 b kq - 27 35";
+        let mut board = Board::from_fen(fen);
+        let legal_moves = generate_moves(&mut board);
+        let mut random = Random::from_seed(3);
+        let mut generated = Vec::new();
+
+        for _ in 0..1000 {
+            let m = generate_move(&mut board, &mut random).unwrap();
+            assert!(legal_moves.contains(&m));
+            if !generated.contains(&m) {
+                generated.push(m);
+            }
+        }
+
+        assert_eq!(generated.len(), legal_moves.len());
+    }
+
+    #[test]
+    fn it_generates_random_captures_which_are_all_legal() {
+        let fen = "2bk3r/8/8/8/8/7Q/8/7K w - - 0 1";
+        let mut board = Board::from_fen(fen);
+        let mut random = Random::from_seed(5);
+        let mut generated = Vec::new();
+
+        for _ in 0..100 {
+            let m = generate_capture(&board, &mut random).unwrap();
+            if !generated.contains(&m) {
+                generated.push(m);
+            }
+        }
+        generated.sort_by_key(|m| m.to);
+
+        assert_eq!(
+            generated,
+            vec![Move::from_to(H3, C8), Move::from_to(H3, H8)]
+        );
+        assert!(generate_moves(&mut board).len() > generated.len());
+        assert_eq!(generate_capture(&Board::new(), &mut random), None);
+    }
+
     mod no_moves {
         use super::*;
 
@@ -312,12 +477,31 @@ mod test {
 
             assert_eq!(generate_move(&mut board, &mut random), move0);
             assert_eq!(generate_move(&mut board, &mut random), move1);
+            assert_eq!(generate_move(&mut board, &mut random), move2);
             assert_eq!(generate_move(&mut board, &mut random), move1);
             assert_eq!(generate_move(&mut board, &mut random), move0);
+            assert_eq!(generate_move(&mut board, &mut random), move2);
             assert_eq!(generate_move(&mut board, &mut random), move0);
             assert_eq!(generate_move(&mut board, &mut random), move1);
-            assert_eq!(generate_move(&mut board, &mut random), move2);
-            assert_eq!(generate_move(&mut board, &mut random), move2);
+        }
+
+        #[test]
+        fn it_generates_every_legal_move_equally_often_although_the_pinned_knight_has_more_pseudo_legal_moves(
+        ) {
+            let fen = "2BK3R/8/8/8/8/7n/8/7k b - - 0 1";
+            let mut board = Board::from_fen(fen);
+            let mut random = Random::from_seed(777);
+            let moves = generate_moves(&mut board);
+            let mut counts = vec![0; moves.len()];
+
+            for _ in 0..3000 {
+                let m = generate_move(&mut board, &mut random).unwrap();
+                counts[moves.iter().position(|l| *l == m).unwrap()] += 1;
+            }
+
+            for count in counts {
+                assert!((920..1080).contains(&count), "{count}");
+            }
         }
 
         #[test]
@@ -635,4 +819,49 @@ mod test {
             assert!(moves.contains(&Move::from_to(H1, F1)));
         }
     }
+
+    mod antichess {
+        use super::*;
+        use crate::model::variant::Variant;
+
+        #[test]
+        fn it_generates_only_captures_if_there_is_one() {
+            let mut board = Board::from_fen_with_variant(
+                "4k3/8/8/8/8/3p4/4P3/4K3 w - - 0 1",
+                Variant::Antichess,
+            );
+            assert_eq!(generate_moves(&mut board), vec![Move::from_to(E2, D3)]);
+
+            let mut random = Random::from_seed(1);
+            for _ in 0..10 {
+                assert_eq!(
+                    generate_move(&mut board, &mut random),
+                    Some(Move::from_to(E2, D3))
+                );
+            }
+        }
+
+        #[test]
+        fn it_generates_king_captures_and_captures_of_kings() {
+            let mut board = Board::from_fen_with_variant(
+                "8/8/8/8/8/8/3k4/4K3 w - - 0 1",
+                Variant::Antichess,
+            );
+            assert_eq!(generate_moves(&mut board), vec![Move::from_to(E1, D2)]);
+        }
+
+        #[test]
+        fn it_generates_more_moves_than_fit_on_the_stack() {
+            let mut board = Board::from_fen_with_variant(
+                "1q1q1q1q/PQPQPQPQ/Q6Q/Q1Q1Q2Q/Q6Q/Q6Q/Q6Q/QQQQQQQQ w - - 0 1",
+                Variant::Antichess,
+            );
+            let moves = generate_moves_pseudo_legal(&mut board);
+
+            assert!(moves.len() > MAX_MOVES);
+            for (i, m) in moves.iter().enumerate() {
+                assert!(!moves[..i].contains(m));
+            }
+        }
+    }
 }
@@ -3,14 +3,17 @@ use crate::model::color::Color;
 use crate::model::piece::Piece;
 use crate::model::r#move::Move;
 use crate::model::r#move::MoveSpecial;
+use crate::model::rank::Rank;
+use crate::model::square::Square;
 use crate::model::types::square_names::*;
-use crate::model::types::SquareIndex;
-use crate::model::types::SQUARE_NEIGHBORHOODS;
+use crate::move_generator::check::is_check;
+use crate::move_generator::king_moves::get_castle_rook_squares;
 
 /// Make a normal move.
 pub fn make_move(board: &mut Board, m: &Move) {
+    let is_capture_or_pawn_move = is_capture_or_pawn_move(board, m);
     move_piece(board, m);
-    update_board_state(board, m);
+    update_board_state(board, m, is_capture_or_pawn_move);
 }
 
 /// Make a "null" or "passing" move where no pieces are moved but the active
@@ -18,7 +21,6 @@ pub fn make_move(board: &mut Board, m: &Move) {
 /// Null moves don't touch the full/halfmove counters.
 pub fn make_null_move(board: &mut Board) {
     board.swap_color();
-    recalculate_active_pieces(board);
 }
 
 /// Only move the piece, without updating the remaining board state (like
@@ -37,105 +39,66 @@ pub fn move_piece(board: &mut Board, m: &Move) {
         make_simple_move(board, m);
     }
 
-    recalculate_active_pieces(board);
+    debug_assert!(
+        board.pieces.is_consistent(),
+        "piece sets out of sync after {m} in {}",
+        board.to_fen()
+    );
 }
 
 fn make_simple_move(board: &mut Board, m: &Move) {
-    let piece = board.pieces.squares.data[m.from as usize];
-    board.pieces.squares.data[m.from as usize] = None;
-    board.pieces.squares.data[m.to as usize] = piece;
+    board.pieces.move_piece(m.from, m.to);
 }
 
 fn make_castle(board: &mut Board, m: &Move) {
-    match m.special {
-        Some(MoveSpecial::CastleLongBlack) => {
-            board.pieces.squares.data[A8 as usize] = None;
-            board.pieces.squares.data[C8 as usize] = Some(Piece::BlackKing);
-            board.pieces.squares.data[D8 as usize] = Some(Piece::BlackRook);
-            board.pieces.squares.data[E8 as usize] = None;
-        }
-        Some(MoveSpecial::CastleLongWhite) => {
-            board.pieces.squares.data[A1 as usize] = None;
-            board.pieces.squares.data[C1 as usize] = Some(Piece::WhiteKing);
-            board.pieces.squares.data[D1 as usize] = Some(Piece::WhiteRook);
-            board.pieces.squares.data[E1 as usize] = None;
-        }
-        Some(MoveSpecial::CastleShortBlack) => {
-            board.pieces.squares.data[E8 as usize] = None;
-            board.pieces.squares.data[F8 as usize] = Some(Piece::BlackRook);
-            board.pieces.squares.data[G8 as usize] = Some(Piece::BlackKing);
-            board.pieces.squares.data[H8 as usize] = None;
-        }
-        Some(MoveSpecial::CastleShortWhite) => {
-            board.pieces.squares.data[E1 as usize] = None;
-            board.pieces.squares.data[F1 as usize] = Some(Piece::WhiteRook);
-            board.pieces.squares.data[G1 as usize] = Some(Piece::WhiteKing);
-            board.pieces.squares.data[H1 as usize] = None;
-        }
-        _ => panic!("Move should be castling"),
-    }
+    let (rook_from, rook_to) = get_castle_rook_squares(board, m);
+
+    // in Chess960 the king may move onto the rook square or vice versa, so
+    // both pieces are removed before they are placed again
+    let king = board.pieces.remove(m.from).expect("castling needs a king");
+    let rook = board
+        .pieces
+        .remove(rook_from)
+        .expect("castling needs a rook");
+    board.pieces.put(m.to, king);
+    board.pieces.put(rook_to, rook);
 }
 
 fn make_capture(board: &mut Board, m: &Move) {
-    board.pieces.squares.data[m.from as usize] = None;
-    board.pieces.squares.data[m.to as usize] = None;
-
-    for blast in SQUARE_NEIGHBORHOODS[m.to as usize] {
-        let Some(blast) = blast else {
-            break;
-        };
-        if !board.has_pawn_at(blast) {
-            board.pieces.squares.data[blast as usize] = None;
-        }
-    }
+    board.variant.resolve_capture(board, m);
 }
 
 fn make_en_passant(board: &mut Board, m: &Move) {
     make_capture(board, m);
 
-    if board.our_color == Color::Black {
-        board.pieces.squares.data[(m.to + 8) as usize] = None;
-    } else {
-        board.pieces.squares.data[(m.to - 8) as usize] = None;
-    }
+    // the captured pawn stands behind the target square
+    let forward = match board.our_color {
+        Color::Black => -1,
+        Color::White => 1,
+    };
+    let captured = Square::from_index(m.to)
+        .offset(0, -forward)
+        .expect("en passant targets the third or sixth rank");
+    board.pieces.remove(captured.index());
 }
 
 fn make_promotion(board: &mut Board, m: &Move) {
-    match m.special {
-        Some(MoveSpecial::PromoteBishopBlack) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::BlackBishop);
-        }
-        Some(MoveSpecial::PromoteKnightBlack) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::BlackKnight);
-        }
-        Some(MoveSpecial::PromoteQueenBlack) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::BlackQueen);
-        }
-        Some(MoveSpecial::PromoteRookBlack) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::BlackRook);
-        }
-        Some(MoveSpecial::PromoteBishopWhite) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::WhiteBishop);
-        }
-        Some(MoveSpecial::PromoteKnightWhite) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::WhiteKnight);
-        }
-        Some(MoveSpecial::PromoteQueenWhite) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::WhiteQueen);
-        }
-        Some(MoveSpecial::PromoteRookWhite) => {
-            board.pieces.squares.data[m.from as usize] = None;
-            board.pieces.squares.data[m.to as usize] = Some(Piece::WhiteRook);
-        }
-        _ => panic!("Move should be castling"),
-    }
+    let piece = match m.special {
+        Some(MoveSpecial::PromoteBishopBlack) => Piece::BlackBishop,
+        Some(MoveSpecial::PromoteKnightBlack) => Piece::BlackKnight,
+        Some(MoveSpecial::PromoteQueenBlack) => Piece::BlackQueen,
+        Some(MoveSpecial::PromoteRookBlack) => Piece::BlackRook,
+        Some(MoveSpecial::PromoteBishopWhite) => Piece::WhiteBishop,
+        Some(MoveSpecial::PromoteKnightWhite) => Piece::WhiteKnight,
+        Some(MoveSpecial::PromoteQueenWhite) => Piece::WhiteQueen,
+        Some(MoveSpecial::PromoteRookWhite) => Piece::WhiteRook,
+        Some(MoveSpecial::PromoteKingBlack) => Piece::BlackKing,
+        Some(MoveSpecial::PromoteKingWhite) => Piece::WhiteKing,
+        _ => panic!("Move should be a promotion"),
+    };
+
+    board.pieces.remove(m.from);
+    board.pieces.put(m.to, piece);
 }
 
 fn is_capture(board: &mut Board, m: &Move) -> bool {
@@ -148,13 +111,19 @@ fn was_double_pawn_advance(board: &mut Board, m: &Move) -> bool {
         && (m.from as i8 - m.to as i8).abs() == 16
 }
 
-fn was_capture_or_pawn_advance(board: &mut Board, m: &Move) -> bool {
-    board.pieces.squares.data[m.to as usize].is_none()
-        || board.pieces.squares.data[m.to as usize].is_some_and(Piece::is_pawn)
-        || m.is_promotion()
+/// Decided before the move is made, because afterwards a capture cannot be
+/// told from a quiet move in every variant.
+fn is_capture_or_pawn_move(board: &Board, m: &Move) -> bool {
+    board.has_their_color_at(m.to)
+        || m.is_en_passant()
+        || board.has_pawn_at(m.from)
 }
 
-fn update_board_state(board: &mut Board, m: &Move) {
+fn update_board_state(
+    board: &mut Board,
+    m: &Move,
+    is_capture_or_pawn_move: bool,
+) {
     // color and fullmove
     if board.our_color == Color::Black {
         board.fullmove += 1;
@@ -162,7 +131,7 @@ fn update_board_state(board: &mut Board, m: &Move) {
     board.swap_color();
 
     // halfmove
-    if was_capture_or_pawn_advance(board, m) {
+    if is_capture_or_pawn_move {
         board.halfmove = 0;
     } else {
         board.halfmove += 1;
@@ -189,59 +158,59 @@ fn update_board_state(board: &mut Board, m: &Move) {
     }
 
     // castle
-    if board.is_empty_at(0) {
-        board.castle.white_long = false;
-    }
-    if board.is_empty_at(7) {
-        board.castle.white_short = false;
+    for special in [
+        MoveSpecial::CastleLongBlack,
+        MoveSpecial::CastleShortBlack,
+        MoveSpecial::CastleLongWhite,
+        MoveSpecial::CastleShortWhite,
+    ] {
+        remove_lost_castling_right(board, m, special);
     }
-    if board.is_empty_at(56) {
-        board.castle.black_long = false;
-    }
-    if board.is_empty_at(63) {
-        board.castle.black_short = false;
-    }
-    if m.from == 4 {
-        board.castle.white_long = false;
-        board.castle.white_short = false;
-    } else if m.from == 60 {
-        board.castle.black_long = false;
-        board.castle.black_short = false;
+    if let Some(king) = board.pieces.squares.data[m.to as usize] {
+        if king.is_king() {
+            board.castle.remove_rights(king.get_color());
+        }
     }
 
-    // active pieces
-    recalculate_active_pieces(board);
+    // checks
+    if board.variant.counts_checks() && is_check(board) {
+        board.checks_given[board.their_color as usize] += 1;
+    }
 }
 
-fn recalculate_active_pieces(board: &mut Board) {
-    board.pieces.our_bishops.clear();
-    board.pieces.our_kings.clear();
-    board.pieces.our_knights.clear();
-    board.pieces.our_pawns.clear();
-    board.pieces.our_queens.clear();
-    board.pieces.our_rooks.clear();
-
-    for i in 0..64 {
-        if let Some(piece) = board.pieces.squares.data[i] {
-            if piece.is_bishop_of_color(board.our_color) {
-                board.pieces.our_bishops.push(i as SquareIndex);
-            } else if piece.is_king_of_color(board.our_color) {
-                board.pieces.our_kings.push(i as SquareIndex);
-            } else if piece.is_knight_of_color(board.our_color) {
-                board.pieces.our_knights.push(i as SquareIndex);
-            } else if piece.is_pawn_of_color(board.our_color) {
-                board.pieces.our_pawns.push(i as SquareIndex);
-            } else if piece.is_queen_of_color(board.our_color) {
-                board.pieces.our_queens.push(i as SquareIndex);
-            } else if piece.is_rook_of_color(board.our_color) {
-                board.pieces.our_rooks.push(i as SquareIndex);
-            }
-        }
+/// The right is lost as soon as a move starts or ends on the square of the
+/// castling rook, so that no other rook can take over the right later on. A
+/// rook removed without a move, e.g. by an explosion, loses it too.
+fn remove_lost_castling_right(
+    board: &mut Board,
+    m: &Move,
+    special: MoveSpecial,
+) {
+    let Some(rook_file) = board.castle.get_rook_file(special) else {
+        return;
+    };
+
+    let color = match special {
+        MoveSpecial::CastleLongBlack | MoveSpecial::CastleShortBlack => {
+            Color::Black
+        }
+        _ => Color::White,
+    };
+    let rook_square =
+        Square::from_file_rank(rook_file, Rank::back_rank(color)).index();
+    let has_rook = board.pieces.squares.data[rook_square as usize]
+        .is_some_and(|p| p.is_rook_of_color(color));
+
+    if m.from == rook_square || m.to == rook_square || !has_rook {
+        board.castle.remove_right(special);
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::file::File;
+    use crate::model::variant::Variant;
+
     use super::*;
 
     #[test]
@@ -289,7 +258,7 @@ mod test {
             "rnbqk3/pp3p2/2p1p1pn/3p4/1b1PP1P1/2N5/PPP1BP2/R1BQK3 b Qq - 0 10",
             "rnb1k3/pp3p2/2p1p1pn/3p4/1b1PP1Pq/2N5/PPP1BP2/R1BQK3 w Qq - 1 11",
             "rnb1k3/pp3p2/2p1p1pn/3p4/1b1PPBPq/2N5/PPP1BP2/R2QK3 b Qq - 2 11",
-            "rnb1k3/pp3p2/2p1p1pn/3p4/1b1PPBP1/2N5/PPP5/R2Q4 w Qq - 0 12",
+            "rnb1k3/pp3p2/2p1p1pn/3p4/1b1PPBP1/2N5/PPP5/R2Q4 w q - 0 12",
         ];
 
         let moves = vec![
@@ -379,7 +348,7 @@ mod test {
             assert_eq!(
                 board,
                 Board::from_fen(
-                    "rnbqkb1r/pppppppp/8/P7/8/8/1PP1PPPP/RN3BNR w KQkq - 0 4"
+                    "rnbqkb1r/pppppppp/8/P7/8/8/1PP1PPPP/RN3BNR w kq - 0 4"
                 )
             );
         }
@@ -640,4 +609,73 @@ mod test {
             assert_eq!(board, Board::from_fen("rn1qkbnr/p1ppp1pp/5p2/8/PpbP3P/1P3N2/2P1PPP1/RNBQKBR1 b Qkq - 0 6"));
         }
     }
+
+    #[test]
+    fn it_makes_a_chess960_castle_where_the_king_lands_on_the_rook_square() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/5KR1 w K - 0 1");
+        make_move(&mut board, &Move::castle(MoveSpecial::CastleShortWhite, F1));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+    }
+
+    #[test]
+    fn it_makes_a_chess960_castle_where_the_king_passes_the_rook() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/2K1R3 w E - 0 1");
+        make_move(&mut board, &Move::castle(MoveSpecial::CastleShortWhite, C1));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/5RK1 b - - 1 1");
+    }
+
+    #[test]
+    fn it_removes_the_castling_right_when_another_rook_enters_the_rook_square()
+    {
+        // the right names the b-file, but the rook arrives from a1
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w BH - 0 1");
+        make_move(&mut board, &Move::from_to(A1, B1));
+        assert_eq!(board.castle.white_long, None);
+        assert_eq!(board.castle.white_short, Some(File::H));
+    }
+
+    #[test]
+    fn it_removes_the_castling_right_of_a_captured_rook() {
+        let mut board = Board::from_fen_with_variant(
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            Variant::Standard,
+        );
+        make_move(&mut board, &Move::from_to(A1, A8));
+        assert_eq!(board.to_fen(), "R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 1");
+    }
+
+    #[test]
+    fn it_makes_captures_without_explosions_in_standard_chess() {
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+        let mut board = Board::from_fen_with_variant(fen, Variant::Standard);
+        make_move(&mut board, &Move::from_to(E4, D5));
+        assert_eq!(board.to_fen(), "4k3/8/8/3P4/8/8/8/4K3 b - - 0 1");
+
+        let mut board = Board::from_fen(fen);
+        make_move(&mut board, &Move::from_to(E4, D5));
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 b - - 0 1");
+    }
+
+    #[test]
+    fn it_counts_the_checks_given_in_three_check() {
+        let mut board = Board::from_fen_with_variant(
+            "4k3/8/8/8/8/8/8/4K2R w K - 0 1 +1+0",
+            Variant::ThreeCheck,
+        );
+        make_move(&mut board, &Move::from_to(H1, H8));
+        assert_eq!(board.to_fen(), "4k2R/8/8/8/8/8/8/4K3 b - - 1 1 +2+0");
+
+        make_move(&mut board, &Move::from_to(E8, E7));
+        assert_eq!(board.to_fen(), "7R/4k3/8/8/8/8/8/4K3 w - - 2 2 +2+0");
+    }
+
+    #[test]
+    fn it_resets_the_halfmove_clock_on_captures_in_standard_chess() {
+        let mut board = Board::from_fen_with_variant(
+            "4k3/8/8/3p4/8/4N3/8/4K3 w - - 5 10",
+            Variant::Standard,
+        );
+        make_move(&mut board, &Move::from_to(E3, D5));
+        assert_eq!(board.to_fen(), "4k3/8/8/3N4/8/8/8/4K3 b - - 0 10");
+    }
 }
@@ -1,39 +1,46 @@
-use std::ops::Range;
+use std::ops::ControlFlow;
 
 use crate::model::board::Board;
 use crate::model::color::Color;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
-use crate::model::types::SquareIndex;
+use crate::model::rank::Rank;
+use crate::model::square::Square;
 use crate::model::types::EN_PASSANT_CANDIDATES;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(15);
-
-    let (forward, rank27, west_capture, east_capture) = match board.our_color {
-        Color::Black => (-8, (48..56), -9, -7),
-        Color::White => (8, (8..16), 7, 9),
+/// Passes the pawn moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    let (forward, start_rank) = match board.our_color {
+        Color::Black => (-1, Rank::SEVENTH),
+        Color::White => (1, Rank::SECOND),
     };
 
-    for from in &board.pieces.our_pawns {
-        add_forwards(board, &mut moves, *from, forward, &rank27);
-        add_west_captures(board, &mut moves, *from, west_capture);
-        add_east_captures(board, &mut moves, *from, east_capture);
+    for from in board.get_our_pieces(PieceType::Pawn) {
+        let from = Square::from_index(from);
+        add_forwards(board, visitor, from, forward, start_rank)?;
+        add_capture(board, visitor, from, from.offset(-1, forward))?;
+        add_capture(board, visitor, from, from.offset(1, forward))?;
     }
 
-    add_en_passants(board, &mut moves);
-
-    moves
+    add_en_passants(board, visitor)
 }
 
-fn add_en_passants(board: &Board, moves: &mut Vec<Move>) {
+fn add_en_passants<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
     let Some(to) = board.en_passant else {
-        return;
+        return ControlFlow::Continue(());
     };
 
     if let (Some(west), ..) = EN_PASSANT_CANDIDATES[to as usize] {
         if let Some(piece) = board.pieces.squares.data[west as usize] {
             if piece.is_pawn_of_color(board.our_color) {
-                moves.push(Move::en_passant(west, to));
+                visitor(Move::en_passant(west, to))?;
             }
         }
     }
@@ -41,101 +48,116 @@ fn add_en_passants(board: &Board, moves: &mut Vec<Move>) {
     if let (.., Some(east)) = EN_PASSANT_CANDIDATES[to as usize] {
         if let Some(piece) = board.pieces.squares.data[east as usize] {
             if piece.is_pawn_of_color(board.our_color) {
-                moves.push(Move::en_passant(east, to));
+                visitor(Move::en_passant(east, to))?;
             }
         }
     }
+
+    ControlFlow::Continue(())
 }
 
-fn add_west_captures(
+fn add_capture<F: FnMut(Move) -> ControlFlow<()>>(
     board: &Board,
-    moves: &mut Vec<Move>,
-    from: SquareIndex,
-    west_capture: i8,
-) {
-    if from % 8 == 0 {
-        return;
-    }
-
-    let to = (from as i8 + west_capture) as u8;
+    visitor: &mut F,
+    from: Square,
+    to: Option<Square>,
+) -> ControlFlow<()> {
+    let Some(to) = to else {
+        return ControlFlow::Continue(());
+    };
 
-    if let Some(piece) = board.pieces.squares.data[to as usize] {
-        if piece.get_color() != board.our_color {
-            moves.push(Move::from_to(from, to));
-        }
+    if !board.has_their_color_at(to.index()) {
+        return ControlFlow::Continue(());
     }
-}
 
-fn add_east_captures(
-    board: &Board,
-    moves: &mut Vec<Move>,
-    from: SquareIndex,
-    east_capture: i8,
-) {
-    if from % 8 == 7 {
-        return;
+    if is_promotion_rank(to) && board.variant.promotes_on_capture() {
+        return add_promotions(board, visitor, from, to);
     }
 
-    let to = (from as i8 + east_capture) as u8;
-
-    if let Some(piece) = board.pieces.squares.data[to as usize] {
-        if piece.get_color() != board.our_color {
-            moves.push(Move::from_to(from, to));
-        }
-    }
+    visitor(Move::from_to(from.index(), to.index()))
 }
 
-fn add_forwards(
+fn add_forwards<F: FnMut(Move) -> ControlFlow<()>>(
     board: &Board,
-    moves: &mut Vec<Move>,
-    from: SquareIndex,
+    visitor: &mut F,
+    from: Square,
     forward: i8,
-    rank27: &Range<u8>,
-) {
-    let to = (from as i8 + forward) as u8;
+    start_rank: Rank,
+) -> ControlFlow<()> {
+    let Some(to) = from.offset(0, forward) else {
+        return ControlFlow::Continue(());
+    };
 
-    if board.pieces.squares.data[to as usize].is_some() {
-        return;
+    if !board.is_empty_at(to.index()) {
+        return ControlFlow::Continue(());
     }
 
-    // promotions
-    if to >= 56 {
-        moves.push(Move::promote_bishop_white(from, to));
-        moves.push(Move::promote_knight_white(from, to));
-        moves.push(Move::promote_queen_white(from, to));
-        moves.push(Move::promote_rook_white(from, to));
-        return;
+    if is_promotion_rank(to) {
+        return add_promotions(board, visitor, from, to);
     }
-    if to < 8 {
-        moves.push(Move::promote_bishop_black(from, to));
-        moves.push(Move::promote_knight_black(from, to));
-        moves.push(Move::promote_queen_black(from, to));
-        moves.push(Move::promote_rook_black(from, to));
-        return;
+
+    visitor(Move::from_to(from.index(), to.index()))?;
+
+    if from.rank() != start_rank {
+        return ControlFlow::Continue(());
     }
 
-    // single step forward
-    moves.push(Move::from_to(from, to));
+    if let Some(to) = to.offset(0, forward) {
+        if board.is_empty_at(to.index()) {
+            visitor(Move::from_to(from.index(), to.index()))?;
+        }
+    }
 
-    // double step forward
-    let to = (from as i8 + forward + forward) as u8;
+    ControlFlow::Continue(())
+}
 
-    if rank27.contains(&from) {
-        if board.pieces.squares.data[to as usize].is_none() {
-            moves.push(Move::from_to(from, to));
+fn is_promotion_rank(square: Square) -> bool {
+    square.rank() == Rank::FIRST || square.rank() == Rank::EIGHTH
+}
+
+fn add_promotions<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+    from: Square,
+    to: Square,
+) -> ControlFlow<()> {
+    let promote_to_king = !board.variant.has_royal_king();
+    let is_white = to.rank() == Rank::EIGHTH;
+    let (from, to) = (from.index(), to.index());
+
+    if is_white {
+        visitor(Move::promote_bishop_white(from, to))?;
+        visitor(Move::promote_knight_white(from, to))?;
+        visitor(Move::promote_queen_white(from, to))?;
+        visitor(Move::promote_rook_white(from, to))?;
+        if promote_to_king {
+            visitor(Move::promote_king_white(from, to))?;
+        }
+    } else {
+        visitor(Move::promote_bishop_black(from, to))?;
+        visitor(Move::promote_knight_black(from, to))?;
+        visitor(Move::promote_queen_black(from, to))?;
+        visitor(Move::promote_rook_black(from, to))?;
+        if promote_to_king {
+            visitor(Move::promote_king_black(from, to))?;
         }
     }
+
+    ControlFlow::Continue(())
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
 
     #[test]
     fn it_generates_white_pawn_moves_unless_blocked() {
         let fen = "2N4n/2P4P/5n2/4bP2/Nn6/3N2r1/PP1PP1P1/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 4);
         assert_eq!(
             moves,
@@ -152,7 +174,8 @@ mod test {
     fn it_generates_black_starting_position_pawn_moves() {
         let fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 16);
         assert_eq!(
             moves,
@@ -181,7 +204,8 @@ mod test {
     fn it_generates_white_starting_position_pawn_moves() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 16);
         assert_eq!(
             moves,
@@ -210,7 +234,8 @@ mod test {
     fn it_generates_unblocked_black_forward_moves() {
         let fen = "8/7p/6p1/5p2/4p3/3p4/2p5/8 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 10);
         assert_eq!(
             moves,
@@ -233,7 +258,8 @@ mod test {
     fn it_generates_unblocked_white_forward_moves() {
         let fen = "8/5P2/4P3/3P4/2P5/1P6/P7/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 10);
         assert_eq!(
             moves,
@@ -256,7 +282,8 @@ mod test {
     fn it_generates_all_captures_for_white() {
         let fen = "8/8/8/pppppppp/PPPPPPPP/8/8/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 14);
         assert_eq!(
             moves,
@@ -283,7 +310,8 @@ mod test {
     fn it_generates_all_captures_for_black() {
         let fen = "8/8/8/pppppppp/PPPPPPPP/8/8/8 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 14);
         assert_eq!(
             moves,
@@ -310,7 +338,8 @@ mod test {
     fn it_generates_all_promotion_captures_for_white() {
         let fen = "nnnnnnnn/PPPPPPPP/8/8/8/8/8/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 14);
     }
 
@@ -318,7 +347,8 @@ mod test {
     fn it_generates_all_promotion_captures_for_black() {
         let fen = "8/8/8/8/8/8/pppppppp/NNNNNNNN b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 14);
     }
 
@@ -326,7 +356,8 @@ mod test {
     fn it_generates_captures_for_white_unless_wrong_color() {
         let fen = "4Nnn1/5P2/8/3NNN2/4P3/1NNN4/2P5/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 1);
         assert_eq!(moves, vec![Move::from_to(53, 62),]);
     }
@@ -335,7 +366,8 @@ mod test {
     fn it_generates_captures_for_black_unless_wrong_color() {
         let fen = "8/2p5/1nnn4/4p3/3nnn2/8/2p5/1Nnn4 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 1);
         assert_eq!(moves, vec![Move::from_to(10, 1),]);
     }
@@ -350,7 +382,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1ppppppp/8/8/pP5P/8/P1PPPPP1/RNBQKBNR b KQkq b3 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(A4, B3)));
         }
@@ -360,7 +393,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pppp2pp/8/3Ppp2/8/8/PPP1PPPP/RNBQKBNR w KQkq e6 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(D5, E6)));
         }
@@ -370,7 +404,8 @@ mod test {
             let fen =
                 "rnbqkbnr/p1pppppp/8/8/Pp5P/8/1PPPPPP1/RNBQKBNR b KQkq a3 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(B4, A3)));
         }
@@ -380,7 +415,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1p1ppppp/8/7P/pPp5/6P1/P1PPPP2/RNBQKBNR b KQkq b3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(A4, B3)));
             assert!(moves.contains(&Move::en_passant(C4, B3)));
@@ -391,7 +427,8 @@ mod test {
             let fen =
                 "rnbqkbnr/p1p1pppp/7P/8/1pPp4/8/PP1PPPP1/RNBQKBNR b KQkq c3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(B4, C3)));
             assert!(moves.contains(&Move::en_passant(D4, C3)));
@@ -402,7 +439,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pp1p1ppp/7P/8/2pPp3/8/PPP1PPP1/RNBQKBNR b KQkq d3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(C4, D3)));
             assert!(moves.contains(&Move::en_passant(E4, D3)));
@@ -413,7 +451,8 @@ mod test {
             let fen =
                 "rnbqkbnr/ppp1p1pp/7P/8/3pPp2/8/PPPP1PP1/RNBQKBNR b KQkq e3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(D4, E3)));
             assert!(moves.contains(&Move::en_passant(F4, E3)));
@@ -424,7 +463,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pppp1p1p/7P/8/4pPp1/8/PPPPP1P1/RNBQKBNR b KQkq f3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(E4, F3)));
             assert!(moves.contains(&Move::en_passant(G4, F3)));
@@ -435,7 +475,8 @@ mod test {
             let fen =
                 "rnbqkbnr/ppppp1p1/8/P7/5pPp/7P/1PPPPP2/RNBQKBNR b KQkq g3 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(F4, G3)));
             assert!(moves.contains(&Move::en_passant(H4, G3)));
@@ -446,7 +487,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pppppp1p/8/8/P5pP/8/1PPPPPP1/RNBQKBNR b KQkq h3 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(G4, H3)));
         }
@@ -456,7 +498,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1pppppp1/8/pP5p/8/8/P1PPPPPP/RNBQKBNR w KQkq a6 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(B5, A6)));
         }
@@ -466,7 +509,8 @@ mod test {
             let fen =
                 "rnbqkbnr/p1ppppp1/8/PpP5/8/7p/1P1PPPPP/RNBQKBNR w KQkq b6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(A5, B6)));
             assert!(moves.contains(&Move::en_passant(C5, B6)));
@@ -477,7 +521,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pp1pppp1/8/1PpP4/7p/8/P1P1PPPP/RNBQKBNR w KQkq c6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(B5, C6)));
             assert!(moves.contains(&Move::en_passant(D5, C6)));
@@ -488,7 +533,8 @@ mod test {
             let fen =
                 "rnbqkbnr/ppp1ppp1/8/2PpP3/7p/8/PP1P1PPP/RNBQKBNR w KQkq d6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(C5, D6)));
             assert!(moves.contains(&Move::en_passant(E5, D6)));
@@ -499,7 +545,8 @@ mod test {
             let fen =
                 "rnbqkbnr/pppp1pp1/8/3PpP2/7p/8/PPP1P1PP/RNBQKBNR w KQkq e6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(D5, E6)));
             assert!(moves.contains(&Move::en_passant(F5, E6)));
@@ -510,7 +557,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1pppp1pp/8/4PpP1/p7/8/PPPP1P1P/RNBQKBNR w KQkq f6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(E5, F6)));
             assert!(moves.contains(&Move::en_passant(G5, F6)));
@@ -521,7 +569,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1ppppp1p/8/5PpP/p7/8/PPPPP1P1/RNBQKBNR w KQkq g6 0 5";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(2 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(F5, G6)));
             assert!(moves.contains(&Move::en_passant(H5, G6)));
@@ -532,7 +581,8 @@ mod test {
             let fen =
                 "rnbqkbnr/1pppppp1/p7/6Pp/8/8/PPPPPP1P/RNBQKBNR w KQkq h6 0 3";
             let board = Board::from_fen(fen);
-            let moves = generate(&board);
+            let mut moves = MoveList::new();
+            let _ = visit(&board, &mut moves.visitor());
             assert!(1 == moves.iter().filter(|m| m.is_en_passant()).count());
             assert!(moves.contains(&Move::en_passant(G5, H6)));
         }
@@ -0,0 +1,151 @@
+use crate::model::board::Board;
+
+use super::legal_moves::generate_moves;
+use super::make_move::make_move;
+
+/// Counts the leaf nodes of the legal move tree up to the given depth.
+/// See: https://www.chessprogramming.org/Perft
+pub fn perft(board: &Board, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let mut board = board.clone();
+    let moves = generate_moves(&mut board);
+
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    moves
+        .iter()
+        .map(|m| {
+            let mut new_board = board.clone();
+            make_move(&mut new_board, m);
+            perft(&new_board, depth - 1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::variant::Variant;
+
+    use super::*;
+
+    #[test]
+    fn it_counts_the_moves_of_the_starting_position() {
+        let board = Board::new();
+        assert_eq!(perft(&board, 0), 1);
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+    }
+
+    #[test]
+    fn it_counts_the_atomic_moves_of_the_starting_position_at_depth_4() {
+        assert_eq!(perft(&Board::new(), 4), 197326);
+    }
+
+    #[test]
+    fn it_counts_the_moves_of_the_starting_position_with_shredder_fen() {
+        let board = Board::from_fen(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w HAha - 0 1",
+        );
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+    }
+
+    #[test]
+    fn it_counts_the_moves_of_chess960_positions() {
+        let board = Board::from_fen(
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+        );
+        assert_eq!(perft(&board, 1), 21);
+        assert_eq!(perft(&board, 2), 528);
+
+        let board = Board::from_fen(
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+        );
+        assert_eq!(perft(&board, 1), 21);
+        assert_eq!(perft(&board, 2), 807);
+    }
+
+    #[test]
+    fn it_counts_the_standard_moves_of_the_starting_position() {
+        let board = Board::from_fen_with_variant(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Variant::Standard,
+        );
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+        assert_eq!(perft(&board, 4), 197281);
+    }
+
+    #[test]
+    fn it_counts_the_standard_moves_of_a_position_with_many_captures() {
+        let board = Board::from_fen_with_variant(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            Variant::Standard,
+        );
+        assert_eq!(perft(&board, 1), 48);
+        assert_eq!(perft(&board, 2), 2039);
+        assert_eq!(perft(&board, 3), 97862);
+    }
+
+    #[test]
+    fn it_counts_the_standard_moves_of_a_position_with_capturing_promotions() {
+        let board = Board::from_fen_with_variant(
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            Variant::Standard,
+        );
+        assert_eq!(perft(&board, 1), 6);
+        assert_eq!(perft(&board, 2), 264);
+        assert_eq!(perft(&board, 3), 9467);
+    }
+
+    #[test]
+    fn it_counts_the_king_of_the_hill_moves_of_the_starting_position() {
+        let board = Board::from_fen_with_variant(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Variant::KingOfTheHill,
+        );
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+    }
+
+    #[test]
+    fn it_counts_the_three_check_moves_of_the_starting_position() {
+        let board = Board::from_fen_with_variant(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 +0+0",
+            Variant::ThreeCheck,
+        );
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+    }
+
+    #[test]
+    fn it_counts_the_antichess_moves_of_the_starting_position() {
+        let board = Board::from_fen_with_variant(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w - - 0 1",
+            Variant::Antichess,
+        );
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8067);
+        assert_eq!(perft(&board, 4), 153299);
+    }
+
+    #[test]
+    fn it_counts_the_antichess_moves_with_promotions_to_kings() {
+        let board = Board::from_fen_with_variant(
+            "8/1P6/8/8/8/8/8/7k w - - 0 1",
+            Variant::Antichess,
+        );
+        assert_eq!(perft(&board, 1), 5);
+    }
+}
@@ -1,37 +1,46 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
+use crate::model::square::Square;
 
 use super::sliding_moves::add_sliding_moves;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(15);
-
-    for from in &board.pieces.our_queens {
-        let from_x = (from % 8) as i8;
-        let from_y = (from / 8) as i8;
+/// Passes the queen moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    for from in board.get_our_pieces(PieceType::Queen) {
+        let from = Square::from_index(from);
 
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, 1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, 1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (0, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, 0));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, 0));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (0, 1));
+        add_sliding_moves(board, visitor, from, (-1, -1))?;
+        add_sliding_moves(board, visitor, from, (1, -1))?;
+        add_sliding_moves(board, visitor, from, (-1, 1))?;
+        add_sliding_moves(board, visitor, from, (1, 1))?;
+        add_sliding_moves(board, visitor, from, (0, -1))?;
+        add_sliding_moves(board, visitor, from, (-1, 0))?;
+        add_sliding_moves(board, visitor, from, (1, 0))?;
+        add_sliding_moves(board, visitor, from, (0, 1))?;
     }
 
-    moves
+    ControlFlow::Continue(())
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
 
     #[test]
     fn it_generates_queen_moves_from_the_starting_position() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 0);
     }
 
@@ -39,7 +48,8 @@ mod test {
     fn it_generates_black_queen_moves() {
         let fen = "8/8/2b2b2/7b/8/2R2q2/7n/6nq b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 16);
         assert_eq!(
             moves,
@@ -68,7 +78,8 @@ mod test {
     fn it_generates_white_queen_moves() {
         let fen = "8/8/2B2R2/7B/8/2r2Q2/7N/6NQ w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 16);
         assert_eq!(
             moves,
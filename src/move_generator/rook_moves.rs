@@ -1,33 +1,42 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
+use crate::model::piece_type::PieceType;
 use crate::model::r#move::Move;
+use crate::model::square::Square;
 
 use super::sliding_moves::add_sliding_moves;
 
-pub fn generate(board: &Board) -> Vec<Move> {
-    let mut moves = Vec::with_capacity(15);
-
-    for from in &board.pieces.our_rooks {
-        let from_x = (from % 8) as i8;
-        let from_y = (from / 8) as i8;
+/// Passes the rook moves one by one to the visitor, which can stop the
+/// generation with `ControlFlow::Break`.
+pub fn visit<F: FnMut(Move) -> ControlFlow<()>>(
+    board: &Board,
+    visitor: &mut F,
+) -> ControlFlow<()> {
+    for from in board.get_our_pieces(PieceType::Rook) {
+        let from = Square::from_index(from);
 
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (0, -1));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (-1, 0));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (1, 0));
-        add_sliding_moves(board, &mut moves, *from, from_x, from_y, (0, 1));
+        add_sliding_moves(board, visitor, from, (0, -1))?;
+        add_sliding_moves(board, visitor, from, (-1, 0))?;
+        add_sliding_moves(board, visitor, from, (1, 0))?;
+        add_sliding_moves(board, visitor, from, (0, 1))?;
     }
 
-    moves
+    ControlFlow::Continue(())
 }
 
 #[cfg(test)]
 mod test {
+    use crate::model::move_list::MoveList;
+
     use super::*;
 
     #[test]
     fn it_generates_rook_moves_from_the_starting_position() {
         let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 0);
     }
 
@@ -35,7 +44,8 @@ mod test {
     fn it_generates_black_rook_moves_on_an_empty_board() {
         let fen = "8/8/8/2r5/5r2/8/8/8 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 28);
         assert_eq!(
             moves,
@@ -76,7 +86,8 @@ mod test {
     fn it_generates_white_rook_moves_on_an_empty_board() {
         let fen = "8/8/8/2R5/5R2/8/8/8 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 28);
         assert_eq!(
             moves,
@@ -117,7 +128,8 @@ mod test {
     fn it_generates_black_rook_capture_moves_on_an_otherwise_empty_board() {
         let fen = "2Q2R2/8/8/Q1r4Q/B4r1P/8/8/2Q2N2 b - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 28);
         assert_eq!(
             moves,
@@ -158,7 +170,8 @@ mod test {
     fn it_generates_white_rook_capture_moves_on_an_otherwise_empty_board() {
         let fen = "2b2n2/8/8/q1R4p/r4R1p/8/8/2b2n2 w - - 0 1";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 28);
         assert_eq!(
             moves,
@@ -199,7 +212,8 @@ mod test {
     fn it_generates_black_rook_moves_in_a_lichess_game() {
         let fen = "2r1r1k1/b4ppp/p7/P2p4/8/3pqPP1/3Q3P/1NR2R1K b - - 5 30";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 16);
         assert_eq!(
             moves,
@@ -228,7 +242,8 @@ mod test {
     fn it_generates_white_rook_moves_in_a_lichess_game() {
         let fen = "2r1r1k1/b4ppp/p7/P2p4/8/3p1PP1/3q3P/1NR2R1K w - - 0 31";
         let board = Board::from_fen(fen);
-        let moves = generate(&board);
+        let mut moves = MoveList::new();
+        let _ = visit(&board, &mut moves.visitor());
         assert_eq!(moves.len(), 13);
         assert_eq!(
             moves,
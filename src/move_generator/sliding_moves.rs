@@ -1,35 +1,31 @@
+use std::ops::ControlFlow;
+
 use crate::model::board::Board;
 use crate::model::r#move::Move;
-use crate::model::types::SquareIndex;
+use crate::model::square::Square;
 
-pub fn add_sliding_moves(
+pub fn add_sliding_moves<F: FnMut(Move) -> ControlFlow<()>>(
     board: &Board,
-    moves: &mut Vec<Move>,
-    from: u8,
-    from_x: i8,
-    from_y: i8,
+    visitor: &mut F,
+    from: Square,
     direction: (i8, i8),
-) {
-    for i in 1..8 {
-        let to_x = from_x + i * direction.0;
-        let to_y = from_y + i * direction.1;
-
-        if to_x < 0 || to_x > 7 || to_y < 0 || to_y > 7 {
-            break;
-        }
+) -> ControlFlow<()> {
+    let mut to = from;
 
-        let to = (8 * to_y + to_x) as SquareIndex;
+    while let Some(next) = to.offset(direction.0, direction.1) {
+        to = next;
+        let m = Move::from_to(from.index(), to.index());
 
-        if board.pieces.squares.data[to as usize].is_none() {
-            moves.push(Move::from_to(from, to));
+        let Some(piece) = board.pieces.squares.data[to.index() as usize] else {
+            visitor(m)?;
             continue;
-        }
+        };
 
-        if let Some(piece) = board.pieces.squares.data[to as usize] {
-            if piece.get_color() != board.our_color {
-                moves.push(Move::from_to(from, to));
-            }
-            break;
+        if piece.get_color() != board.our_color {
+            visitor(m)?;
         }
+        break;
     }
+
+    ControlFlow::Continue(())
 }
@@ -19,11 +19,12 @@ pub fn backpropagate(
         if let Some(win_color) = node.evaluation.get_win_color() {
             let node_evaluation = node.evaluation;
 
+            // a loss is only proven once every move of the parent was tried
             if win_color != node.our_color
-                || tree
-                    .get_sibling_indices(n)
-                    .iter()
-                    .all(|s| tree.get_node(*s).evaluation == node_evaluation)
+                || tree.get_parent(n).is_some_and(|p| p.is_fully_expanded())
+                    && tree
+                        .get_sibling_indices(n)
+                        .all(|s| tree.get_node(s).evaluation == node_evaluation)
             {
                 if let Some(parent) = tree.get_parent_mut(n) {
                     parent.evaluation = node_evaluation;
@@ -31,12 +32,12 @@ pub fn backpropagate(
             }
         }
 
-        let node = tree.get_node_mut(n);
+        let score = tree.get_score_mut(n);
         match simulation_result.evaluation {
-            BoardEvaluation::Draw => node.score.draws += 1,
+            BoardEvaluation::Draw => score.draws += 1,
             BoardEvaluation::Inconclusive => panic!(),
-            BoardEvaluation::WinBlack => node.score.wins_black += 1,
-            BoardEvaluation::WinWhite => node.score.wins_white += 1,
+            BoardEvaluation::WinBlack => score.wins_black += 1,
+            BoardEvaluation::WinWhite => score.wins_white += 1,
         }
     }
 }
@@ -65,6 +66,15 @@ mod test {
 
     use super::*;
 
+    /// Marks every node with children as fully expanded.
+    fn set_fully_expanded(tree: &mut Tree) {
+        for i in 0..tree.get_size() as TreeNodeIndex {
+            if tree.get_node(i).has_children() {
+                tree.set_untried_moves(i, &[]);
+            }
+        }
+    }
+
     #[test]
     fn it_calculates_the_principal_variation_indices() {
         let mut tree = Tree::new(Board::new());
@@ -75,6 +85,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 3);
         tree.add_node(Board::new(), Move::from_to(0, 0), 5);
         tree.add_node(Board::new(), Move::from_to(0, 0), 6);
+        set_fully_expanded(&mut tree);
 
         let indices = get_principal_variation_node_indices(&tree, 7);
 
@@ -90,6 +101,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 2); // index = 4
         tree.add_node(Board::new(), Move::from_to(0, 0), 3); // index = 5
         tree.add_node(Board::new(), Move::from_to(0, 0), 5); // index = 6
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 10,
             evaluation: BoardEvaluation::Draw,
@@ -97,13 +109,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(1).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(2).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(3).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(6).score, score(1, 0, 0));
+        assert_eq!(*tree.get_score(0), score(1, 0, 0));
+        assert_eq!(*tree.get_score(1), score(1, 0, 0));
+        assert_eq!(*tree.get_score(2), score(1, 0, 0));
+        assert_eq!(*tree.get_score(3), score(1, 0, 0));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(1, 0, 0));
+        assert_eq!(*tree.get_score(6), score(1, 0, 0));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -123,6 +135,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 2); // index = 4
         tree.add_node(Board::new(), Move::from_to(0, 0), 3); // index = 5
         tree.add_node(Board::new(), Move::from_to(0, 0), 5); // index = 6
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 10,
             evaluation: BoardEvaluation::WinBlack,
@@ -130,13 +143,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(1).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(2).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(3).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(6).score, score(0, 1, 0));
+        assert_eq!(*tree.get_score(0), score(0, 1, 0));
+        assert_eq!(*tree.get_score(1), score(0, 1, 0));
+        assert_eq!(*tree.get_score(2), score(0, 1, 0));
+        assert_eq!(*tree.get_score(3), score(0, 1, 0));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(0, 1, 0));
+        assert_eq!(*tree.get_score(6), score(0, 1, 0));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -156,6 +169,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 2); // index = 4
         tree.add_node(Board::new(), Move::from_to(0, 0), 3); // index = 5
         tree.add_node(Board::new(), Move::from_to(0, 0), 5); // index = 6
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 10,
             evaluation: BoardEvaluation::WinWhite,
@@ -163,13 +177,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(1).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(2).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(3).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(6).score, score(0, 0, 1));
+        assert_eq!(*tree.get_score(0), score(0, 0, 1));
+        assert_eq!(*tree.get_score(1), score(0, 0, 1));
+        assert_eq!(*tree.get_score(2), score(0, 0, 1));
+        assert_eq!(*tree.get_score(3), score(0, 0, 1));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(0, 0, 1));
+        assert_eq!(*tree.get_score(6), score(0, 0, 1));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -189,6 +203,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 2); // index = 4
         tree.add_node(Board::new(), Move::from_to(0, 0), 3); // index = 5
         tree.add_node(Board::new(), Move::from_to(0, 0), 5); // index = 6
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 10,
             evaluation: BoardEvaluation::WinWhite,
@@ -203,13 +218,13 @@ mod test {
 
         backpropagate(&mut tree, 4, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 1, 1));
-        assert_eq!(tree.get_node(1).score, score(0, 1, 1));
-        assert_eq!(tree.get_node(2).score, score(0, 1, 1));
-        assert_eq!(tree.get_node(3).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(4).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(5).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(6).score, score(0, 0, 1));
+        assert_eq!(*tree.get_score(0), score(0, 1, 1));
+        assert_eq!(*tree.get_score(1), score(0, 1, 1));
+        assert_eq!(*tree.get_score(2), score(0, 1, 1));
+        assert_eq!(*tree.get_score(3), score(0, 0, 1));
+        assert_eq!(*tree.get_score(4), score(0, 1, 0));
+        assert_eq!(*tree.get_score(5), score(0, 0, 1));
+        assert_eq!(*tree.get_score(6), score(0, 0, 1));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -232,6 +247,7 @@ mod test {
         tree.add_node(board_black(), Move::from_to(0, 0), 2); //         4, B
         tree.add_node(board_white(), Move::from_to(0, 0), 3); //         5, W
         tree.add_node(board_black(), Move::from_to(0, 0), 5); //         6, B
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 0,
             evaluation: BoardEvaluation::WinWhite,
@@ -239,13 +255,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(1).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(2).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(3).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(6).score, score(0, 0, 1));
+        assert_eq!(*tree.get_score(0), score(0, 0, 1));
+        assert_eq!(*tree.get_score(1), score(0, 0, 1));
+        assert_eq!(*tree.get_score(2), score(0, 0, 1));
+        assert_eq!(*tree.get_score(3), score(0, 0, 1));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(0, 0, 1));
+        assert_eq!(*tree.get_score(6), score(0, 0, 1));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::WinWhite);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::WinWhite);
@@ -268,6 +284,7 @@ mod test {
         tree.add_node(board_black(), Move::from_to(0, 0), 2); //         4, B
         tree.add_node(board_white(), Move::from_to(0, 0), 4); //         5, W
         tree.add_node(board_black(), Move::from_to(0, 0), 5); //         6, B
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 0,
             evaluation: BoardEvaluation::WinWhite,
@@ -275,13 +292,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(1).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(2).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(3).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(5).score, score(0, 0, 1));
-        assert_eq!(tree.get_node(6).score, score(0, 0, 1));
+        assert_eq!(*tree.get_score(0), score(0, 0, 1));
+        assert_eq!(*tree.get_score(1), score(0, 0, 1));
+        assert_eq!(*tree.get_score(2), score(0, 0, 1));
+        assert_eq!(*tree.get_score(3), score(0, 0, 0));
+        assert_eq!(*tree.get_score(4), score(0, 0, 1));
+        assert_eq!(*tree.get_score(5), score(0, 0, 1));
+        assert_eq!(*tree.get_score(6), score(0, 0, 1));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -304,6 +321,7 @@ mod test {
         tree.add_node(board_black(), Move::from_to(0, 0), 2); //         4, B
         tree.add_node(board_white(), Move::from_to(0, 0), 3); //         5, W
         tree.add_node(board_black(), Move::from_to(0, 0), 5); //         6, B
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 0,
             evaluation: BoardEvaluation::WinBlack,
@@ -311,13 +329,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(1).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(2).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(3).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(0, 1, 0));
-        assert_eq!(tree.get_node(6).score, score(0, 1, 0));
+        assert_eq!(*tree.get_score(0), score(0, 1, 0));
+        assert_eq!(*tree.get_score(1), score(0, 1, 0));
+        assert_eq!(*tree.get_score(2), score(0, 1, 0));
+        assert_eq!(*tree.get_score(3), score(0, 1, 0));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(0, 1, 0));
+        assert_eq!(*tree.get_score(6), score(0, 1, 0));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
@@ -340,6 +358,7 @@ mod test {
         tree.add_node(board_black(), Move::from_to(0, 0), 2); //         4, B
         tree.add_node(board_white(), Move::from_to(0, 0), 3); //         5, W
         tree.add_node(board_black(), Move::from_to(0, 0), 5); //         6, B
+        set_fully_expanded(&mut tree);
         let simulation_result = SimulationResult {
             depth: 0,
             evaluation: BoardEvaluation::Draw,
@@ -347,13 +366,13 @@ mod test {
 
         backpropagate(&mut tree, 6, simulation_result);
 
-        assert_eq!(tree.get_node(0).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(1).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(2).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(3).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(4).score, score(0, 0, 0));
-        assert_eq!(tree.get_node(5).score, score(1, 0, 0));
-        assert_eq!(tree.get_node(6).score, score(1, 0, 0));
+        assert_eq!(*tree.get_score(0), score(1, 0, 0));
+        assert_eq!(*tree.get_score(1), score(1, 0, 0));
+        assert_eq!(*tree.get_score(2), score(1, 0, 0));
+        assert_eq!(*tree.get_score(3), score(1, 0, 0));
+        assert_eq!(*tree.get_score(4), score(0, 0, 0));
+        assert_eq!(*tree.get_score(5), score(1, 0, 0));
+        assert_eq!(*tree.get_score(6), score(1, 0, 0));
 
         assert_eq!(tree.get_node(0).evaluation, BoardEvaluation::Inconclusive);
         assert_eq!(tree.get_node(1).evaluation, BoardEvaluation::Inconclusive);
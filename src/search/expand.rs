@@ -1,58 +1,102 @@
 use crate::common::random::Random;
 use crate::model::board::Board;
 use crate::model::expansion_result::ExpansionResult;
+use crate::model::r#move::Move;
+use crate::model::repetition_table::RepetitionTable;
 use crate::model::tree::Tree;
 use crate::model::types::TreeNodeIndex;
 use crate::move_generator::legal_moves::generate_moves;
 use crate::move_generator::make_move::make_move;
 
+/// Adds children for the next untried moves of the node, as many as the
+/// expansion width of the tree allows, and returns the first of them. The
+/// moves of a node are generated, shuffled and reserved as children on its
+/// first expansion. Nodes
+/// with fewer visits than the expansion visits of the tree and nodes without
+/// untried moves are returned unexpanded. The position of the returned child
+/// is added to the repetitions.
 pub fn expand(
     tree: &mut Tree,
     node_index: TreeNodeIndex,
     board: Board,
+    repetitions: &mut RepetitionTable,
     random: &mut Random,
 ) -> ExpansionResult {
     let mut board = board;
-    let node = tree.get_node_mut(node_index);
+    let node = tree.get_node(node_index);
 
-    if node.evaluation.is_conclusive() || node.is_not_visited() {
+    if node.evaluation.is_conclusive()
+        || node.is_fully_expanded()
+        || tree.get_score(node_index).get_visits() < tree.get_expansion_visits()
+    {
         return ExpansionResult { board, node_index };
     }
 
-    debug_assert!(node.child_indices.is_empty());
-
-    let moves = generate_moves(&mut board);
-    for m in moves {
-        let mut new_board = board.clone();
-        make_move(&mut new_board, &m);
-        tree.add_node(new_board, m.clone(), node_index);
+    if !node.has_untried_moves_generated() {
+        let tried_moves: Vec<Move> = tree
+            .get_node(node_index)
+            .get_child_indices()
+            .map(|c| tree.get_node(c).get_last_move())
+            .collect();
+        let mut moves: Vec<Move> = generate_moves(&mut board)
+            .iter()
+            .filter(|m| !tried_moves.contains(m))
+            .copied()
+            .collect();
+        random.shuffle(&mut moves);
+        tree.set_untried_moves(node_index, &moves);
     }
 
-    let node_index = *random
-        .pick_element(&tree.get_node(node_index).child_indices)
-        .expect(
-            "there must be an expanded node if the parent was inconclusive",
-        );
-
-    let last_move = tree.get_node(node_index).last_move;
-    make_move(&mut board, &last_move);
+    let mut expansion = None;
+    for _ in 0..tree.get_expansion_width() {
+        let Some(m) = tree.get_next_untried_move(node_index) else {
+            break;
+        };
+        let child_index = tree.get_node(node_index).get_child_indices().end;
+        let mut child_board = board.clone();
+        make_move(&mut child_board, &m);
+        tree.add_node(child_board.clone(), m, node_index);
+        expansion.get_or_insert(ExpansionResult {
+            board: child_board,
+            node_index: child_index,
+        });
+    }
 
-    ExpansionResult { board, node_index }
+    // without legal moves the node stays a leaf, even if the evaluation did
+    // not turn that into a conclusive result
+    let Some(expansion) = expansion else {
+        return ExpansionResult { board, node_index };
+    };
+    repetitions.push(
+        tree.get_node(expansion.node_index).board_hash,
+        expansion.board.halfmove,
+    );
+    expansion
 }
 
 #[cfg(test)]
 mod test {
     use crate::model::board::Board;
     use crate::model::board_evaluation::BoardEvaluation;
-    use crate::model::r#move::Move;
     use crate::model::types::square_names::*;
+    use crate::model::variant::Variant;
 
     use super::*;
 
+    fn expand(
+        tree: &mut Tree,
+        node_index: TreeNodeIndex,
+        board: Board,
+        random: &mut Random,
+    ) -> ExpansionResult {
+        let mut repetitions = RepetitionTable::default();
+        super::expand(tree, node_index, board, &mut repetitions, random)
+    }
+
     #[test]
     fn it_does_not_expand_a_conclusive_node() {
         let mut tree = Tree::new(Board::new());
-        tree.get_node_mut(0).score.wins_white = 1;
+        tree.get_score_mut(0).wins_white = 1;
         tree.get_node_mut(0).evaluation = BoardEvaluation::WinWhite;
         let mut random = Random::from_seed(111);
         assert_eq!(
@@ -80,23 +124,90 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
-    #[cfg(debug_assertions)]
-    fn it_panics_when_trying_to_expand_a_node_with_children() {
+    fn it_does_not_expand_a_fully_expanded_node() {
         let mut tree = Tree::new(Board::new());
         let mut random = Random::from_seed(111);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
-        tree.get_node_mut(0).score.wins_white = 1;
-        expand(&mut tree, 0, Board::new(), &mut random);
+        tree.set_untried_moves(0, &[]);
+        tree.get_score_mut(0).wins_white = 1;
+        assert_eq!(
+            expand(&mut tree, 0, Board::new(), &mut random),
+            ExpansionResult {
+                board: Board::new(),
+                node_index: 0
+            },
+        );
+        assert_eq!(tree.get_size(), 2);
+    }
+
+    #[test]
+    fn it_returns_an_inconclusive_node_without_moves_unexpanded() {
+        let board = Board::from_fen_with_variant(
+            "7k/6Q1/6K1/8/8/8/8/8 b - - 0 1",
+            Variant::Standard,
+        );
+        let mut tree = Tree::new(board.clone());
+        let mut random = Random::from_seed(111);
+        tree.get_score_mut(0).wins_white = 1;
+        assert_eq!(
+            expand(&mut tree, 0, board.clone(), &mut random),
+            ExpansionResult {
+                board,
+                node_index: 0
+            },
+        );
+        assert_eq!(tree.get_size(), 1);
+        assert!(tree.get_root().is_fully_expanded());
     }
 
     #[test]
     fn it_expands_a_node_with_exactly_one_visit() {
         let mut tree = Tree::new(Board::new());
         let mut random = Random::from_seed(111);
-        tree.get_node_mut(0).score.wins_white = 1;
+        tree.get_score_mut(0).wins_white = 1;
         expand(&mut tree, 0, Board::new(), &mut random);
-        assert_eq!(tree.get_size(), 21);
+        assert_eq!(tree.get_root().get_child_indices(), 1..2);
+        assert_eq!(tree.get_untried_moves(0).len(), 19);
+    }
+
+    #[test]
+    fn it_adds_one_child_per_expansion_until_fully_expanded() {
+        let mut tree = Tree::new(Board::new());
+        let mut random = Random::from_seed(111);
+        tree.get_score_mut(0).wins_white = 1;
+        for i in 1..=20 {
+            let result = expand(&mut tree, 0, Board::new(), &mut random);
+            assert_eq!(result.node_index, i);
+            assert_eq!(tree.get_root().get_child_indices(), 1..i + 1);
+        }
+        assert!(tree.get_node(0).is_fully_expanded());
+        assert_eq!(tree.get_node(0).get_child_indices().count(), 20);
+    }
+
+    #[test]
+    fn it_waits_for_the_expansion_visits() {
+        let mut tree = Tree::new(Board::new());
+        let mut random = Random::from_seed(111);
+        tree.set_expansion_visits(3);
+        tree.get_score_mut(0).wins_white = 2;
+        expand(&mut tree, 0, Board::new(), &mut random);
+        assert_eq!(tree.get_size(), 1);
+
+        tree.get_score_mut(0).wins_white = 3;
+        expand(&mut tree, 0, Board::new(), &mut random);
+        assert_eq!(tree.get_root().get_child_indices(), 1..2);
+    }
+
+    #[test]
+    fn it_adds_as_many_children_as_the_expansion_width() {
+        let mut tree = Tree::new(Board::new());
+        let mut random = Random::from_seed(111);
+        tree.set_expansion_width(8);
+        tree.get_score_mut(0).wins_white = 1;
+        let result = expand(&mut tree, 0, Board::new(), &mut random);
+        assert_eq!(result.node_index, 1);
+        assert_eq!(tree.get_root().get_child_indices(), 1..9);
+        assert_eq!(tree.get_untried_moves(0).len(), 12);
     }
 
     #[test]
@@ -107,7 +218,7 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(E2, E3), 0);
         tree.add_node(Board::new(), Move::from_to(F7, F6), 1);
         tree.add_node(Board::new(), Move::from_to(D1, H5), 2);
-        tree.get_node_mut(3).score.wins_white = 1;
+        tree.get_score_mut(3).wins_white = 1;
 
         assert_eq!(
             expand(
@@ -126,4 +237,24 @@ mod test {
 
         assert_eq!(tree.get_size(), 5);
     }
+
+    #[test]
+    fn it_adds_the_position_of_the_new_child_to_the_repetitions() {
+        let mut tree = Tree::new(Board::new());
+        let mut repetitions = tree.get_repetitions().clone();
+        let mut random = Random::from_seed(111);
+        tree.get_score_mut(0).wins_white = 1;
+
+        let result = super::expand(
+            &mut tree,
+            0,
+            Board::new(),
+            &mut repetitions,
+            &mut random,
+        );
+
+        let mut expected = tree.get_repetitions().clone();
+        expected.push(result.board.get_hash(), result.board.halfmove);
+        assert_eq!(repetitions, expected);
+    }
 }
@@ -2,18 +2,20 @@ use std::cmp::Ordering;
 
 use crate::model::board_evaluation::BoardEvaluation;
 use crate::model::color::Color;
+use crate::model::r#move::Move;
 use crate::model::tree::Tree;
 use crate::model::tree_node_metrics::TreeNodeMetrics;
+use crate::model::types::TreeNodeIndex;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
 
 pub fn get_search_result(tree: &Tree) -> Vec<TreeNodeMetrics> {
     let root_node = tree.get_root();
     let our_color = root_node.our_color;
 
-    let mut metrics: Vec<TreeNodeMetrics> = root_node
-        .child_indices
-        .iter()
-        .map(|c| tree.get_node(*c))
-        .map(TreeNodeMetrics::from)
+    let mut metrics: Vec<TreeNodeMetrics> = tree
+        .get_node(TREE_NODE_ROOT_INDEX)
+        .get_child_indices()
+        .map(|c| get_node_metrics(tree, c))
         .collect();
 
     if our_color == Color::Black {
@@ -25,6 +27,98 @@ pub fn get_search_result(tree: &Tree) -> Vec<TreeNodeMetrics> {
     metrics
 }
 
+pub fn get_node_metrics(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+) -> TreeNodeMetrics {
+    let node = tree.get_node(node_index);
+    TreeNodeMetrics {
+        score: tree.get_score(node_index).clone(),
+        last_move: node.get_last_move(),
+        evaluation: node.evaluation,
+        pv: get_principal_variation_moves(tree, node_index),
+    }
+}
+
+/// Walks down the tree starting at (and including) the given node, always
+/// following the best child, see `get_best_child_index`.
+pub fn get_principal_variation(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+) -> Vec<TreeNodeIndex> {
+    let mut index = node_index;
+    let mut indices = vec![index];
+
+    while let Some(best_child_index) = get_best_child_index(tree, index) {
+        index = best_child_index;
+        indices.push(index);
+    }
+
+    indices
+}
+
+/// Same as `get_principal_variation` but returns the moves leading to the
+/// nodes. The root node has no last move, so it is skipped.
+pub fn get_principal_variation_moves(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+) -> Vec<Move> {
+    get_principal_variation(tree, node_index)
+        .into_iter()
+        .filter(|i| tree.get_node(*i).parent_index.is_some())
+        .map(|i| tree.get_node(i).get_last_move())
+        .collect()
+}
+
+/// The best child is a proven win for the player to move, otherwise the most
+/// visited child which is not a proven loss. Only if all children are proven
+/// losses the most visited of them is chosen.
+pub fn get_best_child_index(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+) -> Option<TreeNodeIndex> {
+    let node = tree.get_node(node_index);
+    let our_color = node.our_color;
+
+    let proven_win = tree.get_node(node_index).get_child_indices().find(|c| {
+        tree.get_node(*c).evaluation.get_win_color() == Some(our_color)
+    });
+    if proven_win.is_some() {
+        return proven_win;
+    }
+
+    let not_lost = get_most_visited_index(
+        tree,
+        tree.get_node(node_index).get_child_indices().filter(|c| {
+            let win_color = tree.get_node(*c).evaluation.get_win_color();
+            win_color.is_none() || win_color == Some(our_color)
+        }),
+    );
+    if not_lost.is_some() {
+        return not_lost;
+    }
+
+    get_most_visited_index(tree, tree.get_node(node_index).get_child_indices())
+}
+
+fn get_most_visited_index(
+    tree: &Tree,
+    indices: impl Iterator<Item = TreeNodeIndex>,
+) -> Option<TreeNodeIndex> {
+    let mut best_index = None;
+    let mut best_visits = 0;
+
+    for index in indices {
+        let visits = tree.get_score(index).get_visits();
+        if best_index.is_none() || visits > best_visits {
+            best_index = Some(index);
+            best_visits = visits;
+        }
+    }
+
+    best_index
+}
+
 pub fn compare_black(
     left: &TreeNodeMetrics,
     right: &TreeNodeMetrics,
@@ -70,3 +164,91 @@ pub fn compare_white(
 
     right.score.wins_white.cmp(&left.score.wins_white)
 }
+
+#[cfg(test)]
+mod test {
+    use crate::model::board::Board;
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    //    W    B    W    B
+    //    0 -> 1 -> 3 -> 5
+    //     |    `-> 4
+    //     `-> 2
+    fn build_tree() -> Tree {
+        let mut black_board = Board::new();
+        black_board.swap_color();
+
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(black_board.clone(), Move::from_to(E2, E4), 0);
+        tree.add_node(black_board.clone(), Move::from_to(D2, D4), 0);
+        tree.add_node(Board::new(), Move::from_to(E7, E5), 1);
+        tree.add_node(Board::new(), Move::from_to(C7, C5), 1);
+        tree.add_node(black_board.clone(), Move::from_to(G1, F3), 3);
+
+        tree.get_score_mut(0).wins_white = 10;
+        tree.get_score_mut(1).wins_white = 6;
+        tree.get_score_mut(2).wins_white = 4;
+        tree.get_score_mut(3).wins_white = 2;
+        tree.get_score_mut(4).wins_white = 3;
+        tree.get_score_mut(5).wins_white = 1;
+
+        tree
+    }
+
+    #[test]
+    fn it_follows_the_most_visited_children() {
+        let tree = build_tree();
+        assert_eq!(get_principal_variation(&tree, 0), vec![0, 1, 4]);
+        assert_eq!(get_principal_variation(&tree, 3), vec![3, 5]);
+        assert_eq!(get_principal_variation(&tree, 2), vec![2]);
+    }
+
+    #[test]
+    fn it_follows_proven_wins_for_the_player_to_move() {
+        let mut tree = build_tree();
+        tree.get_node_mut(3).evaluation = BoardEvaluation::WinBlack;
+        assert_eq!(get_principal_variation(&tree, 0), vec![0, 1, 3, 5]);
+    }
+
+    #[test]
+    fn it_avoids_proven_losses_for_the_player_to_move() {
+        let mut tree = build_tree();
+        tree.get_node_mut(1).evaluation = BoardEvaluation::WinBlack;
+        assert_eq!(get_principal_variation(&tree, 0), vec![0, 2]);
+    }
+
+    #[test]
+    fn it_chooses_the_most_visited_loss_if_all_children_are_lost() {
+        let mut tree = build_tree();
+        tree.get_node_mut(1).evaluation = BoardEvaluation::WinBlack;
+        tree.get_node_mut(2).evaluation = BoardEvaluation::WinBlack;
+        assert_eq!(get_best_child_index(&tree, 0), Some(1));
+    }
+
+    #[test]
+    fn it_returns_the_principal_variation_moves_without_the_root() {
+        let tree = build_tree();
+        assert_eq!(
+            get_principal_variation_moves(&tree, 0),
+            vec![Move::from_to(E2, E4), Move::from_to(C7, C5)]
+        );
+        assert_eq!(
+            get_principal_variation_moves(&tree, 1),
+            vec![Move::from_to(E2, E4), Move::from_to(C7, C5)]
+        );
+    }
+
+    #[test]
+    fn it_attaches_the_principal_variation_to_the_search_result() {
+        let tree = build_tree();
+        let metrics = get_search_result(&tree);
+        assert_eq!(metrics.len(), 2);
+        assert_eq!(
+            metrics[0].pv,
+            vec![Move::from_to(E2, E4), Move::from_to(C7, C5)]
+        );
+        assert_eq!(metrics[1].pv, vec![Move::from_to(D2, D4)]);
+    }
+}
@@ -1,9 +1,21 @@
+use std::time::Instant;
+
 use crate::common::random::Random;
+use crate::common::tree_file::load_tree;
+use crate::common::tree_file::save_tree;
 use crate::model::args::Args;
-use crate::model::board::Board;
+use crate::model::game::Game;
+use crate::model::metrics_level::MetricsLevel;
+use crate::model::playout_policy::PlayoutPolicy;
+use crate::model::r#move::Move;
+use crate::model::selection_result::SelectionResult;
 use crate::model::tree::Tree;
+use crate::model::tree_dump_filter::TreeDumpFilter;
 use crate::model::tree_node_metrics::TreeNodeMetrics;
+use crate::view::dump_tree::dump_tree;
 use crate::view::print_metrics::print_metrics;
+use crate::view::print_metrics::print_result;
+use crate::view::tui::Tui;
 
 use super::backpropagate::backpropagate;
 use super::expand::expand;
@@ -12,28 +24,127 @@ use super::select::select;
 use super::simulate::simulate;
 
 pub fn search(args: Args) -> Vec<TreeNodeMetrics> {
-    let board = Board::from_fen(&args.fen);
-    let mut tree = Tree::new(board);
+    let game = Game::from_args(&args);
+    let board = game.get_board().clone();
+    let mut tree = match &args.load_tree {
+        Some(path) => load_tree(path, &board).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(1)
+        }),
+        None => Tree::new(board),
+    };
+    tree.set_history(game.get_previous_hashes());
+    tree.configure(&args);
     let mut random = Random::from_seed(args.seed);
+    let start_time = Instant::now();
+    let mut tui = (args.metrics_level == MetricsLevel::Tui).then(Tui::start);
+    let mut iterations = 0;
 
     for i in 1..=args.max_iterations {
-        let s = select(&tree, &mut random);
-        let e = expand(&mut tree, s.node_index, s.board, &mut random);
-        let simulation_result =
-            simulate(&tree, e.node_index, e.board, &mut random);
-        backpropagate(&mut tree, e.node_index, simulation_result);
+        if is_out_of_time(&args, start_time) {
+            break;
+        }
+
+        search_iteration(&mut tree, &mut random, args.playout_policy);
+        iterations = i;
+
+        if let Some(tui) = &mut tui {
+            if i.is_multiple_of(args.report_interval)
+                && !tui.update(&tree, i, &args, start_time.elapsed())
+            {
+                break;
+            }
+        } else {
+            print_metrics(&tree, i, &args, start_time.elapsed());
+        }
 
-        print_metrics(&tree, i, &args);
+        if let Some(path) = &args.save_tree {
+            if i.is_multiple_of(args.save_interval) {
+                save_tree(&tree, path);
+            }
+        }
+    }
+
+    if let Some(path) = &args.save_tree {
+        save_tree(&tree, path);
+    }
+
+    if let Some(mut tui) = tui {
+        if iterations == args.max_iterations {
+            tui.wait_for_quit(&tree, iterations, &args, start_time.elapsed());
+        }
+    }
+
+    print_result(&tree, iterations, &args, start_time.elapsed());
+
+    if let Some(path) = &args.dump_tree {
+        let filter = TreeDumpFilter::from(&args);
+        dump_tree(&tree, path, args.dump_format, &filter);
     }
 
     get_search_result(&tree)
 }
 
+/// Searches the current position of the game with the limits of the args and
+/// returns the best move, without printing any metrics.
+pub fn search_best_move(
+    game: &Game,
+    args: &Args,
+    random: &mut Random,
+) -> Option<Move> {
+    let mut tree = Tree::new(game.get_board().clone());
+    tree.set_history(game.get_previous_hashes());
+    tree.configure(args);
+    let start_time = Instant::now();
+
+    for _ in 0..args.max_iterations {
+        if is_out_of_time(args, start_time) {
+            break;
+        }
+        search_iteration(&mut tree, random, args.playout_policy);
+    }
+
+    get_search_result(&tree).first().map(|m| m.last_move)
+}
+
+/// One round of selection, expansion, simulation and backpropagation,
+/// afterwards the tree is pruned if it has grown too large.
+pub fn search_iteration(
+    tree: &mut Tree,
+    random: &mut Random,
+    playout_policy: PlayoutPolicy,
+) {
+    let SelectionResult {
+        board,
+        node_index,
+        mut repetitions,
+    } = select(tree, random);
+    let e = expand(tree, node_index, board, &mut repetitions, random);
+    let simulation_result = simulate(
+        tree,
+        e.node_index,
+        e.board,
+        repetitions,
+        random,
+        playout_policy,
+    );
+    backpropagate(tree, e.node_index, simulation_result);
+    tree.prune_if_full();
+}
+
+/// Whether the time limit of the args, if any, has been reached.
+pub fn is_out_of_time(args: &Args, start_time: Instant) -> bool {
+    args.max_time
+        .is_some_and(|t| start_time.elapsed().as_millis() >= t as u128)
+}
+
 #[cfg(test)]
 mod test {
+    use crate::model::board::Board;
     use crate::model::board_evaluation::BoardEvaluation;
     use crate::model::r#move::Move;
     use crate::model::types::square_names::*;
+    use crate::model::variant::Variant;
 
     use super::*;
 
@@ -41,7 +152,9 @@ mod test {
     fn it_finds_the_mate_in_1() {
         let mut args = Args::default();
         args.fen = String::from("7k/7p/5N1P/8/8/8/2q5/K7 w - - 0 1");
-        args.max_iterations = 8;
+        // children are added one at a time, so every one of the 8 moves
+        // needs an iteration of its own before the mate is certainly tried
+        args.max_iterations = 16;
         let metrics = search(args);
         assert_eq!(metrics[0].last_move, Move::from_to(F6, H7));
         assert_eq!(metrics[0].evaluation, BoardEvaluation::WinWhite);
@@ -68,4 +181,77 @@ mod test {
             BoardEvaluation::WinWhite
         );
     }
+
+    #[test]
+    fn it_detects_repetitions_with_the_moves_before_the_root() {
+        let args = Args {
+            moves: "g1f3 g8f6 f3g1 f6g8 g1f3 g8f6 f3g1"
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            max_iterations: 200,
+            ..Args::default()
+        };
+        let metrics = search(args);
+        let repetition = metrics
+            .iter()
+            .find(|m| m.last_move == Move::from_to(F6, G8))
+            .unwrap();
+        assert_eq!(repetition.evaluation, BoardEvaluation::Draw);
+    }
+
+    #[test]
+    fn it_finds_the_way_to_the_hill() {
+        let args = Args {
+            fen: String::from("7k/8/8/8/2K5/8/8/8 w - - 0 1"),
+            variant: Variant::KingOfTheHill,
+            max_iterations: 100,
+            ..Args::default()
+        };
+        let metrics = search(args);
+        assert!([D4, D5].contains(&metrics[0].last_move.to));
+        assert_eq!(metrics[0].evaluation, BoardEvaluation::WinWhite);
+    }
+
+    #[test]
+    fn it_finds_the_third_check() {
+        let args = Args {
+            fen: String::from("4k3/8/8/8/8/8/8/4K2R w - - 0 1 +2+0"),
+            variant: Variant::ThreeCheck,
+            max_iterations: 100,
+            ..Args::default()
+        };
+        let metrics = search(args);
+        assert_eq!(metrics[0].last_move, Move::from_to(H1, H8));
+        assert_eq!(metrics[0].evaluation, BoardEvaluation::WinWhite);
+    }
+
+    #[test]
+    fn it_gives_away_the_last_piece_in_antichess() {
+        let args = Args {
+            fen: String::from("7r/8/8/8/8/8/8/R7 w - - 0 1"),
+            variant: Variant::Antichess,
+            max_iterations: 300,
+            ..Args::default()
+        };
+        let metrics = search(args);
+        assert!([A8, H1].contains(&metrics[0].last_move.to));
+        assert_eq!(metrics[0].evaluation, BoardEvaluation::WinWhite);
+    }
+
+    #[test]
+    fn it_keeps_searching_when_the_tree_is_pruned() {
+        let mut tree = Tree::new(Board::new());
+        tree.set_max_size(Some(100));
+        let mut random = Random::from_seed(111);
+
+        for _ in 0..1000 {
+            search_iteration(&mut tree, &mut random, PlayoutPolicy::Random);
+            assert!(tree.get_size() <= 100);
+        }
+
+        assert!(tree.get_prunings() > 0);
+        assert_eq!(tree.get_node(0).get_child_indices().count(), 20);
+        assert_eq!(tree.get_score(0).get_visits(), 1000);
+    }
 }
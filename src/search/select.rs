@@ -7,6 +7,7 @@ use crate::move_generator::make_move::make_move;
 pub fn select(tree: &Tree, random: &mut Random) -> SelectionResult {
     let mut best_node_indices = vec![TREE_NODE_ROOT_INDEX];
     let mut board = tree.get_board().clone();
+    let mut repetitions = tree.get_repetitions().clone();
 
     loop {
         let random_best_node_index = *random
@@ -16,25 +17,29 @@ pub fn select(tree: &Tree, random: &mut Random) -> SelectionResult {
         let parent = tree.get_node(random_best_node_index);
         if random_best_node_index != TREE_NODE_ROOT_INDEX {
             // TODO: rewrite accordingly when last_move is option
-            make_move(&mut board, &parent.last_move);
+            make_move(&mut board, &parent.get_last_move());
+            repetitions.push(parent.board_hash, board.halfmove);
         }
-        if parent.child_indices.is_empty() {
+        if !parent.is_fully_expanded() || !parent.has_children() {
             return SelectionResult {
                 board,
                 node_index: random_best_node_index,
+                repetitions,
             };
         }
 
-        let mut best_uct = std::u32::MIN;
+        let mut best_uct = u32::MIN;
         best_node_indices = Vec::new();
 
-        for node_index in &parent.child_indices {
-            let uct = tree.calculate_uct(*node_index);
+        for node_index in
+            tree.get_node(random_best_node_index).get_child_indices()
+        {
+            let uct = tree.calculate_uct(node_index);
             if uct == best_uct {
-                best_node_indices.push(*node_index);
+                best_node_indices.push(node_index);
             } else if uct > best_uct {
                 best_uct = uct;
-                best_node_indices = vec![*node_index];
+                best_node_indices = vec![node_index];
             }
         }
     }
@@ -45,10 +50,22 @@ mod test {
     use crate::model::board::Board;
     use crate::model::board_evaluation::BoardEvaluation;
     use crate::model::r#move::Move;
+    use crate::model::repetition_table::RepetitionTable;
     use crate::model::types::square_names::*;
+    use crate::model::types::TreeNodeIndex;
+    use crate::move_generator::legal_moves::find_legal_move;
 
     use super::*;
 
+    /// Marks every node with children as fully expanded.
+    fn set_fully_expanded(tree: &mut Tree) {
+        for i in 0..tree.get_size() as TreeNodeIndex {
+            if tree.get_node(i).has_children() {
+                tree.set_untried_moves(i, &[]);
+            }
+        }
+    }
+
     #[test]
     fn it_selects_and_returns_the_starting_board_when_tree_is_empty() {
         let tree = Tree::new(Board::new());
@@ -59,6 +76,7 @@ mod test {
             SelectionResult {
                 board: Board::new(),
                 node_index: TREE_NODE_ROOT_INDEX,
+                repetitions: tree.get_repetitions().clone(),
             }
         );
     }
@@ -72,16 +90,62 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(F7, F6), 1);
         tree.add_node(Board::new(), Move::from_to(E2, E3), 2);
         tree.add_node(Board::new(), Move::from_to(E7, E6), 3);
+        set_fully_expanded(&mut tree);
 
         assert_eq!(
             select(&tree, &mut random),
             SelectionResult {
                 board: Board::from_fen("rnbqkbnr/pppp2pp/4pp2/8/8/4PN2/PPPP1PPP/RNBQKB1R w KQkq - 0 3"),
                 node_index: 4,
+                // the nodes were added with the hash of the starting board
+                repetitions: RepetitionTable::new(&[Board::new().get_hash()], 0),
             },
         );
     }
 
+    #[test]
+    fn it_collects_the_positions_along_the_selected_path() {
+        let mut board = Board::new();
+        let mut tree = Tree::new(board.clone());
+        let mut random = Random::from_seed(111);
+
+        let mut parent = TREE_NODE_ROOT_INDEX;
+        for uci in ["g1f3", "g8f6", "f3g1", "f6g8"].repeat(2) {
+            let m = find_legal_move(&mut board, uci).unwrap();
+            make_move(&mut board, &m);
+            tree.add_node(board.clone(), m, parent);
+            parent = tree.get_node(parent).get_child_indices().start;
+        }
+        set_fully_expanded(&mut tree);
+
+        let result = select(&tree, &mut random);
+        assert_eq!(result.node_index, parent);
+        assert!(result.repetitions.is_threefold());
+    }
+
+    #[test]
+    fn it_collects_the_positions_of_the_game_before_the_root() {
+        // the knights went out and back once before the root
+        let mut board = Board::from_fen(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 4 3",
+        );
+        let mut tree = Tree::new(board.clone());
+        tree.set_history(&[board.get_hash(), 1, 2, 3]);
+        let mut random = Random::from_seed(111);
+
+        let mut parent = TREE_NODE_ROOT_INDEX;
+        for uci in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            let m = find_legal_move(&mut board, uci).unwrap();
+            make_move(&mut board, &m);
+            tree.add_node(board.clone(), m, parent);
+            parent = tree.get_node(parent).get_child_indices().start;
+        }
+        set_fully_expanded(&mut tree);
+
+        assert!(!tree.get_repetitions().is_threefold());
+        assert!(select(&tree, &mut random).repetitions.is_threefold());
+    }
+
     #[test]
     fn it_selects_the_root_not_in_an_otherwise_empty_tree() {
         let tree = Tree::new(Board::new());
@@ -97,11 +161,12 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
+        set_fully_expanded(&mut tree);
 
-        tree.get_node_mut(0).score.wins_white = 1;
-        tree.get_node_mut(0).score.wins_black = 1;
-        tree.get_node_mut(1).score.wins_white = 1;
-        tree.get_node_mut(3).score.wins_black = 1;
+        tree.get_score_mut(0).wins_white = 1;
+        tree.get_score_mut(0).wins_black = 1;
+        tree.get_score_mut(1).wins_white = 1;
+        tree.get_score_mut(3).wins_black = 1;
 
         assert_eq!(select(&tree, &mut random).node_index, 2);
     }
@@ -115,19 +180,20 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
+        set_fully_expanded(&mut tree);
 
-        tree.get_node_mut(0).score.wins_white = 1;
-        tree.get_node_mut(0).score.wins_black = 1;
-        tree.get_node_mut(1).score.wins_white = 1;
-        tree.get_node_mut(3).score.wins_black = 1;
+        tree.get_score_mut(0).wins_white = 1;
+        tree.get_score_mut(0).wins_black = 1;
+        tree.get_score_mut(1).wins_white = 1;
+        tree.get_score_mut(3).wins_black = 1;
 
-        assert_eq!(select(&tree, &mut random).node_index, 4);
         assert_eq!(select(&tree, &mut random).node_index, 4);
         assert_eq!(select(&tree, &mut random).node_index, 2);
         assert_eq!(select(&tree, &mut random).node_index, 4);
         assert_eq!(select(&tree, &mut random).node_index, 4);
-        assert_eq!(select(&tree, &mut random).node_index, 4);
         assert_eq!(select(&tree, &mut random).node_index, 2);
+        assert_eq!(select(&tree, &mut random).node_index, 2);
+        assert_eq!(select(&tree, &mut random).node_index, 4);
     }
 
     #[test]
@@ -140,13 +206,14 @@ mod test {
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
+        set_fully_expanded(&mut tree);
 
-        tree.get_node_mut(0).score.wins_white = 1;
-        tree.get_node_mut(0).score.wins_black = 2;
+        tree.get_score_mut(0).wins_white = 1;
+        tree.get_score_mut(0).wins_black = 2;
 
-        tree.get_node_mut(1).score.wins_black = 1;
-        tree.get_node_mut(2).score.wins_white = 1;
-        tree.get_node_mut(3).score.wins_black = 1;
+        tree.get_score_mut(1).wins_black = 1;
+        tree.get_score_mut(2).wins_white = 1;
+        tree.get_score_mut(3).wins_black = 1;
 
         assert_eq!(select(&tree, &mut random).node_index, 2);
     }
@@ -161,14 +228,15 @@ mod test {
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
         tree.add_node(black_board.clone(), Move::from_to(0, 0), 0);
+        set_fully_expanded(&mut tree);
 
-        tree.get_node_mut(0).score.wins_white = 1;
-        tree.get_node_mut(0).score.wins_black = 2;
+        tree.get_score_mut(0).wins_white = 1;
+        tree.get_score_mut(0).wins_black = 2;
 
-        tree.get_node_mut(1).score.wins_black = 1;
-        tree.get_node_mut(2).score.wins_white = 1;
+        tree.get_score_mut(1).wins_black = 1;
+        tree.get_score_mut(2).wins_white = 1;
         tree.get_node_mut(2).evaluation = BoardEvaluation::WinWhite;
-        tree.get_node_mut(3).score.wins_black = 1;
+        tree.get_score_mut(3).wins_black = 1;
 
         assert_eq!(select(&tree, &mut random).node_index, 2);
     }
@@ -181,18 +249,19 @@ mod test {
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
         tree.add_node(Board::new(), Move::from_to(0, 0), 0);
+        set_fully_expanded(&mut tree);
 
-        tree.get_node_mut(0).score.wins_white = 1;
-        tree.get_node_mut(0).score.wins_black = 2;
+        tree.get_score_mut(0).wins_white = 1;
+        tree.get_score_mut(0).wins_black = 2;
 
-        tree.get_node_mut(1).score.wins_black = 1;
-        tree.get_node_mut(2).score.wins_black = 1;
-        tree.get_node_mut(3).score.wins_black = 1;
+        tree.get_score_mut(1).wins_black = 1;
+        tree.get_score_mut(2).wins_black = 1;
+        tree.get_score_mut(3).wins_black = 1;
 
-        assert_eq!(select(&tree, &mut random).node_index, 1);
+        assert_eq!(select(&tree, &mut random).node_index, 3);
         assert_eq!(select(&tree, &mut random).node_index, 2);
-        assert_eq!(select(&tree, &mut random).node_index, 1);
         assert_eq!(select(&tree, &mut random).node_index, 2);
         assert_eq!(select(&tree, &mut random).node_index, 3);
+        assert_eq!(select(&tree, &mut random).node_index, 2);
     }
 }
@@ -3,17 +3,25 @@ use crate::evaluation::evaluate_board::evaluate_board;
 use crate::model::board::Board;
 use crate::model::board_evaluation::BoardEvaluation;
 use crate::model::board_evaluation_result::BoardEvaluationResult;
+use crate::model::playout_policy::PlayoutPolicy;
+use crate::model::r#move::Move;
+use crate::model::repetition_table::RepetitionTable;
 use crate::model::simulation_result::SimulationResult;
 use crate::model::tree::Tree;
 use crate::model::types::TreeNodeIndex;
+use crate::move_generator::legal_moves::generate_capture;
 use crate::move_generator::legal_moves::generate_move;
 use crate::move_generator::make_move::make_move;
 
+/// Plays moves from the board of the node until the game is decided. The
+/// repetitions hold the positions of the game up to the node.
 pub fn simulate(
     tree: &Tree,
     node_index: TreeNodeIndex,
     board: Board,
+    repetitions: RepetitionTable,
     random: &mut Random,
+    playout_policy: PlayoutPolicy,
 ) -> SimulationResult {
     // TODO: should be easy to parallelize this
 
@@ -26,18 +34,18 @@ pub fn simulate(
         };
     }
 
-    debug_assert!(node.is_not_visited());
-
     let mut board = board;
-    let mut board_hashes = get_principal_variation_hashes(tree, node_index);
-    let mut last_board_hash = node.board_hash;
+    let mut repetitions = repetitions;
     let mut depth = 0;
 
     loop {
         debug_assert!(depth < 1000);
 
-        if has_three_duplicates(&board_hashes, last_board_hash) {
-            board.draw_by_repetition = true;
+        if repetitions.is_threefold() {
+            return SimulationResult {
+                depth,
+                evaluation: BoardEvaluation::Draw,
+            };
         }
 
         let BoardEvaluationResult {
@@ -48,104 +56,57 @@ pub fn simulate(
             return SimulationResult { depth, evaluation };
         }
 
-        // TODO: unwrap_or_else???
-        let random_move = random_move
-            .unwrap_or_else(|| generate_move(&mut board, random).unwrap());
-        make_move(&mut board, &random_move);
-        last_board_hash = board.get_hash();
-        board_hashes.push(last_board_hash);
+        let playout_move = choose_playout_move(
+            &mut board,
+            random,
+            random_move,
+            playout_policy,
+        );
+        make_move(&mut board, &playout_move);
+        repetitions.push(board.get_hash(), board.halfmove);
         depth += 1;
     }
 }
 
-fn get_principal_variation_hashes(
-    tree: &Tree,
-    node_index: TreeNodeIndex,
-) -> Vec<u64> {
-    let mut index = node_index;
-    let mut hashes = Vec::new();
-
-    loop {
-        let node = tree.get_node(index);
-        hashes.push(node.board_hash);
-
-        let Some(parent) = node.parent_index else {
-            return hashes;
-        };
-        index = parent;
-    }
-}
-
-fn has_three_duplicates(list: &Vec<u64>, check_value: u64) -> bool {
-    let mut strikes = 0;
-    for item in list {
-        if *item == check_value {
-            strikes += 1;
-        }
-
-        if strikes >= 3 {
-            return true;
+fn choose_playout_move(
+    board: &mut Board,
+    random: &mut Random,
+    random_move: Option<Move>,
+    playout_policy: PlayoutPolicy,
+) -> Move {
+    if playout_policy == PlayoutPolicy::CapturesFirst {
+        if let Some(capture) = generate_capture(board, random) {
+            return capture;
         }
     }
 
-    false
+    // TODO: unwrap_or_else???
+    random_move.unwrap_or_else(|| generate_move(board, random).unwrap())
 }
 
 #[cfg(test)]
 mod test {
     use crate::model::board::Board;
-    use crate::model::r#move::Move;
+    use crate::model::types::square_names::*;
 
     use super::*;
 
-    #[test]
-    fn it_gets_all_principal_variation_hashes() {
-        let mut tree = Tree::new(Board::new());
-        tree.add_node(Board::new(), Move::from_to(0, 0), 0);
-        tree.add_node(Board::new(), Move::from_to(0, 0), 1);
-        tree.add_node(Board::new(), Move::from_to(0, 0), 2);
-
-        tree.get_node_mut(0).board_hash = 2;
-        tree.get_node_mut(1).board_hash = 3;
-        tree.get_node_mut(2).board_hash = 5;
-        tree.get_node_mut(3).board_hash = 7;
-
-        let hashes = get_principal_variation_hashes(&tree, 3);
-
-        assert_eq!(hashes.len(), 4);
-        assert!(hashes.contains(&2));
-        assert!(hashes.contains(&3));
-        assert!(hashes.contains(&5));
-        assert!(hashes.contains(&7));
-    }
-
-    #[test]
-    fn it_finds_three_duplicates_in_a_list() {
-        let list = vec![1, 2, 3, 5, 65, 3, 35, 5, 3, 8];
-        assert!(has_three_duplicates(&list, 3));
-    }
-
-    #[test]
-    fn it_does_not_find_three_duplicates_in_an_empty_list() {
-        let list = vec![];
-        assert!(!has_three_duplicates(&list, 5));
-    }
-
-    #[test]
-    fn it_does_not_find_three_duplicates_in_a_list() {
-        let list = vec![1, 2, 3, 5, 65, 3, 35, 5, 3, 8];
-        assert!(!has_three_duplicates(&list, 5));
-    }
-
     #[test]
     fn it_simulates_moves_from_the_starting_position() {
         let tree = Tree::new(Board::new());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, Board::new(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            Board::new(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
-        assert_eq!(result.depth, 12);
-        assert_eq!(result.evaluation, BoardEvaluation::WinBlack);
+        assert_eq!(result.depth, 65);
+        assert_eq!(result.evaluation, BoardEvaluation::WinWhite);
 
         // TODO: remove
         //let mut random = Random::from_seed(999);
@@ -164,13 +125,39 @@ mod test {
         //println!("white: {}, black: {}, draws: {}", white_wins, black_wins, draws);
     }
 
+    #[test]
+    fn it_prefers_captures_in_captures_first_playouts() {
+        // the rook taking the pawn is the only capture
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/p7/R3K3 w - - 0 1");
+        let mut random = Random::from_seed(0);
+
+        for _ in 0..10 {
+            assert_eq!(
+                choose_playout_move(
+                    &mut board,
+                    &mut random,
+                    None,
+                    PlayoutPolicy::CapturesFirst
+                ),
+                Move::from_to(A1, A2)
+            );
+        }
+    }
+
     #[test]
     fn it_simulates_moves_for_a_board_with_forced_stalemate() {
         let board = Board::from_fen("kb6/p1p5/P1P4p/8/7p/7P/8/2K5 w - - 0 1");
         let tree = Tree::new(board.clone());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, board.clone(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            board.clone(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
         assert_eq!(result.depth, 3);
         assert_eq!(result.evaluation, BoardEvaluation::Draw);
@@ -184,7 +171,14 @@ mod test {
         let tree = Tree::new(board.clone());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, board.clone(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            board.clone(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
         assert_eq!(result.depth, 3);
         assert_eq!(result.evaluation, BoardEvaluation::WinWhite);
@@ -197,7 +191,14 @@ mod test {
         let tree = Tree::new(board.clone());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, board.clone(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            board.clone(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
         assert_eq!(result.depth, 2);
         assert_eq!(result.evaluation, BoardEvaluation::Draw);
@@ -209,12 +210,40 @@ mod test {
         let tree = Tree::new(board.clone());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, board.clone(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            board.clone(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
         assert_eq!(result.depth, 5);
         assert_eq!(result.evaluation, BoardEvaluation::Draw);
     }
 
+    #[test]
+    fn it_simulates_a_draw_for_a_node_repeating_the_game_for_the_third_time() {
+        let board = Board::new();
+        let tree = Tree::new(board.clone());
+        let hash = board.get_hash();
+        let repetitions = RepetitionTable::new(&[hash, 1, hash, 2, hash], 4);
+        let mut random = Random::from_seed(0);
+
+        let result = simulate(
+            &tree,
+            0,
+            board,
+            repetitions,
+            &mut random,
+            PlayoutPolicy::Random,
+        );
+
+        assert_eq!(result.depth, 0);
+        assert_eq!(result.evaluation, BoardEvaluation::Draw);
+    }
+
     #[test]
     fn it_simulates_moves_for_a_board_with_draw_because_of_threefold_repetition(
     ) {
@@ -224,7 +253,14 @@ mod test {
         let tree = Tree::new(board.clone());
         let mut random = Random::from_seed(0);
 
-        let result = simulate(&tree, 0, board.clone(), &mut random);
+        let result = simulate(
+            &tree,
+            0,
+            board.clone(),
+            tree.get_repetitions().clone(),
+            &mut random,
+            PlayoutPolicy::Random,
+        );
 
         assert_eq!(result.depth, 9);
         assert_eq!(result.evaluation, BoardEvaluation::Draw);
@@ -0,0 +1,28 @@
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+
+/// The side to move wins as soon as it has lost all of its pieces.
+pub fn evaluate_terminal(board: &Board) -> Option<BoardEvaluation> {
+    let has_pieces = board
+        .pieces
+        .squares
+        .data
+        .iter()
+        .flatten()
+        .any(|p| p.get_color() == board.our_color);
+
+    if has_pieces {
+        return None;
+    }
+
+    Some(evaluate_stalemate(board))
+}
+
+/// The side to move wins if it cannot move.
+pub fn evaluate_stalemate(board: &Board) -> BoardEvaluation {
+    match board.our_color {
+        Color::Black => BoardEvaluation::WinBlack,
+        Color::White => BoardEvaluation::WinWhite,
+    }
+}
@@ -0,0 +1,100 @@
+use crate::bitboards::r#move::attack_maps::AttackMaps;
+use crate::bitboards::r#move::attacks::KING_PSEUDO_ATTACKS;
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::piece_type::PieceType;
+use crate::model::r#move::Move;
+use crate::model::types::SquareIndex;
+use crate::model::types::SQUARE_NEIGHBORHOODS;
+
+/// The capturing and the captured piece are removed together with all
+/// pieces but pawns next to the capture square.
+pub fn resolve_capture(board: &mut Board, m: &Move) {
+    board.pieces.remove(m.from);
+    board.pieces.remove(m.to);
+
+    for blast in SQUARE_NEIGHBORHOODS[m.to as usize] {
+        let Some(blast) = blast else {
+            break;
+        };
+        if let Some(piece) = board.pieces.squares.data[blast as usize] {
+            if piece.is_king() {
+                board.castle.remove_rights(piece.get_color());
+            }
+            if !piece.is_pawn() {
+                board.pieces.remove(blast);
+            }
+        }
+    }
+}
+
+/// Kings cannot capture, so they attack nothing. A king touching the
+/// opponent's king cannot be attacked at all, because capturing it would
+/// explode both kings.
+pub fn is_square_attacked(
+    maps: &AttackMaps,
+    square: SquareIndex,
+    by: Color,
+) -> bool {
+    let their_kings = maps.kings & maps.colors[by as usize];
+
+    if their_kings.is_empty() {
+        // king might have exploded already
+        return false;
+    }
+
+    if !(KING_PSEUDO_ATTACKS[square as usize] & their_kings).is_empty() {
+        return false;
+    }
+
+    maps.is_square_attacked(square, by)
+}
+
+/// The game is lost as soon as the own king has exploded.
+pub fn evaluate_terminal(board: &Board) -> Option<BoardEvaluation> {
+    if !board.get_our_pieces(PieceType::King).is_empty() {
+        return None;
+    }
+
+    Some(match board.our_color {
+        Color::Black => BoardEvaluation::WinWhite,
+        Color::White => BoardEvaluation::WinBlack,
+    })
+}
+
+/// Draws with kk, kkb, kkn and kkr.
+pub fn is_insufficient_material(board_with_2_kings: &Board) -> bool {
+    // TODO: this function assumes that the board has 2 kings on the board
+    let count =
+        |piece_type| board_with_2_kings.get_our_pieces(piece_type).count();
+
+    if count(PieceType::Pawn) + count(PieceType::Queen) > 0 {
+        return false;
+    }
+
+    if count(PieceType::Bishop)
+        + count(PieceType::Knight)
+        + count(PieceType::Rook)
+        > 1
+    {
+        return false;
+    }
+
+    let mut total_pieces = 0;
+    let mut total_nbr_pieces = 0;
+    for i in 0..64 {
+        if let Some(piece) = board_with_2_kings.pieces.squares.data[i] {
+            total_pieces += 1;
+            if piece.is_bishop() || piece.is_knight() || piece.is_rook() {
+                total_nbr_pieces += 1;
+            }
+        }
+    }
+
+    if total_pieces <= 3 && total_nbr_pieces <= 1 {
+        return true;
+    }
+
+    false
+}
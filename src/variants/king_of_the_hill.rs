@@ -0,0 +1,52 @@
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::types::square_names::*;
+
+const HILL: [u8; 4] = [D4, E4, D5, E5];
+
+/// The side whose king reached one of the centre squares wins.
+pub fn evaluate_terminal(board: &Board) -> Option<BoardEvaluation> {
+    HILL.iter().find_map(|square| {
+        let piece = board.pieces.squares.data[*square as usize]?;
+        if !piece.is_king() {
+            return None;
+        }
+
+        Some(match piece.get_color() {
+            Color::Black => BoardEvaluation::WinBlack,
+            Color::White => BoardEvaluation::WinWhite,
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::variant::Variant;
+
+    use super::*;
+
+    fn evaluate(fen: &str) -> Option<BoardEvaluation> {
+        evaluate_terminal(&Board::from_fen_with_variant(
+            fen,
+            Variant::KingOfTheHill,
+        ))
+    }
+
+    #[test]
+    fn it_evaluates_a_king_on_the_hill_as_win() {
+        assert_eq!(
+            evaluate("8/8/8/3K4/8/8/8/7k b - - 0 1"),
+            Some(BoardEvaluation::WinWhite)
+        );
+        assert_eq!(
+            evaluate("7K/8/8/8/4k3/8/8/8 w - - 0 1"),
+            Some(BoardEvaluation::WinBlack)
+        );
+    }
+
+    #[test]
+    fn it_evaluates_other_pieces_on_the_hill_as_no_game_end() {
+        assert_eq!(evaluate("k7/8/8/3QQ3/3QQ3/8/8/K7 b - - 0 1"), None);
+    }
+}
@@ -0,0 +1,40 @@
+use crate::bitboards::r#move::attack_maps::AttackMaps;
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::r#move::Move;
+use crate::model::types::SquareIndex;
+
+/// The capturing piece replaces the captured one.
+pub fn resolve_capture(board: &mut Board, m: &Move) {
+    board.pieces.move_piece(m.from, m.to);
+}
+
+/// Every piece attacks, the king included.
+pub fn is_square_attacked(
+    maps: &AttackMaps,
+    square: SquareIndex,
+    by: Color,
+) -> bool {
+    !(maps.attackers_to(square, maps.occupancy) & maps.colors[by as usize])
+        .is_empty()
+}
+
+/// Games only end by checkmate, stalemate and the draw rules.
+pub fn evaluate_terminal(_board: &Board) -> Option<BoardEvaluation> {
+    None
+}
+
+/// Draws with kk, kkb and kkn.
+pub fn is_insufficient_material(board: &Board) -> bool {
+    let mut total_pieces = 0;
+    let mut total_minor_pieces = 0;
+    for piece in board.pieces.squares.data.iter().flatten() {
+        total_pieces += 1;
+        if piece.is_bishop() || piece.is_knight() {
+            total_minor_pieces += 1;
+        }
+    }
+
+    total_pieces == 2 || (total_pieces == 3 && total_minor_pieces == 1)
+}
@@ -0,0 +1,97 @@
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+
+const CHECKS_TO_WIN: u8 = 3;
+
+/// The side which gave check for the third time wins.
+pub fn evaluate_terminal(board: &Board) -> Option<BoardEvaluation> {
+    if board.checks_given[Color::White as usize] >= CHECKS_TO_WIN {
+        return Some(BoardEvaluation::WinWhite);
+    }
+    if board.checks_given[Color::Black as usize] >= CHECKS_TO_WIN {
+        return Some(BoardEvaluation::WinBlack);
+    }
+
+    None
+}
+
+/// Only bare kings cannot give check.
+pub fn is_insufficient_material(board: &Board) -> bool {
+    board.pieces.squares.data.iter().flatten().count() == 2
+}
+
+/// Parses the check counter suffix of a FEN, e.g. `+1+0` for one check
+/// given by white and none by black.
+pub fn parse_checks_given(field: &str) -> Result<[u8; 2], String> {
+    let counts: Vec<u8> = field
+        .split('+')
+        .skip(1)
+        .map(|c| {
+            c.parse().map_err(|_| {
+                String::from("Invalid FEN: check counter is no number")
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    if !field.starts_with('+') || counts.len() != 2 {
+        return Err(String::from(
+            "Invalid FEN: check counters must look like '+1+0'",
+        ));
+    }
+
+    let mut checks_given = [0; 2];
+    checks_given[Color::White as usize] = counts[0];
+    checks_given[Color::Black as usize] = counts[1];
+    Ok(checks_given)
+}
+
+pub fn format_checks_given(checks_given: &[u8; 2]) -> String {
+    format!(
+        "+{}+{}",
+        checks_given[Color::White as usize],
+        checks_given[Color::Black as usize]
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::variant::Variant;
+
+    use super::*;
+
+    fn from_fen(fen: &str) -> Board {
+        Board::from_fen_with_variant(fen, Variant::ThreeCheck)
+    }
+
+    #[test]
+    fn it_evaluates_the_third_check_as_win() {
+        let board = from_fen("4k3/8/8/8/8/8/8/4KQ2 w - - 0 1 +2+3");
+        assert_eq!(evaluate_terminal(&board), Some(BoardEvaluation::WinBlack));
+
+        let board = from_fen("4k3/8/8/8/8/8/8/4KQ2 w - - 0 1 +2+2");
+        assert_eq!(evaluate_terminal(&board), None);
+    }
+
+    #[test]
+    fn it_parses_and_formats_check_counters() {
+        let checks_given = parse_checks_given("+2+1").unwrap();
+        assert_eq!(checks_given[Color::White as usize], 2);
+        assert_eq!(checks_given[Color::Black as usize], 1);
+        assert_eq!(format_checks_given(&checks_given), "+2+1");
+    }
+
+    #[test]
+    fn it_rejects_malformed_check_counters() {
+        assert_eq!(
+            parse_checks_given("2+1"),
+            Err(String::from(
+                "Invalid FEN: check counters must look like '+1+0'"
+            ))
+        );
+        assert_eq!(
+            parse_checks_given("+x+1"),
+            Err(String::from("Invalid FEN: check counter is no number"))
+        );
+    }
+}
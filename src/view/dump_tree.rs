@@ -0,0 +1,238 @@
+use std::fs;
+
+use crate::model::tree::Tree;
+use crate::model::tree_dump_filter::TreeDumpFilter;
+use crate::model::tree_dump_format::TreeDumpFormat;
+use crate::model::types::TreeNodeIndex;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
+use crate::search::interpret::get_best_child_index;
+
+use super::print_metrics_json::format_evaluation_json;
+
+pub fn dump_tree(
+    tree: &Tree,
+    path: &str,
+    format: TreeDumpFormat,
+    filter: &TreeDumpFilter,
+) {
+    let content = match format {
+        TreeDumpFormat::Dot => format_tree_dot(tree, filter),
+        TreeDumpFormat::Json => format_tree_json(tree, filter),
+    };
+
+    fs::write(path, content)
+        .unwrap_or_else(|e| panic!("Could not dump tree to {path}: {e}"));
+}
+
+/// Formats the (filtered) tree as Graphviz DOT, render it with e.g.
+/// `dot -Tsvg tree.dot -o tree.svg`.
+pub fn format_tree_dot(tree: &Tree, filter: &TreeDumpFilter) -> String {
+    let mut dot = String::from("digraph tree {\n    node [shape=box];\n");
+    append_node_dot(&mut dot, tree, filter, TREE_NODE_ROOT_INDEX, 0);
+    dot.push_str("}\n");
+    dot
+}
+
+/// Formats the (filtered) tree as one nested JSON object.
+pub fn format_tree_json(tree: &Tree, filter: &TreeDumpFilter) -> String {
+    let mut json = String::new();
+    append_node_json(&mut json, tree, filter, TREE_NODE_ROOT_INDEX, 0);
+    json
+}
+
+fn append_node_dot(
+    dot: &mut String,
+    tree: &Tree,
+    filter: &TreeDumpFilter,
+    node_index: TreeNodeIndex,
+    depth: usize,
+) {
+    let node = tree.get_node(node_index);
+    let score = tree.get_score(node_index);
+    let mut label = format!(
+        "{}\\nvisits {}\\nW/D/B {}/{}/{}\\n{}",
+        get_move_name(tree, node_index),
+        score.get_visits(),
+        score.wins_white,
+        score.draws,
+        score.wins_black,
+        format_evaluation_json(node.evaluation),
+    );
+    if let Some(uct) = get_uct(tree, node_index) {
+        label.push_str(&format!("\\nuct {uct}"));
+    }
+    dot.push_str(&format!("    n{node_index} [label=\"{label}\"];\n"));
+
+    for child_index in get_dumped_children(tree, filter, node_index, depth) {
+        dot.push_str(&format!("    n{node_index} -> n{child_index};\n"));
+        append_node_dot(dot, tree, filter, child_index, depth + 1);
+    }
+}
+
+fn append_node_json(
+    json: &mut String,
+    tree: &Tree,
+    filter: &TreeDumpFilter,
+    node_index: TreeNodeIndex,
+    depth: usize,
+) {
+    let node = tree.get_node(node_index);
+    let score = tree.get_score(node_index);
+    let last_move = if node.parent_index.is_some() {
+        format!("\"{}\"", node.last_move)
+    } else {
+        String::from("null")
+    };
+    let uct = get_uct(tree, node_index)
+        .map_or(String::from("null"), |uct| uct.to_string());
+
+    json.push_str(&format!(
+        "{{\"index\":{},\"move\":{},\"visits\":{},\"draws\":{},\"wins_black\":{},\"wins_white\":{},\"evaluation\":\"{}\",\"uct\":{},\"children\":[",
+        node_index,
+        last_move,
+        score.get_visits(),
+        score.draws,
+        score.wins_black,
+        score.wins_white,
+        format_evaluation_json(node.evaluation),
+        uct,
+    ));
+
+    let children = get_dumped_children(tree, filter, node_index, depth);
+    for (i, child_index) in children.into_iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        append_node_json(json, tree, filter, child_index, depth + 1);
+    }
+
+    json.push_str("]}");
+}
+
+fn get_dumped_children(
+    tree: &Tree,
+    filter: &TreeDumpFilter,
+    node_index: TreeNodeIndex,
+    depth: usize,
+) -> Vec<TreeNodeIndex> {
+    if filter.max_depth.is_some_and(|max_depth| depth >= max_depth) {
+        return Vec::new();
+    }
+
+    let candidates: Vec<TreeNodeIndex> = if filter.only_pv {
+        get_best_child_index(tree, node_index).into_iter().collect()
+    } else {
+        tree.get_node(node_index).get_child_indices().collect()
+    };
+
+    candidates
+        .into_iter()
+        .filter(|c| tree.get_score(*c).get_visits() >= filter.min_visits)
+        .collect()
+}
+
+fn get_move_name(tree: &Tree, node_index: TreeNodeIndex) -> String {
+    let node = tree.get_node(node_index);
+    if node.parent_index.is_some() {
+        node.last_move.to_string()
+    } else {
+        String::from("root")
+    }
+}
+
+/// The UCT value is not defined for the root.
+fn get_uct(tree: &Tree, node_index: TreeNodeIndex) -> Option<u32> {
+    tree.get_node(node_index)
+        .parent_index
+        .map(|_| tree.calculate_uct(node_index))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::board::Board;
+    use crate::model::r#move::Move;
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    fn build_tree() -> Tree {
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(Board::new(), Move::from_to(E2, E4), 0);
+        tree.add_node(Board::new(), Move::from_to(D2, D4), 0);
+        tree.add_node(Board::new(), Move::from_to(E7, E5), 1);
+        tree.get_score_mut(0).wins_white = 3;
+        tree.get_score_mut(0).draws = 1;
+        tree.get_score_mut(1).wins_white = 3;
+        tree.get_score_mut(2).draws = 1;
+        tree.get_score_mut(3).wins_white = 2;
+        tree
+    }
+
+    #[test]
+    fn it_formats_the_whole_tree_as_dot() {
+        let tree = build_tree();
+        let dot = format_tree_dot(&tree, &TreeDumpFilter::default());
+
+        assert!(dot.starts_with("digraph tree {\n"));
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains(
+            "    n0 [label=\"root\\nvisits 4\\nW/D/B 3/1/0\\ninconclusive\"];\n"
+        ));
+        assert!(dot.contains("    n0 -> n1;\n"));
+        assert!(dot.contains("    n0 -> n2;\n"));
+        assert!(dot.contains("    n1 -> n3;\n"));
+        assert!(dot.contains(&format!(
+            "    n1 [label=\"e2e4\\nvisits 3\\nW/D/B 3/0/0\\ninconclusive\\nuct {}\"];\n",
+            tree.calculate_uct(1)
+        )));
+    }
+
+    #[test]
+    fn it_formats_the_tree_as_json_with_max_depth() {
+        let tree = build_tree();
+        let filter = TreeDumpFilter {
+            max_depth: Some(1),
+            ..TreeDumpFilter::default()
+        };
+
+        assert_eq!(
+            format_tree_json(&tree, &filter),
+            format!(
+                "{{\"index\":0,\"move\":null,\"visits\":4,\"draws\":1,\"wins_black\":0,\"wins_white\":3,\"evaluation\":\"inconclusive\",\"uct\":null,\"children\":[\
+                {{\"index\":1,\"move\":\"e2e4\",\"visits\":3,\"draws\":0,\"wins_black\":0,\"wins_white\":3,\"evaluation\":\"inconclusive\",\"uct\":{},\"children\":[]}},\
+                {{\"index\":2,\"move\":\"d2d4\",\"visits\":1,\"draws\":1,\"wins_black\":0,\"wins_white\":0,\"evaluation\":\"inconclusive\",\"uct\":{},\"children\":[]}}]}}",
+                tree.calculate_uct(1),
+                tree.calculate_uct(2),
+            )
+        );
+    }
+
+    #[test]
+    fn it_skips_nodes_with_too_few_visits() {
+        let tree = build_tree();
+        let filter = TreeDumpFilter {
+            min_visits: 2,
+            ..TreeDumpFilter::default()
+        };
+        let dot = format_tree_dot(&tree, &filter);
+
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n1 -> n3;"));
+        assert!(!dot.contains("n2"));
+    }
+
+    #[test]
+    fn it_dumps_only_the_principal_variation() {
+        let mut tree = build_tree();
+        tree.get_score_mut(2).draws = 5;
+        let filter = TreeDumpFilter {
+            only_pv: true,
+            ..TreeDumpFilter::default()
+        };
+        let dot = format_tree_dot(&tree, &filter);
+
+        assert!(dot.contains("n0 -> n2;"));
+        assert!(!dot.contains("n1"));
+        assert!(!dot.contains("n3"));
+    }
+}
@@ -0,0 +1,153 @@
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+
+const STARTING_POSITION: &str =
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const MAX_LINE_LENGTH: usize = 80;
+
+/// Formats a game in the PGN export format. The tag pairs are written in the
+/// given order, followed by the `Result`, `Variant` and (for games not
+/// starting from the initial position) the `SetUp` and `FEN` tags.
+/// See: https://www.chessprogramming.org/Portable_Game_Notation
+pub fn format_pgn(
+    tags: &[(&str, String)],
+    start_board: &Board,
+    sans: &[String],
+    result: BoardEvaluation,
+) -> String {
+    let result = format_result(result);
+    let fen = start_board.to_fen();
+    let mut pgn = String::new();
+
+    for (name, value) in tags {
+        pgn.push_str(&format!("[{name} \"{value}\"]\n"));
+    }
+    pgn.push_str(&format!("[Result \"{result}\"]\n"));
+    pgn.push_str(&format!(
+        "[Variant \"{}\"]\n",
+        start_board.variant.get_pgn_name()
+    ));
+    if fen != STARTING_POSITION {
+        pgn.push_str("[SetUp \"1\"]\n");
+        pgn.push_str(&format!("[FEN \"{fen}\"]\n"));
+    }
+    pgn.push('\n');
+
+    let mut tokens = Vec::new();
+    let mut fullmove = start_board.fullmove;
+    let mut color = start_board.our_color;
+
+    for (i, san) in sans.iter().enumerate() {
+        if color == Color::White {
+            tokens.push(format!("{fullmove}."));
+        } else if i == 0 {
+            tokens.push(format!("{fullmove}..."));
+        }
+        tokens.push(san.clone());
+
+        if color == Color::Black {
+            fullmove += 1;
+            color = Color::White;
+        } else {
+            color = Color::Black;
+        }
+    }
+    tokens.push(String::from(result));
+
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + 1 + token.len() > MAX_LINE_LENGTH {
+            pgn.push_str(&line);
+            pgn.push('\n');
+            line.clear();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&token);
+    }
+    pgn.push_str(&line);
+    pgn.push_str("\n\n");
+
+    pgn
+}
+
+pub fn format_result(result: BoardEvaluation) -> &'static str {
+    match result {
+        BoardEvaluation::Draw => "1/2-1/2",
+        BoardEvaluation::Inconclusive => "*",
+        BoardEvaluation::WinBlack => "0-1",
+        BoardEvaluation::WinWhite => "1-0",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::variant::Variant;
+
+    use super::*;
+
+    fn sans(moves: &str) -> Vec<String> {
+        moves.split(' ').map(String::from).collect()
+    }
+
+    #[test]
+    fn it_formats_a_game_from_the_starting_position() {
+        let pgn = format_pgn(
+            &[("White", String::from("A")), ("Black", String::from("B"))],
+            &Board::new(),
+            &sans("Nf3 f6 Ng5 fxg5"),
+            BoardEvaluation::Draw,
+        );
+
+        assert_eq!(
+            pgn,
+            "[White \"A\"]\n[Black \"B\"]\n[Result \"1/2-1/2\"]\n[Variant \"Atomic\"]\n\n1. Nf3 f6 2. Ng5 fxg5 1/2-1/2\n\n"
+        );
+    }
+
+    #[test]
+    fn it_formats_a_game_starting_with_black_to_move() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/3RK3 b - - 0 7");
+        let pgn = format_pgn(
+            &[],
+            &board,
+            &sans("Kf7 Rd7#"),
+            BoardEvaluation::WinWhite,
+        );
+
+        assert_eq!(
+            pgn,
+            "[Result \"1-0\"]\n[Variant \"Atomic\"]\n[SetUp \"1\"]\n[FEN \"4k3/8/8/8/8/8/8/3RK3 b - - 0 7\"]\n\n7... Kf7 8. Rd7# 1-0\n\n"
+        );
+    }
+
+    #[test]
+    fn it_writes_the_variant_of_the_board() {
+        let board = Board::from_fen_with_variant(
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            Variant::Standard,
+        );
+        let pgn = format_pgn(&[], &board, &sans("e4"), BoardEvaluation::Draw);
+
+        assert_eq!(
+            pgn,
+            "[Result \"1/2-1/2\"]\n[Variant \"Standard\"]\n\n1. e4 1/2-1/2\n\n"
+        );
+    }
+
+    #[test]
+    fn it_wraps_long_move_texts() {
+        let moves = vec![String::from("Nf3 Nf6 Ng1 Ng8"); 10].join(" ");
+        let pgn = format_pgn(
+            &[],
+            &Board::new(),
+            &sans(&moves),
+            BoardEvaluation::Inconclusive,
+        );
+
+        assert!(pgn.lines().all(|l| l.len() <= MAX_LINE_LENGTH));
+        assert!(pgn.ends_with(" *\n\n"));
+    }
+}
@@ -0,0 +1,163 @@
+use crate::model::board::Board;
+use crate::model::piece_type::PieceType;
+use crate::model::r#move::Move;
+use crate::model::r#move::MoveSpecial;
+use crate::model::square::Square;
+use crate::move_generator::check::is_check;
+use crate::move_generator::legal_moves::generate_moves;
+use crate::move_generator::make_move::make_move;
+
+/// Formats a legal move in standard algebraic notation, e.g. "Nbd7", "exd6",
+/// "O-O" or "e8=Q+". Exploding the enemy king is marked like a mate.
+/// See: https://en.wikipedia.org/wiki/Algebraic_notation_(chess)
+pub fn format_san(board: &Board, m: &Move) -> String {
+    let mut board = board.clone();
+    let legal_moves = generate_moves(&mut board);
+    let mut san = format_san_without_suffix(&board, m, &legal_moves);
+
+    make_move(&mut board, m);
+    if board.get_our_pieces(PieceType::King).is_empty() {
+        san.push('#');
+    } else if is_check(&board) {
+        if generate_moves(&mut board).is_empty() {
+            san.push('#');
+        } else {
+            san.push('+');
+        }
+    }
+
+    san
+}
+
+/// Formats the moves played one after another from the board.
+pub fn format_san_line(board: &Board, moves: &[Move]) -> Vec<String> {
+    let mut board = board.clone();
+
+    moves
+        .iter()
+        .map(|m| {
+            let san = format_san(&board, m);
+            make_move(&mut board, m);
+            san
+        })
+        .collect()
+}
+
+fn format_san_without_suffix(
+    board: &Board,
+    m: &Move,
+    legal_moves: &[Move],
+) -> String {
+    match m.special {
+        Some(MoveSpecial::CastleLongBlack)
+        | Some(MoveSpecial::CastleLongWhite) => return String::from("O-O-O"),
+        Some(MoveSpecial::CastleShortBlack)
+        | Some(MoveSpecial::CastleShortWhite) => return String::from("O-O"),
+        _ => {}
+    }
+
+    let piece = board.pieces.squares.data[m.from as usize]
+        .expect("there must be a piece on the from square");
+    let from = Square::from_index(m.from);
+    let to = Square::from_index(m.to);
+    let is_capture = m.is_en_passant() || board.has_their_color_at(m.to);
+    let capture = if is_capture { "x" } else { "" };
+
+    if piece.is_pawn() {
+        let file = if is_capture {
+            from.file().to_string()
+        } else {
+            String::new()
+        };
+        return format!("{file}{capture}{to}{}", format_promotion(m));
+    }
+
+    let letter = piece.get_fen_char().to_ascii_uppercase();
+    let rivals: Vec<&Move> = legal_moves
+        .iter()
+        .filter(|r| {
+            r.to == m.to
+                && r.from != m.from
+                && board.pieces.squares.data[r.from as usize] == Some(piece)
+        })
+        .collect();
+
+    let rival_squares = rivals.iter().map(|r| Square::from_index(r.from));
+    let disambiguation = if rivals.is_empty() {
+        String::new()
+    } else if rival_squares.clone().all(|r| r.file() != from.file()) {
+        from.file().to_string()
+    } else if rival_squares.clone().all(|r| r.rank() != from.rank()) {
+        from.rank().to_string()
+    } else {
+        from.to_string()
+    };
+
+    format!("{letter}{disambiguation}{capture}{to}")
+}
+
+fn format_promotion(m: &Move) -> &'static str {
+    match m.special {
+        Some(MoveSpecial::PromoteBishopBlack)
+        | Some(MoveSpecial::PromoteBishopWhite) => "=B",
+        Some(MoveSpecial::PromoteKnightBlack)
+        | Some(MoveSpecial::PromoteKnightWhite) => "=N",
+        Some(MoveSpecial::PromoteQueenBlack)
+        | Some(MoveSpecial::PromoteQueenWhite) => "=Q",
+        Some(MoveSpecial::PromoteRookBlack)
+        | Some(MoveSpecial::PromoteRookWhite) => "=R",
+        Some(MoveSpecial::PromoteKingBlack)
+        | Some(MoveSpecial::PromoteKingWhite) => "=K",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_formats_pawn_and_piece_moves() {
+        let board = Board::new();
+        assert_eq!(format_san(&board, &Move::from_to(E2, E4)), "e4");
+        assert_eq!(format_san(&board, &Move::from_to(G1, F3)), "Nf3");
+    }
+
+    #[test]
+    fn it_formats_castling_and_promotions() {
+        let board = Board::from_fen("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(format_san(&board, &Move::castle_short_white()), "O-O");
+        assert_eq!(format_san(&board, &Move::castle_long_white()), "O-O-O");
+        assert_eq!(
+            format_san(&board, &Move::promote_knight_white(B7, B8)),
+            "b8=N"
+        );
+    }
+
+    #[test]
+    fn it_disambiguates_pieces_of_the_same_type() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R4RK1 w - - 0 1");
+        assert_eq!(format_san(&board, &Move::from_to(A1, D1)), "Rad1");
+
+        let board = Board::from_fen("4k3/8/R7/8/8/8/8/R3K3 w - - 0 1");
+        assert_eq!(format_san(&board, &Move::from_to(A1, A3)), "R1a3");
+    }
+
+    #[test]
+    fn it_marks_captures_checks_and_exploded_kings() {
+        let board = Board::from_fen("4k3/8/8/3p4/4P3/8/8/4K2R w - - 0 1");
+        assert_eq!(format_san(&board, &Move::from_to(E4, D5)), "exd5");
+        assert_eq!(format_san(&board, &Move::from_to(H1, H8)), "Rh8+");
+
+        let board = Board::from_fen("3rk3/8/8/8/8/8/8/3RK3 w - - 0 1");
+        assert_eq!(format_san(&board, &Move::from_to(D1, D8)), "Rxd8#");
+    }
+
+    #[test]
+    fn it_formats_a_line_of_moves() {
+        let moves = [Move::from_to(G1, F3), Move::from_to(D7, D5)];
+        assert_eq!(format_san_line(&Board::new(), &moves), vec!["Nf3", "d5"]);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::model::board::Board;
+use crate::model::r#move::Move;
+use crate::model::types::square_names::SQUARE_NAMES;
+use crate::move_generator::king_moves::get_castle_rook_squares;
+use crate::move_generator::make_move::make_move;
+
+/// Formats a move in UCI notation, e.g. "e2e4" or "a7a8q". In Chess960
+/// (`UCI_Chess960`) castling is written as the king taking its own rook, e.g.
+/// "e1h1" instead of "e1g1", because "f1g1" could be a king move as well.
+pub fn format_uci(board: &Board, m: &Move) -> String {
+    if m.is_castle() && board.castle.chess960 {
+        return format_king_takes_rook(board, m);
+    }
+
+    m.to_string()
+}
+
+/// Formats a castling move as the king taking its own rook.
+pub fn format_king_takes_rook(board: &Board, m: &Move) -> String {
+    let (rook_from, _) = get_castle_rook_squares(board, m);
+    format!(
+        "{}{}",
+        SQUARE_NAMES[m.from as usize], SQUARE_NAMES[rook_from as usize]
+    )
+}
+
+/// Formats the moves of a line which starts at the board.
+pub fn format_uci_line(board: &Board, moves: &[Move]) -> Vec<String> {
+    let mut board = board.clone();
+
+    moves
+        .iter()
+        .map(|m| {
+            let uci = format_uci(&board, m);
+            make_move(&mut board, m);
+            uci
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::r#move::MoveSpecial;
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_formats_castles_by_the_king_destination_in_standard_chess() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+        assert_eq!(format_uci(&board, &Move::castle_short_white()), "e1g1");
+        assert_eq!(format_uci(&board, &Move::from_to(A1, A8)), "a1a8");
+    }
+
+    #[test]
+    fn it_formats_castles_as_the_king_taking_its_rook_in_chess960() {
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1");
+        board.castle.chess960 = true;
+        assert_eq!(format_uci(&board, &Move::castle_short_white()), "e1h1");
+        assert_eq!(format_uci(&board, &Move::castle_long_white()), "e1a1");
+
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/6KR w H - 0 1");
+        let m = Move::castle(MoveSpecial::CastleShortWhite, G1);
+        assert_eq!(format_uci_line(&board, &[m]), vec!["g1h1"]);
+    }
+}
@@ -1,16 +1,33 @@
+use std::time::Duration;
+
 use crate::model::args::Args;
 use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
 use crate::model::metrics_level::MetricsLevel;
 use crate::model::tree::Tree;
+use crate::model::tree_node_metrics::TreeNodeMetrics;
 use crate::model::types::square_names::SQUARE_NAMES;
 use crate::search::interpret::get_search_result;
 
-pub fn print_metrics(tree: &Tree, iteration: u64, args: &Args) {
+use super::print_metrics_json::print_metrics_json;
+use super::print_metrics_json::print_result_json;
+
+pub fn print_metrics(
+    tree: &Tree,
+    iteration: u64,
+    args: &Args,
+    elapsed: Duration,
+) {
     if args.metrics_level == MetricsLevel::Silent {
         return;
     }
 
-    if iteration % 50 != 0 {
+    if !iteration.is_multiple_of(args.report_interval) {
+        return;
+    }
+
+    if args.metrics_level == MetricsLevel::Json {
+        print_metrics_json(tree, iteration, elapsed);
         return;
     }
 
@@ -80,4 +97,53 @@ pub fn print_metrics(tree: &Tree, iteration: u64, args: &Args) {
         print!("|{:05}", info.score.wins_white);
     }
     println!("|");
+
+    print_multi_pv(&infos, tree.get_root().our_color, args.multi_pv);
+}
+
+pub fn print_result(
+    tree: &Tree,
+    iteration: u64,
+    args: &Args,
+    elapsed: Duration,
+) {
+    if args.metrics_level == MetricsLevel::Json {
+        print_result_json(tree, iteration, elapsed);
+    }
+}
+
+fn print_multi_pv(
+    infos: &[TreeNodeMetrics],
+    our_color: Color,
+    multi_pv: usize,
+) {
+    for (i, info) in infos.iter().take(multi_pv).enumerate() {
+        let visits = info.score.get_visits();
+        let wins = match our_color {
+            Color::Black => info.score.wins_black,
+            Color::White => info.score.wins_white,
+        };
+        let win_rate = if visits == 0 {
+            0.0
+        } else {
+            100.0 * wins as f32 / visits as f32
+        };
+        let proven = match info.evaluation {
+            BoardEvaluation::Draw => "0    ",
+            BoardEvaluation::Inconclusive => "?    ",
+            BoardEvaluation::WinBlack => "Black",
+            BoardEvaluation::WinWhite => "White",
+        };
+        let pv: Vec<String> = info.pv.iter().map(|m| m.to_string()).collect();
+
+        println!(
+            "multipv {} |{:<5}|visits {:05}|win rate {:>5.1}%|proven {}|pv {}",
+            i + 1,
+            info.last_move.to_string(),
+            visits,
+            win_rate,
+            proven,
+            pv.join(" ")
+        );
+    }
 }
@@ -0,0 +1,123 @@
+use std::time::Duration;
+
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::tree::Tree;
+use crate::model::tree_node_metrics::TreeNodeMetrics;
+use crate::search::interpret::get_search_result;
+
+/// Prints one JSON object (JSON Lines) per report interval.
+pub fn print_metrics_json(tree: &Tree, iteration: u64, elapsed: Duration) {
+    println!(
+        "{}",
+        format_metrics_json("report", tree, iteration, elapsed)
+    );
+}
+
+/// Prints the final JSON object after the search has finished.
+pub fn print_result_json(tree: &Tree, iteration: u64, elapsed: Duration) {
+    println!(
+        "{}",
+        format_metrics_json("result", tree, iteration, elapsed)
+    );
+}
+
+pub fn format_metrics_json(
+    kind: &str,
+    tree: &Tree,
+    iteration: u64,
+    elapsed: Duration,
+) -> String {
+    let infos = get_search_result(tree);
+    let seconds = elapsed.as_secs_f64();
+    let tree_size = tree.get_size();
+
+    let (nodes_per_second, iterations_per_second) = if seconds > 0.0 {
+        (tree_size as f64 / seconds, iteration as f64 / seconds)
+    } else {
+        (0.0, 0.0)
+    };
+
+    let best_move = infos
+        .first()
+        .map_or(String::from("null"), |i| format!("\"{}\"", i.last_move));
+
+    let moves: Vec<String> = infos.iter().map(format_move_json).collect();
+
+    format!(
+        "{{\"type\":\"{}\",\"iteration\":{},\"tree_size\":{},\"elapsed_ms\":{},\"nodes_per_second\":{:.1},\"iterations_per_second\":{:.1},\"best_move\":{},\"moves\":[{}]}}",
+        kind,
+        iteration,
+        tree_size,
+        elapsed.as_millis(),
+        nodes_per_second,
+        iterations_per_second,
+        best_move,
+        moves.join(",")
+    )
+}
+
+fn format_move_json(info: &TreeNodeMetrics) -> String {
+    let total = info.score.get_visits();
+    let score = if total == 0 {
+        0.0
+    } else {
+        let score = info.score.wins_white as f64 - info.score.wins_black as f64;
+        100.0 * score / total as f64
+    };
+    let evaluation = format_evaluation_json(info.evaluation);
+    let pv: Vec<String> = info.pv.iter().map(|m| format!("\"{m}\"")).collect();
+
+    format!(
+        "{{\"move\":\"{}\",\"evaluation\":\"{}\",\"visits\":{},\"draws\":{},\"wins_black\":{},\"wins_white\":{},\"score\":{:.1},\"pv\":[{}]}}",
+        info.last_move,
+        evaluation,
+        total,
+        info.score.draws,
+        info.score.wins_black,
+        info.score.wins_white,
+        score,
+        pv.join(",")
+    )
+}
+
+pub fn format_evaluation_json(evaluation: BoardEvaluation) -> &'static str {
+    match evaluation {
+        BoardEvaluation::Draw => "draw",
+        BoardEvaluation::Inconclusive => "inconclusive",
+        BoardEvaluation::WinBlack => "win_black",
+        BoardEvaluation::WinWhite => "win_white",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::board::Board;
+    use crate::model::r#move::Move;
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    #[test]
+    fn it_formats_a_report_of_an_unexpanded_tree() {
+        let tree = Tree::new(Board::new());
+        assert_eq!(
+            format_metrics_json("report", &tree, 0, Duration::ZERO),
+            "{\"type\":\"report\",\"iteration\":0,\"tree_size\":1,\"elapsed_ms\":0,\"nodes_per_second\":0.0,\"iterations_per_second\":0.0,\"best_move\":null,\"moves\":[]}"
+        );
+    }
+
+    #[test]
+    fn it_formats_a_result_with_move_statistics() {
+        let mut tree = Tree::new(Board::new());
+        tree.add_node(Board::new(), Move::from_to(E2, E4), 0);
+        tree.get_score_mut(0).wins_white = 3;
+        tree.get_score_mut(0).draws = 1;
+        tree.get_score_mut(1).wins_white = 3;
+        tree.get_score_mut(1).draws = 1;
+
+        assert_eq!(
+            format_metrics_json("result", &tree, 4, Duration::from_secs(2)),
+            "{\"type\":\"result\",\"iteration\":4,\"tree_size\":2,\"elapsed_ms\":2000,\"nodes_per_second\":1.0,\"iterations_per_second\":2.0,\"best_move\":\"e2e4\",\"moves\":[{\"move\":\"e2e4\",\"evaluation\":\"inconclusive\",\"visits\":4,\"draws\":1,\"wins_black\":0,\"wins_white\":3,\"score\":75.0,\"pv\":[\"e2e4\"]}]}"
+        );
+    }
+}
@@ -0,0 +1,459 @@
+use std::io::stdout;
+use std::io::Stdout;
+use std::io::Write;
+use std::time::Duration;
+use std::time::Instant;
+
+use crossterm::cursor::Hide;
+use crossterm::cursor::MoveTo;
+use crossterm::cursor::Show;
+use crossterm::event::poll;
+use crossterm::event::read;
+use crossterm::event::Event;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEventKind;
+use crossterm::execute;
+use crossterm::queue;
+use crossterm::terminal::disable_raw_mode;
+use crossterm::terminal::enable_raw_mode;
+use crossterm::terminal::size;
+use crossterm::terminal::Clear;
+use crossterm::terminal::ClearType;
+use crossterm::terminal::EnterAlternateScreen;
+use crossterm::terminal::LeaveAlternateScreen;
+
+use crate::model::args::Args;
+use crate::model::board::Board;
+use crate::model::board_evaluation::BoardEvaluation;
+use crate::model::color::Color;
+use crate::model::tree::Tree;
+use crate::model::types::TreeNodeIndex;
+use crate::model::types::TREE_NODE_ROOT_INDEX;
+use crate::move_generator::make_move::make_move;
+use crate::search::interpret::get_principal_variation_moves;
+
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const VISIT_BAR_WIDTH: u64 = 20;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SortColumn {
+    Visits,
+    Score,
+    Move,
+}
+
+/// What the user navigated to.
+struct DashboardState {
+    /// Nodes the user drilled into, starting with the root.
+    path: Vec<TreeNodeIndex>,
+    selected_row: usize,
+    sort_column: SortColumn,
+}
+
+/// Full screen dashboard of a running search. The terminal is restored when
+/// the dashboard is dropped.
+pub struct Tui {
+    last_draw: Option<Instant>,
+    /// Prunings of the tree seen so far, the path is reset after a pruning.
+    prunings: u64,
+    state: DashboardState,
+    stdout: Stdout,
+}
+
+impl Tui {
+    pub fn start() -> Self {
+        let mut stdout = stdout();
+        enable_raw_mode().expect("Could not enable raw terminal mode");
+        execute!(stdout, EnterAlternateScreen, Hide)
+            .expect("Could not enter the alternate screen");
+
+        Self {
+            last_draw: None,
+            prunings: 0,
+            state: DashboardState {
+                path: vec![TREE_NODE_ROOT_INDEX],
+                selected_row: 0,
+                sort_column: SortColumn::Visits,
+            },
+            stdout,
+        }
+    }
+
+    /// Handles pending key presses and redraws the screen if necessary,
+    /// returns false if the user wants to stop the search.
+    pub fn update(
+        &mut self,
+        tree: &Tree,
+        iteration: u64,
+        args: &Args,
+        elapsed: Duration,
+    ) -> bool {
+        let mut redraw = self
+            .last_draw
+            .is_none_or(|d| d.elapsed() >= REDRAW_INTERVAL);
+
+        if self.prunings != tree.get_prunings() {
+            self.prunings = tree.get_prunings();
+            self.state.path = vec![TREE_NODE_ROOT_INDEX];
+            self.state.selected_row = 0;
+        }
+
+        while poll(Duration::ZERO).unwrap_or(false) {
+            match self.handle_event(tree) {
+                Some(false) => return false,
+                Some(true) => redraw = true,
+                None => {}
+            }
+        }
+
+        if redraw {
+            self.draw(tree, iteration, args, elapsed, "searching");
+        }
+
+        true
+    }
+
+    /// Keeps the final state of the search on screen until the user quits.
+    pub fn wait_for_quit(
+        &mut self,
+        tree: &Tree,
+        iteration: u64,
+        args: &Args,
+        elapsed: Duration,
+    ) {
+        loop {
+            self.draw(tree, iteration, args, elapsed, "finished");
+            if self.handle_event(tree) == Some(false) {
+                return;
+            }
+        }
+    }
+
+    /// Blocks until the next event, returns whether to continue if the event
+    /// was a key press.
+    fn handle_event(&mut self, tree: &Tree) -> Option<bool> {
+        let Ok(Event::Key(key)) = read() else {
+            return Some(true);
+        };
+        if key.kind == KeyEventKind::Release {
+            return None;
+        }
+
+        let state = &mut self.state;
+        let node_index = *state.path.last().unwrap();
+        let rows = get_sorted_children(tree, node_index, state.sort_column);
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Some(false),
+            KeyCode::Up | KeyCode::Char('k') => {
+                state.selected_row = state.selected_row.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if state.selected_row + 1 < rows.len() {
+                    state.selected_row += 1;
+                }
+            }
+            KeyCode::Right | KeyCode::Enter | KeyCode::Char('l') => {
+                if let Some(child_index) = rows.get(state.selected_row) {
+                    if tree.get_node(*child_index).has_children() {
+                        state.path.push(*child_index);
+                        state.selected_row = 0;
+                    }
+                }
+            }
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => {
+                if state.path.len() > 1 {
+                    state.path.pop();
+                    state.selected_row = 0;
+                }
+            }
+            KeyCode::Char('s') => {
+                state.sort_column = match state.sort_column {
+                    SortColumn::Visits => SortColumn::Score,
+                    SortColumn::Score => SortColumn::Move,
+                    SortColumn::Move => SortColumn::Visits,
+                };
+            }
+            _ => return None,
+        }
+
+        Some(true)
+    }
+
+    fn draw(
+        &mut self,
+        tree: &Tree,
+        iteration: u64,
+        args: &Args,
+        elapsed: Duration,
+        status: &str,
+    ) {
+        let (width, height) = size().unwrap_or((80, 24));
+        let lines = format_dashboard(
+            tree,
+            &self.state,
+            iteration,
+            args.max_iterations,
+            elapsed,
+            status,
+        );
+
+        for (y, line) in lines.iter().take(height as usize).enumerate() {
+            let line: String = line.chars().take(width as usize).collect();
+            queue!(
+                self.stdout,
+                MoveTo(0, y as u16),
+                Clear(ClearType::CurrentLine)
+            )
+            .unwrap();
+            write!(self.stdout, "{line}").unwrap();
+        }
+        queue!(self.stdout, Clear(ClearType::FromCursorDown)).unwrap();
+        self.stdout.flush().unwrap();
+
+        self.last_draw = Some(Instant::now());
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = execute!(self.stdout, Show, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+fn format_dashboard(
+    tree: &Tree,
+    state: &DashboardState,
+    iteration: u64,
+    max_iterations: u64,
+    elapsed: Duration,
+    status: &str,
+) -> Vec<String> {
+    let path = &state.path;
+    let selected_row = state.selected_row;
+    let sort_column = state.sort_column;
+    let node_index = *path.last().unwrap();
+    let seconds = elapsed.as_secs_f64();
+    let nodes_per_second = if seconds > 0.0 {
+        tree.get_size() as f64 / seconds
+    } else {
+        0.0
+    };
+    let max_iterations = if max_iterations == u64::MAX {
+        String::from("-")
+    } else {
+        max_iterations.to_string()
+    };
+
+    let path_names: Vec<String> = path
+        .iter()
+        .skip(1)
+        .map(|i| tree.get_node(*i).last_move.to_string())
+        .collect();
+
+    let mut lines = vec![
+        format!(
+            "{status} | iteration {iteration}/{max_iterations} | tree size {} | nodes/sec {nodes_per_second:.1} | elapsed {seconds:.1}s",
+            tree.get_size()
+        ),
+        format!(
+            "node: root{} | sort: {sort_column:?}",
+            path_names
+                .iter()
+                .map(|n| format!(" > {n}"))
+                .collect::<String>()
+        ),
+        String::from(
+            "keys: up/down select | right/enter drill in | left back | s sort | q quit",
+        ),
+    ];
+
+    let board = get_board(tree, path);
+    lines.extend(
+        format!("{:?}", board.pieces.squares)
+            .lines()
+            .map(String::from),
+    );
+    lines.push(String::new());
+
+    let rows = get_sorted_children(tree, node_index, sort_column);
+    let max_visits = rows
+        .iter()
+        .map(|i| tree.get_score(*i).get_visits())
+        .max()
+        .unwrap_or(0);
+
+    lines.push(format!(
+        "  {:<6} {:>8} {:<w$} {:>6} {:>6} {:>6} {}",
+        "move",
+        "visits",
+        "",
+        "W%",
+        "D%",
+        "B%",
+        "proven",
+        w = VISIT_BAR_WIDTH as usize
+    ));
+
+    for (row, child_index) in rows.iter().enumerate() {
+        let child = tree.get_node(*child_index);
+        let child_score = tree.get_score(*child_index);
+        let visits = child_score.get_visits();
+        let bar_len = (VISIT_BAR_WIDTH * visits)
+            .checked_div(max_visits)
+            .unwrap_or(0) as usize;
+        let percent = |n: u64| {
+            if visits == 0 {
+                0.0
+            } else {
+                100.0 * n as f64 / visits as f64
+            }
+        };
+        let proven = match child.evaluation {
+            BoardEvaluation::Draw => "draw",
+            BoardEvaluation::Inconclusive => "",
+            BoardEvaluation::WinBlack => "black wins",
+            BoardEvaluation::WinWhite => "white wins",
+        };
+
+        lines.push(format!(
+            "{} {:<6} {:>8} {:<w$} {:>6.1} {:>6.1} {:>6.1} {}",
+            if row == selected_row { ">" } else { " " },
+            child.last_move.to_string(),
+            visits,
+            "#".repeat(bar_len),
+            percent(child_score.wins_white),
+            percent(child_score.draws),
+            percent(child_score.wins_black),
+            proven,
+            w = VISIT_BAR_WIDTH as usize
+        ));
+    }
+
+    if rows.is_empty() {
+        lines.push(format!(
+            "  no children yet, {} visits",
+            tree.get_score(node_index).get_visits()
+        ));
+    }
+
+    if let Some(selected_index) = rows.get(selected_row) {
+        let pv: Vec<String> =
+            get_principal_variation_moves(tree, *selected_index)
+                .iter()
+                .map(|m| m.to_string())
+                .collect();
+        lines.push(String::new());
+        lines.push(format!("pv: {}", pv.join(" ")));
+    }
+
+    lines
+}
+
+/// Nodes don't store their boards, so the board is replayed from the root.
+fn get_board(tree: &Tree, path: &[TreeNodeIndex]) -> Board {
+    let mut board = tree.get_board().clone();
+    for node_index in path.iter().skip(1) {
+        make_move(&mut board, &tree.get_node(*node_index).get_last_move());
+    }
+    board
+}
+
+fn get_sorted_children(
+    tree: &Tree,
+    node_index: TreeNodeIndex,
+    sort_column: SortColumn,
+) -> Vec<TreeNodeIndex> {
+    let node = tree.get_node(node_index);
+    let mut children: Vec<TreeNodeIndex> =
+        tree.get_node(node_index).get_child_indices().collect();
+
+    // scores are seen from the point of view of the player to move
+    let score = |i: &TreeNodeIndex| {
+        let s = tree.get_score(*i);
+        let visits = s.get_visits().max(1) as f64;
+        let score = (s.wins_white as f64 - s.wins_black as f64) / visits;
+        if node.our_color == Color::White {
+            score
+        } else {
+            -score
+        }
+    };
+
+    match sort_column {
+        SortColumn::Visits => children.sort_by_key(|i| {
+            std::cmp::Reverse(tree.get_score(*i).get_visits())
+        }),
+        SortColumn::Score => {
+            children.sort_by(|a, b| score(b).total_cmp(&score(a)))
+        }
+        SortColumn::Move => {
+            children.sort_by_key(|i| tree.get_node(*i).last_move.to_string())
+        }
+    }
+
+    children
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::r#move::Move;
+    use crate::model::types::square_names::*;
+
+    use super::*;
+
+    fn build_tree() -> Tree {
+        let mut tree = Tree::new(Board::new());
+        let mut board = Board::new();
+        make_move(&mut board, &Move::from_to(E2, E4));
+        tree.add_node(board.clone(), Move::from_to(E2, E4), 0);
+        tree.add_node(Board::new(), Move::from_to(D2, D4), 0);
+        tree.add_node(board.clone(), Move::from_to(E7, E5), 1);
+        tree.get_score_mut(0).wins_white = 4;
+        tree.get_score_mut(1).wins_white = 1;
+        tree.get_score_mut(1).wins_black = 1;
+        tree.get_score_mut(2).wins_white = 2;
+        tree.get_score_mut(3).wins_black = 1;
+        tree.get_node_mut(3).evaluation = BoardEvaluation::WinBlack;
+        tree
+    }
+
+    #[test]
+    fn it_sorts_children_by_visits_score_and_move() {
+        let tree = build_tree();
+        assert_eq!(get_sorted_children(&tree, 0, SortColumn::Visits), [1, 2]);
+        assert_eq!(get_sorted_children(&tree, 0, SortColumn::Score), [2, 1]);
+        assert_eq!(get_sorted_children(&tree, 0, SortColumn::Move), [2, 1]);
+    }
+
+    #[test]
+    fn it_formats_the_dashboard_of_a_drilled_in_node() {
+        let tree = build_tree();
+        let state = DashboardState {
+            path: vec![0, 1],
+            selected_row: 0,
+            sort_column: SortColumn::Visits,
+        };
+        let lines = format_dashboard(
+            &tree,
+            &state,
+            4,
+            u64::MAX,
+            Duration::from_secs(2),
+            "searching",
+        );
+
+        assert_eq!(
+            lines[0],
+            "searching | iteration 4/- | tree size 4 | nodes/sec 2.0 | elapsed 2.0s"
+        );
+        assert_eq!(lines[1], "node: root > e2e4 | sort: Visits");
+        assert_eq!(lines[8], "----P---");
+        assert!(lines.contains(&format!(
+            "> e7e5          1 {}    0.0    0.0  100.0 black wins",
+            "#".repeat(20)
+        )));
+        assert_eq!(lines.last().unwrap(), "pv: e7e5");
+    }
+}